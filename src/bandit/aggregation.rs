@@ -0,0 +1,61 @@
+//! Reward aggregation policies for batch updates.
+//!
+//! When a batch contains several rewards for the same arm, applying them as
+//! N separate updates lets a burst of feedback dominate the arm's estimate.
+//! An [`AggregationPolicy`] decides how those grouped rewards are combined
+//! before they reach the bandit.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::aggregation::{aggregate, AggregationPolicy};
+//!
+//! let batch = [(0, 1.0), (1, 0.5), (0, 3.0)];
+//! let combined = aggregate(&batch, AggregationPolicy::Mean);
+//! assert_eq!(combined, vec![(0, 2.0), (1, 0.5)]);
+//! ```
+
+/// How grouped rewards for a single arm are combined in a batch update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AggregationPolicy {
+    /// Apply every reward as its own update (no aggregation).
+    #[default]
+    PerSample,
+    /// Apply the mean of the arm's rewards as a single update.
+    Mean,
+    /// Apply the maximum of the arm's rewards as a single update.
+    Max,
+}
+
+/// Combines `(arm, reward)` pairs according to `policy`.
+///
+/// For [`AggregationPolicy::PerSample`] the input is returned unchanged.
+/// Otherwise one `(arm, reward)` pair is produced per distinct arm, ordered
+/// by the arm's first appearance in the batch.
+pub fn aggregate(updates: &[(usize, f64)], policy: AggregationPolicy) -> Vec<(usize, f64)> {
+    if policy == AggregationPolicy::PerSample {
+        return updates.to_vec();
+    }
+
+    // (arm, running sum or max, sample count), in first-seen order
+    let mut groups: Vec<(usize, f64, usize)> = Vec::new();
+    for &(arm, reward) in updates {
+        match groups.iter_mut().find(|(a, _, _)| *a == arm) {
+            Some((_, acc, n)) => {
+                *acc = match policy {
+                    AggregationPolicy::Max => acc.max(reward),
+                    _ => *acc + reward,
+                };
+                *n += 1;
+            }
+            None => groups.push((arm, reward, 1)),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(arm, acc, n)| match policy {
+            AggregationPolicy::Mean => (arm, acc / n as f64),
+            _ => (arm, acc),
+        })
+        .collect()
+}
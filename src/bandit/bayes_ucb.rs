@@ -0,0 +1,219 @@
+//! # Bayesian UCB with Gaussian Posteriors
+//!
+//! Each arm's mean reward gets a Normal prior `N(μ₀, σ₀²)`, and rewards are
+//! modelled as that mean plus Gaussian noise of known standard deviation
+//! `σ`. After `n` rewards summing to `S`, the conjugate posterior is
+//! ```text
+//! precision = 1/σ₀² + n/σ²      mean = (μ₀/σ₀² + S/σ²) / precision
+//! ```
+//! Selection picks the arm with the highest posterior `quantile`,
+//! `mean + z_q · std`, so uncertain arms keep an optimistic bonus that shrinks
+//! as `1/√n`. Unlike [`ThompsonSampling`](super::thompson::ThompsonSampling),
+//! which samples Beta posteriors for rewards in `[0, 1]`, selection is
+//! deterministic and rewards may be any real number.
+//!
+//! Ties go to the lowest index, so untried arms (which share the prior) are
+//! visited in index order.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::bayes_ucb::BayesUcb;
+//!
+//! let mut agent = BayesUcb::new(2, 0.95);
+//! let arm = agent.select_arm();
+//! agent.update(arm, 3.0);
+//! let (mean, std) = agent.posterior(arm);
+//! assert!(mean > 0.0 && std < 1.0);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::BanditSnapshot;
+
+/// Bayes-UCB agent with Normal-Normal conjugate posteriors.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BayesUcb {
+    /// Posterior quantile used for selection, in `(0, 1)`.
+    quantile: f64,
+    /// Prior mean `μ₀` of every arm.
+    prior_mean: f64,
+    /// Prior standard deviation `σ₀` of every arm.
+    prior_std: f64,
+    /// Known reward noise standard deviation `σ`.
+    noise_std: f64,
+    /// Number of rewards per arm.
+    counts: Vec<u64>,
+    /// Sum of rewards per arm.
+    sums: Vec<f64>,
+    /// Sample mean reward per arm.
+    values: Vec<f64>,
+}
+
+impl BayesUcb {
+    /// Creates an agent selecting by posterior `quantile`, with a standard
+    /// normal prior and unit reward noise.
+    ///
+    /// # Panics
+    /// Panics if `num_arms == 0` or `quantile` is outside `(0, 1)`.
+    pub fn new(num_arms: usize, quantile: f64) -> Self {
+        assert!(num_arms > 0, "must have at least one arm");
+        assert!(
+            quantile > 0.0 && quantile < 1.0,
+            "quantile must be between 0.0 and 1.0 (exclusive)"
+        );
+        Self {
+            quantile,
+            prior_mean: 0.0,
+            prior_std: 1.0,
+            noise_std: 1.0,
+            counts: vec![0; num_arms],
+            sums: vec![0.0; num_arms],
+            values: vec![0.0; num_arms],
+        }
+    }
+
+    /// Sets every arm's prior to `N(mean, std²)` (builder style).
+    ///
+    /// # Panics
+    /// Panics if `std` is not positive and finite.
+    pub fn with_prior(mut self, mean: f64, std: f64) -> Self {
+        assert!(std > 0.0 && std.is_finite(), "prior std must be positive and finite");
+        self.prior_mean = mean;
+        self.prior_std = std;
+        self
+    }
+
+    /// Sets the reward noise standard deviation (builder style).
+    ///
+    /// # Panics
+    /// Panics if `std` is not positive and finite.
+    pub fn with_noise(mut self, std: f64) -> Self {
+        assert!(std > 0.0 && std.is_finite(), "noise std must be positive and finite");
+        self.noise_std = std;
+        self
+    }
+
+    /// Returns the posterior quantile used for selection.
+    pub fn quantile(&self) -> f64 {
+        self.quantile
+    }
+
+    /// Returns the posterior `(mean, std)` of `arm`'s mean reward.
+    pub fn posterior(&self, arm: usize) -> (f64, f64) {
+        let prior_precision = self.prior_std.powi(-2);
+        let noise_precision = self.noise_std.powi(-2);
+        let precision = prior_precision + self.counts[arm] as f64 * noise_precision;
+        let mean =
+            (self.prior_mean * prior_precision + self.sums[arm] * noise_precision) / precision;
+        (mean, precision.sqrt().recip())
+    }
+
+    /// Returns every arm's posterior quantile, the score selection maximizes.
+    pub fn scores(&self) -> Vec<f64> {
+        let z = normal_quantile(self.quantile);
+        (0..self.counts.len())
+            .map(|arm| {
+                let (mean, std) = self.posterior(arm);
+                mean + z * std
+            })
+            .collect()
+    }
+
+    /// Selects the arm with the highest posterior quantile.
+    pub fn select_arm(&mut self) -> usize {
+        let all: Vec<usize> = (0..self.counts.len()).collect();
+        self.select_arm_among(&all)
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but only ever returns one of `candidates`.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    pub fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        assert!(!candidates.is_empty(), "must have at least one candidate arm");
+        let scores = self.scores();
+        let mut best_arm = candidates[0];
+        for &i in candidates {
+            if scores[i] > scores[best_arm] {
+                best_arm = i;
+            }
+        }
+        best_arm
+    }
+
+    /// Records `reward` for `chosen_arm`.
+    pub fn update(&mut self, chosen_arm: usize, reward: f64) {
+        self.counts[chosen_arm] += 1;
+        self.sums[chosen_arm] += reward;
+        self.values[chosen_arm] = self.sums[chosen_arm] / self.counts[chosen_arm] as f64;
+    }
+
+    /// Returns the number of rewards per arm.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns the sample mean reward per arm (`0.0` if none).
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns an owned, consistent copy of the counts and values.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
+
+/// Inverse of the standard normal CDF for `p` in `(0, 1)`.
+///
+/// Acklam's rational approximation; relative error below 1.2e-9.
+fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969683028665376e1,
+        2.209460984245205e2,
+        -2.759285104469687e2,
+        1.38357751867269e2,
+        -3.066479806614716e1,
+        2.506628277459239,
+    ];
+    const B: [f64; 5] = [
+        -5.447609879822406e1,
+        1.615858368580409e2,
+        -1.556989798598866e2,
+        6.680131188771972e1,
+        -1.328068155288572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784894002430293e-3,
+        -3.223964580411365e-1,
+        -2.400758277161838,
+        -2.549732539343734,
+        4.374664141464968,
+        2.938163982698783,
+    ];
+    const D: [f64; 4] = [
+        7.784695709041462e-3,
+        3.224671290700398e-1,
+        2.445134137142996,
+        3.754408661907416,
+    ];
+    const P_LOW: f64 = 0.02425;
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
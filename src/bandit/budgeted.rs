@@ -0,0 +1,242 @@
+//! # Budgeted Bandit
+//!
+//! Wraps any [`Bandit`] with a per-arm cost and a total budget, for settings
+//! such as ad spend or API quotas where each pull consumes a limited resource.
+//!
+//! * **Selection** only considers arms whose cost fits in the remaining
+//!   budget, and returns `None` once no arm does, unless a fallback arm is set
+//!   with [`BudgetedBandit::set_default_arm`].
+//! * **Update** reports the reward to the inner bandit and charges the arm's
+//!   cost against the budget.
+//!
+//! The cost is charged on update rather than on selection, so a selection
+//! whose reward is never reported does not consume budget. Conversely, when
+//! several selections are outstanding at once their rewards can together
+//! cost more than was left: the charge is still recorded in full, and
+//! [`Budget::overspent`] reports by how much the total was exceeded.
+//!
+//! [`Budget`] holds the accounting on its own, for callers (such as the REST
+//! service) that keep the bandit behind a `dyn Bandit`.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::budgeted::BudgetedBandit;
+//! use rustybrain::bandit::ucb1::Ucb1;
+//!
+//! let mut agent = BudgetedBandit::new(Ucb1::new(2, 1.0), vec![3.0, 1.0], 4.0);
+//! let arm = agent.select_arm().unwrap();
+//! agent.update(arm, 1.0);
+//! assert!(agent.budget().remaining() < 4.0);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::Bandit;
+
+/// Per-arm costs and the budget they are charged against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Budget {
+    /// Cost of one pull of each arm.
+    costs: Vec<f64>,
+    /// Budget available before any pull.
+    total: f64,
+    /// Cost charged so far.
+    spent: f64,
+}
+
+impl Budget {
+    /// Creates a budget of `total` over arms with the given `costs`.
+    ///
+    /// # Panics
+    /// Panics if `costs` is empty, or a cost or `total` is negative or not finite.
+    pub fn new(costs: Vec<f64>, total: f64) -> Self {
+        let budget = Self {
+            costs,
+            total,
+            spent: 0.0,
+        };
+        if let Err(msg) = budget.validate() {
+            panic!("{}", msg);
+        }
+        budget
+    }
+
+    /// Arms whose cost fits in the remaining budget, in index order.
+    pub fn affordable(&self) -> Vec<usize> {
+        let remaining = self.remaining();
+        (0..self.costs.len())
+            .filter(|&arm| self.costs[arm] <= remaining)
+            .collect()
+    }
+
+    /// Selects an arm from `bandit` among the affordable ones, or `None` if
+    /// none is affordable.
+    pub fn select_arm(&self, bandit: &mut dyn Bandit) -> Option<usize> {
+        let candidates = self.affordable();
+        (!candidates.is_empty()).then(|| bandit.select_arm_among(&candidates))
+    }
+
+    /// Selects a slate of up to `k` distinct arms whose combined cost fits in
+    /// the remaining budget, in selection order.
+    ///
+    /// Each slot is filled by the bandit from the arms still affordable after
+    /// paying for the slots before it.
+    pub fn select_arms(&self, bandit: &mut dyn Bandit, k: usize) -> Vec<usize> {
+        let mut remaining = self.remaining();
+        let mut slate = Vec::new();
+        while slate.len() < k {
+            let candidates: Vec<usize> = (0..self.costs.len())
+                .filter(|arm| !slate.contains(arm) && self.costs[*arm] <= remaining)
+                .collect();
+            if candidates.is_empty() {
+                break;
+            }
+            let arm = bandit.select_arm_among(&candidates);
+            remaining -= self.costs[arm];
+            slate.push(arm);
+        }
+        slate
+    }
+
+    /// Charges one pull of `arm`, even if it exceeds the remaining budget
+    /// (see [`overspent`](Self::overspent)).
+    ///
+    /// # Panics
+    /// Panics if `arm` is out of range.
+    pub fn charge(&mut self, arm: usize) {
+        self.spent += self.costs[arm];
+    }
+
+    /// Restores the full budget.
+    pub fn reset(&mut self) {
+        self.spent = 0.0;
+    }
+
+    /// Returns the cost of each arm.
+    pub fn costs(&self) -> &[f64] {
+        &self.costs
+    }
+
+    /// Returns the budget available before any pull.
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+
+    /// Returns the cost charged so far.
+    pub fn spent(&self) -> f64 {
+        self.spent
+    }
+
+    /// Returns the budget left to spend (`0.0` once overspent).
+    pub fn remaining(&self) -> f64 {
+        (self.total - self.spent).max(0.0)
+    }
+
+    /// Returns how far the charges exceed the total budget (`0.0` if they do not).
+    pub fn overspent(&self) -> f64 {
+        (self.spent - self.total).max(0.0)
+    }
+
+    /// Whether no arm fits in the remaining budget.
+    pub fn is_exhausted(&self) -> bool {
+        self.affordable().is_empty()
+    }
+
+    /// Checks the invariants [`Budget::new`] enforces, for budgets restored
+    /// through `Deserialize`.
+    ///
+    /// # Errors
+    /// Returns a description of the first violated invariant.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        if self.costs.is_empty() {
+            return Err("must have at least one arm");
+        }
+        if !self.costs.iter().all(|&c| c >= 0.0 && c.is_finite()) {
+            return Err("costs must be finite and non-negative");
+        }
+        if !(self.total >= 0.0 && self.total.is_finite()) {
+            return Err("budget must be finite and non-negative");
+        }
+        if !(self.spent >= 0.0 && self.spent.is_finite()) {
+            return Err("spent budget must be finite and non-negative");
+        }
+        Ok(())
+    }
+}
+
+/// A [`Bandit`] whose pulls are paid for from a fixed budget.
+#[derive(Debug, Clone)]
+pub struct BudgetedBandit<B: Bandit> {
+    inner: B,
+    budget: Budget,
+    /// Arm returned once the budget is exhausted, if any.
+    default_arm: Option<usize>,
+}
+
+impl<B: Bandit> BudgetedBandit<B> {
+    /// Wraps `inner` with one cost per arm and a `total` budget.
+    ///
+    /// # Panics
+    /// Panics if `costs.len()` differs from the number of arms, or under the
+    /// conditions of [`Budget::new`].
+    pub fn new(inner: B, costs: Vec<f64>, total: f64) -> Self {
+        assert_eq!(
+            costs.len(),
+            inner.counts().len(),
+            "costs must have one entry per arm"
+        );
+        Self {
+            inner,
+            budget: Budget::new(costs, total),
+            default_arm: None,
+        }
+    }
+
+    /// Returns `arm` instead of `None` once the budget is exhausted. The
+    /// default arm bypasses the budget and the inner strategy, but updating
+    /// it still charges its cost.
+    ///
+    /// # Panics
+    /// Panics if `arm` is out of range.
+    pub fn set_default_arm(&mut self, arm: usize) {
+        assert!(arm < self.inner.counts().len(), "default arm out of range");
+        self.default_arm = Some(arm);
+    }
+
+    /// Returns the configured fallback arm, if any.
+    pub fn default_arm(&self) -> Option<usize> {
+        self.default_arm
+    }
+
+    /// Selects an affordable arm, or the [default arm](Self::set_default_arm)
+    /// (`None` if unset) once the budget is exhausted.
+    pub fn select_arm(&mut self) -> Option<usize> {
+        self.budget.select_arm(&mut self.inner).or(self.default_arm)
+    }
+
+    /// Selects an affordable slate of up to `k` arms (see [`Budget::select_arms`]).
+    pub fn select_arms(&mut self, k: usize) -> Vec<usize> {
+        self.budget.select_arms(&mut self.inner, k)
+    }
+
+    /// Reports the reward observed for `arm` and charges its cost.
+    pub fn update(&mut self, arm: usize, reward: f64) {
+        self.inner.update(arm, reward);
+        self.budget.charge(arm);
+    }
+
+    /// Returns the budget accounting.
+    pub fn budget(&self) -> &Budget {
+        &self.budget
+    }
+
+    /// Returns the wrapped bandit.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the inner bandit.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
@@ -0,0 +1,99 @@
+//! Arm cool-down bookkeeping shared by the bandit implementations.
+//!
+//! After an arm is selected it sits out the next `period` selections. The
+//! arm still accepts reward updates while cooling down; only selection is
+//! affected. A `period` of `0` disables the cool-down entirely.
+
+use serde::{Deserialize, Serialize};
+
+/// Tracks when each arm was last selected and which arms are selectable.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Cooldown {
+    /// Number of selections an arm is excluded for after being chosen.
+    period: usize,
+    /// Selection step at which each arm was last chosen.
+    last_selected: Vec<Option<u64>>,
+    /// Total number of selections made so far.
+    step: u64,
+}
+
+impl Cooldown {
+    /// Creates a disabled cool-down tracker for `num_arms` arms.
+    pub(crate) fn new(num_arms: usize) -> Self {
+        Self {
+            period: 0,
+            last_selected: vec![None; num_arms],
+            step: 0,
+        }
+    }
+
+    /// Sets the number of selections an arm is excluded for after being chosen.
+    pub(crate) fn set_period(&mut self, period: usize) {
+        self.period = period;
+    }
+
+    /// Returns the number of arms tracked.
+    pub(crate) fn num_arms(&self) -> usize {
+        self.last_selected.len()
+    }
+
+    /// Returns the configured cool-down period.
+    pub(crate) fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Forgets all past selections, keeping the period.
+    pub(crate) fn reset(&mut self) {
+        self.last_selected.fill(None);
+        self.step = 0;
+    }
+
+    /// Starts tracking one more arm, which has never been selected.
+    pub(crate) fn add_arm(&mut self) {
+        self.last_selected.push(None);
+    }
+
+    /// Stops tracking `arm`; later arms shift down by one index.
+    pub(crate) fn remove_arm(&mut self, arm: usize) {
+        self.last_selected.remove(arm);
+    }
+
+    /// Returns the `candidates` that are not currently cooling down.
+    pub(crate) fn eligible(&self, candidates: &[usize]) -> Vec<usize> {
+        candidates
+            .iter()
+            .copied()
+            .filter(|&arm| self.is_ready(arm))
+            .collect()
+    }
+
+    /// Returns the least-recently selected of `candidates` (never-selected arms first).
+    pub(crate) fn least_recent(&self, candidates: &[usize]) -> usize {
+        let mut best_arm = candidates[0];
+        let mut best_step = None;
+        for &i in candidates {
+            match (self.last_selected[i], best_step) {
+                (None, _) => return i,
+                (Some(s), Some(b)) if s >= b => {}
+                (Some(s), _) => {
+                    best_arm = i;
+                    best_step = Some(s);
+                }
+            }
+        }
+        best_arm
+    }
+
+    /// Records that `arm` was selected at the current step.
+    pub(crate) fn record(&mut self, arm: usize) {
+        self.last_selected[arm] = Some(self.step);
+        self.step += 1;
+    }
+
+    fn is_ready(&self, arm: usize) -> bool {
+        match self.last_selected[arm] {
+            None => true,
+            Some(s) => self.step - s > self.period as u64,
+        }
+    }
+}
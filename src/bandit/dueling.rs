@@ -0,0 +1,227 @@
+//! # Dueling Bandit (Relative UCB)
+//!
+//! For feedback that is only comparative ("A beat B"), such as interleaved
+//! ranking experiments or side-by-side human ratings. Each round the agent
+//! proposes a duel between two arms, and the caller reports which one won.
+//!
+//! [`Rucb`] implements Relative Upper Confidence Bound (Zoghi et al., 2014).
+//! With `W[i][j]` the number of times arm `i` beat arm `j` and `t` the number
+//! of duels so far plus one, every ordered pair gets an optimistic estimate
+//! of the probability that `i` beats `j`:
+//! ```text
+//! U[i][j] = W[i][j] / N + sqrt(α ln t / N),    N = W[i][j] + W[j][i]
+//! ```
+//! with `U[i][j] = 1` for pairs that never met and `U[i][i] = ½`.
+//!
+//! * **Champion:** a random arm among those that might beat every other arm
+//!   (`U[c][j] ≥ ½` for all `j`), or among all arms if there is none.
+//! * **Challenger:** the arm most likely to beat the champion, `argmax_j U[j][c]`.
+//!   Once the champion is clearly best the challenger is the champion itself,
+//!   so the duel degenerates to exploitation.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::dueling::Rucb;
+//!
+//! let mut agent = Rucb::new(3, 0.51, 7);
+//! let (first, second) = agent.select_duel();
+//! agent.update_duel(first, second);
+//! assert_eq!(agent.wins(first, second), u64::from(first != second));
+//! ```
+//!
+//! ## Determinism
+//!
+//! The champion is drawn from a seeded RNG that is serialized with the agent,
+//! so [`Rucb::from_json`] resumes the exact random sequence.
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use super::BanditError;
+
+/// Relative Upper Confidence Bound dueling bandit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Rucb {
+    /// Exploration parameter `α` (> ½).
+    alpha: f64,
+    /// `wins[i][j]`: times arm `i` beat arm `j`.
+    wins: Vec<Vec<u64>>,
+    /// Number of duels reported so far.
+    duels: u64,
+    /// Deterministic random number generator for reproducibility.
+    rng: ChaCha12Rng,
+}
+
+impl Rucb {
+    /// Creates an agent with `num_arms` arms, exploration factor `alpha`, and
+    /// a seeded RNG.
+    ///
+    /// # Panics
+    /// Panics if `num_arms == 0` or `alpha <= 0.5`.
+    pub fn new(num_arms: usize, alpha: f64, seed: u64) -> Self {
+        assert!(num_arms > 0, "must have at least one arm");
+        assert!(alpha > 0.5, "alpha must be greater than 0.5");
+        Self {
+            alpha,
+            wins: vec![vec![0; num_arms]; num_arms],
+            duels: 0,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Proposes the next duel as `(champion, challenger)`.
+    ///
+    /// The two arms are equal once the agent is confident the champion beats
+    /// every other arm.
+    pub fn select_duel(&mut self) -> (usize, usize) {
+        let n = self.wins.len();
+        let upper = self.upper_bounds();
+        let mut candidates: Vec<usize> = (0..n)
+            .filter(|&c| upper[c].iter().all(|&u| u >= 0.5))
+            .collect();
+        if candidates.is_empty() {
+            candidates = (0..n).collect();
+        }
+        let champion = candidates[self.rng.gen_range(0..candidates.len())];
+
+        let mut challenger = champion;
+        for j in 0..n {
+            if upper[j][champion] > upper[challenger][champion] {
+                challenger = j;
+            }
+        }
+        (champion, challenger)
+    }
+
+    /// Records that `winner` beat `loser`. A duel of an arm against itself
+    /// carries no preference and only advances the duel count.
+    ///
+    /// # Panics
+    /// Panics if either arm is out of range.
+    pub fn update_duel(&mut self, winner: usize, loser: usize) {
+        let n = self.wins.len();
+        assert!(winner < n && loser < n, "arm out of range");
+        if winner != loser {
+            self.wins[winner][loser] += 1;
+        }
+        self.duels += 1;
+    }
+
+    /// Like [`update_duel`](Self::update_duel), but rejects out-of-range arms
+    /// instead of panicking.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::ArmOutOfRange`].
+    pub fn try_update_duel(&mut self, winner: usize, loser: usize) -> Result<(), BanditError> {
+        let num_arms = self.wins.len();
+        for arm in [winner, loser] {
+            if arm >= num_arms {
+                return Err(BanditError::ArmOutOfRange { arm, num_arms });
+            }
+        }
+        self.update_duel(winner, loser);
+        Ok(())
+    }
+
+    /// Returns the optimistic estimate `U[i][j]` of `P(i beats j)` for every pair.
+    pub fn upper_bounds(&self) -> Vec<Vec<f64>> {
+        let n = self.wins.len();
+        let log_t = ((self.duels + 1) as f64).ln();
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        if i == j {
+                            return 0.5;
+                        }
+                        let met = (self.wins[i][j] + self.wins[j][i]) as f64;
+                        if met == 0.0 {
+                            return 1.0;
+                        }
+                        self.wins[i][j] as f64 / met + (self.alpha * log_t / met).sqrt()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns how often `i` beat `j` among their duels, or `None` if they
+    /// never met.
+    pub fn preference(&self, i: usize, j: usize) -> Option<f64> {
+        let met = self.wins[i][j] + self.wins[j][i];
+        (met > 0).then(|| self.wins[i][j] as f64 / met as f64)
+    }
+
+    /// Returns, per arm, how many other arms it beats in more than half of
+    /// their duels (its Copeland score).
+    pub fn copeland_scores(&self) -> Vec<usize> {
+        let n = self.wins.len();
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .filter(|&j| self.preference(i, j).is_some_and(|p| p > 0.5))
+                    .count()
+            })
+            .collect()
+    }
+
+    /// Returns the arm with the highest Copeland score (the lowest index on ties).
+    pub fn best_arm(&self) -> usize {
+        let scores = self.copeland_scores();
+        let mut best = 0;
+        for (i, &s) in scores.iter().enumerate() {
+            if s > scores[best] {
+                best = i;
+            }
+        }
+        best
+    }
+
+    /// Returns how often `winner` beat `loser`.
+    pub fn wins(&self, winner: usize, loser: usize) -> u64 {
+        self.wins[winner][loser]
+    }
+
+    /// Returns the number of duels reported so far.
+    pub fn duels(&self) -> u64 {
+        self.duels
+    }
+
+    /// Returns the number of arms.
+    pub fn num_arms(&self) -> usize {
+        self.wins.len()
+    }
+
+    /// Forgets every duel. The RNG continues from its current position.
+    pub fn reset(&mut self) {
+        for row in &mut self.wins {
+            row.fill(0);
+        }
+        self.duels = 0;
+    }
+
+    /// Serializes the win matrix and RNG state to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Rucb state is always serializable")
+    }
+
+    /// Restores an agent previously serialized with [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    /// Returns an error for malformed JSON, a win matrix that is not square
+    /// and non-empty, or an `alpha` that is not a finite number above 0.5.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        use serde::de::Error;
+
+        let agent: Self = serde_json::from_str(json)?;
+        let n = agent.wins.len();
+        if n == 0 || agent.wins.iter().any(|row| row.len() != n) {
+            return Err(serde_json::Error::custom("win matrix must be square and non-empty"));
+        }
+        if !(agent.alpha > 0.5 && agent.alpha.is_finite()) {
+            return Err(serde_json::Error::custom("alpha must be greater than 0.5"));
+        }
+        Ok(agent)
+    }
+}
@@ -10,7 +10,12 @@
 //!
 //! * With probability **ε**, it **explores** by selecting a random arm.  
 //! * With probability **1 − ε**, it **exploits** by choosing the arm with the
-//!   highest estimated mean reward.
+//!   highest estimated mean reward (ties go to the lowest index unless
+//!   [`EpsilonGreedy::set_tie_break`] says otherwise).
+//!
+//! [`EpsilonGreedy::with_policy`] can instead select by softmax (Boltzmann)
+//! exploration, `P(i) ∝ exp(value_i / temperature)`, which explores in
+//! proportion to the estimates rather than uniformly.
 //!
 //! Over time, these estimates converge toward the true expected rewards,
 //! balancing discovery of new options with refinement of known good ones.
@@ -22,6 +27,16 @@
 //! value[i] ← value[i] + (r − value[i]) / count[i]
 //! ```
 //!
+//! For non-stationary rewards, [`EpsilonGreedy::with_step_size`] replaces
+//! `1 / count[i]` with a constant step `α`,
+//!
+//! ```text
+//! value[i] ← value[i] + α · (r − value[i])
+//! ```
+//!
+//! an exponential recency-weighted average that keeps tracking drifting
+//! rewards instead of averaging over all history.
+//!
 //! ## Example
 //!
 //! ```
@@ -42,29 +57,187 @@
 //!
 //! ## Determinism
 //!
-//! The internal RNG (`StdRng`) is seeded with a fixed value for reproducible tests.
+//! The internal RNG (ChaCha12, the generator behind `StdRng`) is seeded with
+//! a fixed value (42) by [`EpsilonGreedy::new`] for reproducible tests. Use
+//! [`EpsilonGreedy::with_seed`] to run independent agents that explore
+//! differently, and [`EpsilonGreedy::reset`] to replay a run from scratch.
+//!
+//! ## Checkpointing
+//!
+//! The agent implements `Serialize`/`Deserialize`, including the exact RNG
+//! state, so [`EpsilonGreedy::to_json`] followed by
+//! [`EpsilonGreedy::from_json`] yields an agent that continues identically.
+//!
+//! ## Epsilon Decay
+//!
+//! [`EpsilonGreedy::with_decay`] (or [`EpsilonGreedy::set_schedule`] on an
+//! existing agent) anneals exploration with a [`DecaySchedule`]: linear,
+//! exponential, or inverse-time (`1/t`).
+//! The effective ε is derived from the total number of updates at selection
+//! time; [`EpsilonGreedy::current_epsilon`] reports it.
+//!
+//! ## Cool-down
+//!
+//! [`EpsilonGreedy::set_cooldown`] excludes a freshly selected arm from the next
+//! `cooldown` selections, both when exploring and when exploiting. If every arm
+//! is cooling down, the least-recently selected arm is returned.
 //!
 //! ## Complexity
 //!
 //! * Selection: **O(k)** to find max over k arms.  
 //! * Update: **O(1)** per reward.
 
-use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use super::aggregation::{aggregate, AggregationPolicy};
+use super::cooldown::Cooldown;
+use super::{
+    all_finite, check_arm_lengths, check_priors, check_update, confidence_interval, index_label,
+    labels_unique, max_ties, sample_variance, sample_weighted, softmax, welford_push, zip_priors,
+    BanditError, BanditSnapshot, TieBreak,
+};
+use crate::metrics::reward_tracker::RewardTracker;
+
+/// How the exploration rate evolves with the total number of updates `t`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DecaySchedule {
+    /// ε stays at its initial value.
+    #[default]
+    Constant,
+    /// ε falls linearly from its initial value to `min` over `steps` updates.
+    Linear { min: f64, steps: u64 },
+    /// ε = max(min, ε₀ · e^(−rate · t)).
+    Exponential { rate: f64, min: f64 },
+    /// ε = max(min, ε₀ · scale / (scale + t)), i.e. ε halves after `scale`
+    /// updates and then falls off as `1/t`.
+    InverseTime { scale: f64, min: f64 },
+}
+
+impl DecaySchedule {
+    /// Returns the exploration rate after `t` updates, starting from `epsilon0`.
+    pub fn epsilon_at(&self, epsilon0: f64, t: u64) -> f64 {
+        match *self {
+            DecaySchedule::Constant => epsilon0,
+            DecaySchedule::Linear { min, steps } => {
+                if t >= steps {
+                    return min;
+                }
+                let progress = t as f64 / steps as f64;
+                (epsilon0 - (epsilon0 - min) * progress).max(min)
+            }
+            DecaySchedule::Exponential { rate, min } => {
+                (epsilon0 * (-rate * t as f64).exp()).max(min)
+            }
+            DecaySchedule::InverseTime { scale, min } => {
+                (epsilon0 * scale / (scale + t as f64)).max(min)
+            }
+        }
+    }
+
+    /// Panics unless the schedule is valid for an initial rate of `epsilon0`.
+    fn validate(&self, epsilon0: f64) {
+        if let Err(msg) = self.check(epsilon0) {
+            panic!("{}", msg);
+        }
+    }
+
+    /// Checks that the schedule is valid for an initial rate of `epsilon0`.
+    fn check(&self, epsilon0: f64) -> Result<(), &'static str> {
+        let min = match *self {
+            DecaySchedule::Constant => epsilon0,
+            DecaySchedule::Linear { min, .. } => min,
+            DecaySchedule::Exponential { rate, min } => {
+                if rate.is_nan() || rate < 0.0 {
+                    return Err("decay rate must be non-negative");
+                }
+                min
+            }
+            DecaySchedule::InverseTime { scale, min } => {
+                if scale.is_nan() || scale <= 0.0 {
+                    return Err("decay scale must be positive");
+                }
+                min
+            }
+        };
+        if !(0.0..=epsilon0).contains(&min) {
+            return Err("minimum epsilon must be between 0.0 and epsilon0");
+        }
+        Ok(())
+    }
+}
+
+/// How [`EpsilonGreedy`] picks an arm.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum SelectionPolicy {
+    /// Explore uniformly with probability ε, otherwise exploit the best estimate.
+    #[default]
+    EpsilonGreedy,
+    /// Boltzmann exploration: pick arm `i` with probability proportional to
+    /// `exp(value_i / temperature)`. ε and its decay are ignored.
+    Softmax { temperature: f64 },
+}
+
+impl SelectionPolicy {
+    /// Checks that a softmax temperature is positive and finite.
+    fn check(&self) -> Result<(), &'static str> {
+        match *self {
+            SelectionPolicy::Softmax { temperature }
+                if !(temperature > 0.0 && temperature.is_finite()) =>
+            {
+                Err("temperature must be positive and finite")
+            }
+            _ => Ok(()),
+        }
+    }
+}
 
 /// ε-Greedy multi-armed bandit agent.
 ///
 /// Maintains average reward estimates for each arm and selects arms
 /// according to the ε-greedy exploration policy.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EpsilonGreedy {
-    /// Exploration probability (0.0 = always exploit, 1.0 = always explore).
+    /// Initial exploration probability (0.0 = always exploit, 1.0 = always explore).
     epsilon: f64,
+    /// How `epsilon` decays with the total number of updates.
+    decay: DecaySchedule,
     /// Number of times each arm has been selected.
     counts: Vec<u64>,
     /// Current estimated mean reward for each arm.
     values: Vec<f64>,
+    /// Sum of squared deviations of each arm's observed rewards from
+    /// `observed_means` (Welford's M2).
+    m2: Vec<f64>,
+    /// Rewards observed per arm, excluding seeded prior counts.
+    #[serde(default)]
+    observed: Vec<u64>,
+    /// Mean of each arm's observed rewards, excluding seeded priors.
+    #[serde(default)]
+    observed_means: Vec<f64>,
     /// Deterministic random number generator for reproducibility.
-    rng: StdRng,
+    rng: ChaCha12Rng,
+    /// Seed `rng` was created from; reused by [`EpsilonGreedy::reset`].
+    seed: u64,
+    /// Per-arm cool-down state (disabled by default).
+    cooldown: Cooldown,
+    /// Optional bounded reward history per arm (disabled by default).
+    arm_history: Option<Vec<RewardTracker>>,
+    /// Pulls every arm needs before [`EpsilonGreedy::try_select_arm`] commits (0 = none).
+    min_pulls: u64,
+    /// Optional human-readable name per arm.
+    #[serde(default)]
+    labels: Option<Vec<String>>,
+    /// How exploitation chooses among arms with equal estimates.
+    #[serde(default)]
+    tie_break: TieBreak,
+    /// Exploration policy used by selection.
+    #[serde(default)]
+    policy: SelectionPolicy,
+    /// Constant learning rate; `None` averages over all rewards.
+    #[serde(default)]
+    step_size: Option<f64>,
 }
 
 impl EpsilonGreedy {
@@ -74,6 +247,14 @@ impl EpsilonGreedy {
     /// - If `num_arms == 0`
     /// - If `epsilon` is outside `[0.0, 1.0]`
     pub fn new(num_arms: usize, epsilon: f64) -> Self {
+        Self::with_seed(num_arms, epsilon, 42)
+    }
+
+    /// Creates a new ε-Greedy agent whose exploration RNG is seeded with `seed`.
+    ///
+    /// # Panics
+    /// Same conditions as [`EpsilonGreedy::new`].
+    pub fn with_seed(num_arms: usize, epsilon: f64, seed: u64) -> Self {
         assert!(num_arms > 0, "must have at least one arm");
         assert!(
             (0.0..=1.0).contains(&epsilon),
@@ -82,25 +263,458 @@ impl EpsilonGreedy {
 
         Self {
             epsilon,
+            decay: DecaySchedule::Constant,
             counts: vec![0; num_arms],
             values: vec![0.0; num_arms],
-            rng: StdRng::seed_from_u64(42), // deterministic seed for reproducibility
+            m2: vec![0.0; num_arms],
+            observed: vec![0; num_arms],
+            observed_means: vec![0.0; num_arms],
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            seed,
+            cooldown: Cooldown::new(num_arms),
+            arm_history: None,
+            min_pulls: 0,
+            labels: None,
+            tie_break: TieBreak::Lowest,
+            policy: SelectionPolicy::EpsilonGreedy,
+            step_size: None,
+        }
+    }
+
+    /// Creates an agent with one arm per label.
+    ///
+    /// # Panics
+    /// Under the same conditions as [`EpsilonGreedy::new`], with `num_arms = labels.len()`.
+    pub fn with_labels(epsilon: f64, labels: Vec<String>) -> Self {
+        let mut agent = Self::new(labels.len(), epsilon);
+        agent.set_labels(labels);
+        agent
+    }
+
+    /// Names the arms, one label per arm.
+    ///
+    /// # Panics
+    /// Panics if `labels.len()` differs from the number of arms or two arms
+    /// share a label.
+    pub fn set_labels(&mut self, labels: Vec<String>) {
+        assert_eq!(labels.len(), self.counts.len(), "labels must have one entry per arm");
+        assert!(labels_unique(&labels), "labels must be unique");
+        self.labels = Some(labels);
+    }
+
+    /// Returns the label of `arm`, or `None` if the arms are unlabeled.
+    pub fn label(&self, arm: usize) -> Option<&str> {
+        self.labels.as_ref()?.get(arm).map(String::as_str)
+    }
+
+    /// Returns the index of the arm labeled `label`.
+    pub fn arm_index(&self, label: &str) -> Option<usize> {
+        self.labels.as_ref()?.iter().position(|l| l == label)
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but also returns the arm's label.
+    pub fn select_labeled(&mut self) -> (usize, Option<&str>) {
+        let arm = self.select_arm();
+        (arm, self.label(arm))
+    }
+
+    /// Records `reward` for the arm labeled `label`.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::UnknownArm`] if no arm has that label, or
+    /// [`BanditError::NonFiniteReward`].
+    pub fn update_labeled(&mut self, label: &str, reward: f64) -> Result<(), BanditError> {
+        let arm = self
+            .arm_index(label)
+            .ok_or_else(|| BanditError::UnknownArm(label.to_string()))?;
+        self.try_update(arm, reward)
+    }
+
+    /// Sets how exploitation chooses among arms with equal estimates
+    /// (default [`TieBreak::Lowest`]).
+    ///
+    /// [`TieBreak::Random`] draws from the agent's seeded RNG, so selections
+    /// stay reproducible.
+    pub fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.tie_break = tie_break;
+    }
+
+    /// Returns the tie-breaking rule.
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    /// Creates an agent whose arms start from historical `(value, count)` priors,
+    /// one pair per arm.
+    ///
+    /// Updates continue the running mean from the seeded counts, so an arm
+    /// with prior `(v, n)` behaves as if it had already seen `n` rewards
+    /// averaging `v`. Variance estimates only cover rewards observed after
+    /// construction (see [`arm_variance`](Self::arm_variance)), and
+    /// [`reset`](Self::reset) clears the priors along with learned statistics.
+    ///
+    /// # Panics
+    /// Under the same conditions as [`EpsilonGreedy::new`], with `num_arms = priors.len()`,
+    /// or if a prior value is not finite or a prior count exceeds
+    /// [`MAX_PRIOR_COUNT`](super::MAX_PRIOR_COUNT).
+    pub fn with_priors(epsilon: f64, priors: Vec<(f64, u64)>) -> Self {
+        if let Err(msg) = check_priors(&priors) {
+            panic!("{}", msg);
         }
+        let mut agent = Self::new(priors.len(), epsilon);
+        (agent.values, agent.counts) = priors.into_iter().unzip();
+        agent
+    }
+
+    /// Like [`with_priors`](Self::with_priors), but takes the prior counts and
+    /// values as separate per-arm slices and reports bad priors as an error.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::InvalidPriors`] if `counts` and `values` differ in
+    /// length, a value is not finite, or a count exceeds
+    /// [`MAX_PRIOR_COUNT`](super::MAX_PRIOR_COUNT).
+    ///
+    /// # Panics
+    /// Under the same conditions as [`EpsilonGreedy::new`], with `num_arms = counts.len()`.
+    pub fn try_with_prior_stats(
+        epsilon: f64,
+        counts: &[u64],
+        values: &[f64],
+    ) -> Result<Self, BanditError> {
+        zip_priors(counts, values).map(|priors| Self::with_priors(epsilon, priors))
+    }
+
+    /// Creates an agent whose exploration rate starts at `epsilon0` and decays
+    /// according to `schedule`.
+    ///
+    /// # Panics
+    /// - Under the same conditions as [`EpsilonGreedy::new`]
+    /// - If the schedule's `min` is outside `[0.0, epsilon0]`
+    /// - If an exponential `rate` is negative or an inverse-time `scale` is
+    ///   not positive
+    ///
+    /// # Example
+    /// ```
+    /// use rustybrain::bandit::epsilon_greedy::{DecaySchedule, EpsilonGreedy};
+    ///
+    /// let schedule = DecaySchedule::Linear { min: 0.05, steps: 100 };
+    /// let mut agent = EpsilonGreedy::with_decay(2, 0.5, schedule);
+    /// for _ in 0..100 {
+    ///     agent.update(0, 1.0);
+    /// }
+    /// assert_eq!(agent.current_epsilon(), 0.05);
+    /// ```
+    pub fn with_decay(num_arms: usize, epsilon0: f64, schedule: DecaySchedule) -> Self {
+        schedule.validate(epsilon0);
+        Self {
+            decay: schedule,
+            ..Self::new(num_arms, epsilon0)
+        }
+    }
+
+    /// Replaces the decay schedule, keeping the initial rate and statistics.
+    ///
+    /// The schedule is evaluated at the current update count, so ε may jump.
+    ///
+    /// # Panics
+    /// Under the same schedule conditions as [`EpsilonGreedy::with_decay`].
+    pub fn set_schedule(&mut self, schedule: DecaySchedule) {
+        schedule.validate(self.epsilon);
+        self.decay = schedule;
+    }
+
+    /// Returns the decay schedule.
+    pub fn schedule(&self) -> DecaySchedule {
+        self.decay
+    }
+
+    /// Switches the agent to `policy` (builder style).
+    ///
+    /// # Panics
+    /// Panics if a softmax temperature is not positive and finite.
+    ///
+    /// # Example
+    /// ```
+    /// use rustybrain::bandit::epsilon_greedy::{EpsilonGreedy, SelectionPolicy};
+    ///
+    /// let agent = EpsilonGreedy::new(3, 0.1)
+    ///     .with_policy(SelectionPolicy::Softmax { temperature: 0.5 });
+    /// let p = agent.selection_probabilities();
+    /// assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    /// ```
+    pub fn with_policy(mut self, policy: SelectionPolicy) -> Self {
+        if let Err(msg) = policy.check() {
+            panic!("{}", msg);
+        }
+        self.policy = policy;
+        self
+    }
+
+    /// Returns the exploration policy.
+    pub fn policy(&self) -> SelectionPolicy {
+        self.policy
+    }
+
+    /// Updates estimates with the constant step `alpha` instead of the
+    /// running mean (builder style).
+    ///
+    /// Each reward then weighs `alpha` and older rewards decay geometrically,
+    /// so the estimates follow drifting rewards. The initial value (`0.0` or
+    /// a prior) keeps a weight of `(1 − alpha)^n` after `n` updates.
+    ///
+    /// The estimates are then no longer sample means, so
+    /// [`arm_variance`](Self::arm_variance) returns `None` and
+    /// [`arm_confidence_interval`](Self::arm_confidence_interval) is unbounded.
+    ///
+    /// # Panics
+    /// Panics if `alpha` is outside `(0.0, 1.0]`.
+    pub fn with_step_size(mut self, alpha: f64) -> Self {
+        assert!(alpha > 0.0 && alpha <= 1.0, "step size must be in (0.0, 1.0]");
+        self.step_size = Some(alpha);
+        self
+    }
+
+    /// Returns the constant step size, or `None` when averaging all rewards.
+    pub fn step_size(&self) -> Option<f64> {
+        self.step_size
+    }
+
+    /// Returns the probability of each arm being chosen by the next
+    /// [`select_arm`](Self::select_arm), ignoring cool-downs and warm-up.
+    pub fn selection_probabilities(&self) -> Vec<f64> {
+        let k = self.values.len();
+        match self.policy {
+            SelectionPolicy::Softmax { temperature } => {
+                let scaled: Vec<f64> = self.values.iter().map(|v| v / temperature).collect();
+                softmax(&scaled)
+            }
+            SelectionPolicy::EpsilonGreedy => {
+                let epsilon = self.current_epsilon();
+                let all: Vec<usize> = (0..k).collect();
+                let tied = max_ties(&all, |i| self.values[i]);
+                let best = match self.tie_break {
+                    TieBreak::Lowest => vec![tied[0]],
+                    TieBreak::Highest => vec![tied[tied.len() - 1]],
+                    TieBreak::Random => tied,
+                };
+                let mut probs = vec![epsilon / k as f64; k];
+                for &i in &best {
+                    probs[i] += (1.0 - epsilon) / best.len() as f64;
+                }
+                probs
+            }
+        }
+    }
+
+    /// Returns the exploration rate the next selection will use.
+    pub fn current_epsilon(&self) -> f64 {
+        let t = self.counts.iter().sum();
+        self.decay.epsilon_at(self.epsilon, t)
+    }
+
+    /// Enables a per-arm reward history holding the last `window` rewards of each arm.
+    ///
+    /// History is off by default so memory stays bounded by the arm count.
+    ///
+    /// # Panics
+    /// Panics if `window == 0`.
+    pub fn with_arm_history(mut self, window: usize) -> Self {
+        self.arm_history = Some(vec![RewardTracker::new(window); self.values.len()]);
+        self
+    }
+
+    /// Returns `(mean, min, max, count)` over the recent rewards of `arm`,
+    /// or `None` if per-arm history is disabled.
+    pub fn arm_stats(&self, arm: usize) -> Option<(f64, f64, f64, usize)> {
+        let t = &self.arm_history.as_ref()?[arm];
+        Some((t.mean(), t.min(), t.max(), t.count()))
+    }
+
+    /// Returns a copy of this agent's learned state with a freshly seeded RNG.
+    ///
+    /// Useful for branching an experiment: the fork starts from the same
+    /// counts and values but explores along its own random sequence.
+    pub fn fork(&self, seed: u64) -> Self {
+        Self {
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            seed,
+            ..self.clone()
+        }
+    }
+
+    /// Returns the seed the exploration RNG was created from.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Clears all learned statistics and re-seeds the RNG with [`seed`](Self::seed).
+    ///
+    /// Configuration (epsilon, cool-down period, history window, warm-up)
+    /// is kept, so the agent replays exactly as if freshly constructed.
+    pub fn reset(&mut self) {
+        self.counts.fill(0);
+        self.values.fill(0.0);
+        self.m2.fill(0.0);
+        self.observed.fill(0);
+        self.observed_means.fill(0.0);
+        self.rng = ChaCha12Rng::seed_from_u64(self.seed);
+        self.cooldown.reset();
+        if let Some(history) = &mut self.arm_history {
+            for t in history.iter_mut() {
+                *t = RewardTracker::new(t.window());
+            }
+        }
+    }
+
+    /// Appends a new, untried arm and returns its index. Existing arms keep
+    /// their statistics.
+    ///
+    /// On a labeled agent the new arm is labeled with its index (suffixed
+    /// with `_1`, `_2`, … if another arm already has that name); use
+    /// [`add_labeled_arm`](Self::add_labeled_arm) to name it.
+    pub fn add_arm(&mut self) -> usize {
+        let arm = self.counts.len();
+        self.counts.push(0);
+        self.values.push(0.0);
+        self.m2.push(0.0);
+        self.observed.push(0);
+        self.observed_means.push(0.0);
+        self.cooldown.add_arm();
+        if let Some(history) = &mut self.arm_history {
+            history.push(RewardTracker::new(history[0].window()));
+        }
+        if let Some(labels) = &mut self.labels {
+            labels.push(index_label(labels, arm));
+        }
+        arm
+    }
+
+    /// Like [`add_arm`](Self::add_arm), but names the new arm `label`.
+    ///
+    /// If the agent was unlabeled, its existing arms are labeled with their indices.
+    ///
+    /// # Panics
+    /// Panics if another arm already carries `label`; see
+    /// [`try_add_labeled_arm`](Self::try_add_labeled_arm).
+    pub fn add_labeled_arm(&mut self, label: String) -> usize {
+        self.try_add_labeled_arm(label).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like [`add_labeled_arm`](Self::add_labeled_arm), but rejects a label
+    /// already in use, counting the index labels an unlabeled agent would get.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::DuplicateLabel`] and leaves the agent unchanged.
+    pub fn try_add_labeled_arm(&mut self, label: String) -> Result<usize, BanditError> {
+        let n = self.counts.len();
+        let taken = match &self.labels {
+            Some(labels) => labels.contains(&label),
+            None => (0..n).any(|i| i.to_string() == label),
+        };
+        if taken {
+            return Err(BanditError::DuplicateLabel(label));
+        }
+        self.labels.get_or_insert_with(|| (0..n).map(|i| i.to_string()).collect());
+        let arm = self.add_arm();
+        if let Some(labels) = &mut self.labels {
+            labels[arm] = label;
+        }
+        Ok(arm)
+    }
+
+    /// Removes `arm`; every later arm shifts down by one index and keeps its
+    /// statistics.
+    ///
+    /// # Panics
+    /// Panics if `arm` is out of range or is the only arm.
+    pub fn remove_arm(&mut self, arm: usize) {
+        assert!(arm < self.counts.len(), "arm out of range");
+        assert!(self.counts.len() > 1, "must have at least one arm");
+        self.counts.remove(arm);
+        self.values.remove(arm);
+        self.m2.remove(arm);
+        self.observed.remove(arm);
+        self.observed_means.remove(arm);
+        self.cooldown.remove_arm(arm);
+        if let Some(history) = &mut self.arm_history {
+            history.remove(arm);
+        }
+        if let Some(labels) = &mut self.labels {
+            labels.remove(arm);
+        }
+    }
+
+    /// Excludes each selected arm from the next `cooldown` selections.
+    ///
+    /// Arms keep accepting updates while cooling down. `0` disables the cool-down.
+    pub fn set_cooldown(&mut self, cooldown: usize) {
+        self.cooldown.set_period(cooldown);
+    }
+
+    /// Returns the configured cool-down period.
+    pub fn cooldown(&self) -> usize {
+        self.cooldown.period()
+    }
+
+    /// Sets how many updates every arm needs before [`try_select_arm`](Self::try_select_arm)
+    /// returns a selection. `0` disables the warm-up.
+    pub fn set_min_pulls(&mut self, min_pulls: u64) {
+        self.min_pulls = min_pulls;
+    }
+
+    /// Returns the configured warm-up pull count.
+    pub fn min_pulls(&self) -> u64 {
+        self.min_pulls
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but returns `None` without
+    /// selecting while any arm has fewer than [`min_pulls`](Self::min_pulls) updates.
+    pub fn try_select_arm(&mut self) -> Option<usize> {
+        if self.counts.iter().any(|&n| n < self.min_pulls) {
+            return None;
+        }
+        Some(self.select_arm())
     }
 
     /// Selects an arm index according to the ε-greedy policy.
     ///
     /// * With probability `epsilon`, a random arm is chosen (exploration).  
     /// * Otherwise, the arm with the highest estimated value is selected (exploitation).
+    ///
+    /// Arms that are cooling down are skipped in both cases.
     pub fn select_arm(&mut self) -> usize {
-        let p: f64 = self.rng.gen();
-        if p < self.epsilon {
-            // Explore
-            self.rng.gen_range(0..self.values.len())
+        let all: Vec<usize> = (0..self.counts.len()).collect();
+        self.select_arm_among(&all)
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but only ever returns one of
+    /// `candidates`, both when exploring and when exploiting.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    pub fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        assert!(!candidates.is_empty(), "must have at least one candidate arm");
+        let eligible = self.cooldown.eligible(candidates);
+        let arm = if eligible.is_empty() {
+            // Every candidate is cooling down
+            self.cooldown.least_recent(candidates)
+        } else if let SelectionPolicy::Softmax { temperature } = self.policy {
+            // Softmax shifts by the maximum, so large values cannot overflow.
+            let scaled: Vec<f64> =
+                eligible.iter().map(|&i| self.values[i] / temperature).collect();
+            sample_weighted(&eligible, &softmax(&scaled), &mut self.rng)
         } else {
-            // Exploit
-            self.argmax()
-        }
+            let p: f64 = self.rng.gen();
+            if p < self.current_epsilon() {
+                // Explore
+                eligible[self.rng.gen_range(0..eligible.len())]
+            } else {
+                // Exploit
+                self.argmax(&eligible)
+            }
+        };
+        self.cooldown.record(arm);
+        arm
     }
 
     /// Updates the reward statistics for the chosen arm.
@@ -118,23 +732,144 @@ impl EpsilonGreedy {
     pub fn update(&mut self, chosen_arm: usize, reward: f64) {
         let n = self.counts[chosen_arm] + 1;
         let value = self.values[chosen_arm];
-        let new_value = value + (reward - value) / n as f64;
+        let step = self.step_size.unwrap_or(1.0 / n as f64);
+        let new_value = value + step * (reward - value);
 
         self.counts[chosen_arm] = n;
         self.values[chosen_arm] = new_value;
+        // Variance is only tracked for running means.
+        if self.step_size.is_none() {
+            welford_push(
+                &mut self.observed[chosen_arm],
+                &mut self.observed_means[chosen_arm],
+                &mut self.m2[chosen_arm],
+                reward,
+            );
+        }
+
+        if let Some(history) = &mut self.arm_history {
+            history[chosen_arm].update(reward);
+        }
     }
 
-    /// Internal helper: returns the index of the arm with the highest estimated reward.
-    fn argmax(&self) -> usize {
-        let mut max_index = 0;
-        let mut max_value = f64::NEG_INFINITY;
-        for (i, &v) in self.values.iter().enumerate() {
-            if v > max_value {
-                max_value = v;
-                max_index = i;
-            }
+    /// Like [`update`](Self::update), but rejects out-of-range arms and
+    /// non-finite rewards instead of panicking or corrupting the estimates.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::ArmOutOfRange`] or [`BanditError::NonFiniteReward`].
+    pub fn try_update(&mut self, chosen_arm: usize, reward: f64) -> Result<(), BanditError> {
+        check_update(self.counts.len(), chosen_arm, reward)?;
+        self.update(chosen_arm, reward);
+        Ok(())
+    }
+
+    /// Internal helper: returns the candidate arm with the highest estimated
+    /// reward, breaking ties according to `tie_break`.
+    fn argmax(&mut self, candidates: &[usize]) -> usize {
+        let tied = max_ties(candidates, |i| self.values[i]);
+        self.tie_break.pick(&tied, &mut self.rng)
+    }
+
+    /// Returns the unbiased sample variance of the rewards `arm` has
+    /// observed, or `None` with fewer than two observations or a constant
+    /// [step size](Self::with_step_size).
+    ///
+    /// Seeded [priors](Self::with_priors) carry no spread, so they are not
+    /// counted as observations.
+    pub fn arm_variance(&self, arm: usize) -> Option<f64> {
+        self.step_size
+            .is_none()
+            .then(|| sample_variance(self.m2[arm], self.observed[arm]))
+            .flatten()
+    }
+
+    /// Returns `mean ± z * sqrt(var / n)` over the rewards `arm` has observed,
+    /// excluding seeded priors.
+    ///
+    /// The interval is `(-inf, inf)` while the arm has fewer than two
+    /// observations, or always with a constant [step size](Self::with_step_size).
+    pub fn arm_confidence_interval(&self, arm: usize, z: f64) -> (f64, f64) {
+        if self.step_size.is_some() {
+            return (f64::NEG_INFINITY, f64::INFINITY);
+        }
+        let (n, mean) = (self.observed[arm], self.observed_means[arm]);
+        confidence_interval(mean, self.m2[arm], n, z)
+    }
+
+    /// Applies a batch of `(arm, reward)` updates, combining rewards for the
+    /// same arm according to `policy` first.
+    ///
+    /// # Example
+    /// ```
+    /// use rustybrain::bandit::aggregation::AggregationPolicy;
+    ///
+    /// let mut agent = rustybrain::bandit::epsilon_greedy::EpsilonGreedy::new(1, 0.0);
+    /// agent.update_batch(&[(0, 1.0), (0, 3.0)], AggregationPolicy::Mean);
+    /// assert_eq!(agent.counts()[0], 1);
+    /// assert_eq!(agent.values()[0], 2.0);
+    /// ```
+    pub fn update_batch(&mut self, updates: &[(usize, f64)], policy: AggregationPolicy) {
+        for (arm, reward) in aggregate(updates, policy) {
+            self.update(arm, reward);
         }
-        max_index
+    }
+
+    /// Checks the invariants the constructors and builders enforce.
+    fn check_config(&self) -> Result<(), &'static str> {
+        if !(0.0..=1.0).contains(&self.epsilon) {
+            return Err("epsilon must be between 0.0 and 1.0");
+        }
+        self.decay.check(self.epsilon)?;
+        self.policy.check()?;
+        if self.step_size.is_some_and(|alpha| !(alpha > 0.0 && alpha <= 1.0)) {
+            return Err("step size must be in (0.0, 1.0]");
+        }
+        Ok(())
+    }
+
+    /// Serializes the full agent state, including its RNG position, to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("EpsilonGreedy state is always serializable")
+    }
+
+    /// Restores an agent previously serialized with [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    /// Returns an error for malformed JSON, inconsistent per-arm state,
+    /// non-finite statistics, or configuration the constructors would reject
+    /// (ε, decay schedule, softmax temperature, step size).
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        use serde::de::Error;
+
+        let mut agent: Self = serde_json::from_str(json)?;
+        agent.check_config().map_err(serde_json::Error::custom)?;
+        if agent.observed.is_empty() && agent.observed_means.is_empty() {
+            // Saved before priors were tracked apart from observations.
+            agent.observed = agent.counts.clone();
+            agent.observed_means = agent.values.clone();
+        }
+        let stats = [&agent.values, &agent.m2, &agent.observed_means];
+        if !stats.into_iter().all(|v| all_finite(v)) {
+            return Err(serde_json::Error::custom("per-arm statistics must be finite"));
+        }
+        if agent.observed.iter().zip(&agent.counts).any(|(o, n)| o > n) {
+            return Err(serde_json::Error::custom("observed counts exceed total counts"));
+        }
+        let mut lengths = vec![
+            agent.counts.len(),
+            agent.values.len(),
+            agent.m2.len(),
+            agent.observed.len(),
+            agent.observed_means.len(),
+            agent.cooldown.num_arms(),
+        ];
+        lengths.extend(agent.arm_history.as_ref().map(Vec::len));
+        lengths.extend(agent.labels.as_ref().map(Vec::len));
+        if agent.labels.as_deref().is_some_and(|labels| !labels_unique(labels)) {
+            return Err(serde_json::Error::custom("labels must be unique"));
+        }
+        check_arm_lengths(&lengths)?;
+        Ok(agent)
     }
 
     /// Returns the number of times each arm has been selected.
@@ -146,4 +881,12 @@ impl EpsilonGreedy {
     pub fn values(&self) -> &[f64] {
         &self.values
     }
-}
\ No newline at end of file
+
+    /// Returns an owned, consistent copy of the per-arm counts and values.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
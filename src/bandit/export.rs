@@ -0,0 +1,34 @@
+//! # Interaction Log Export
+//!
+//! Converts logged bandit interactions into formats understood by external
+//! tools for offline evaluation.
+//!
+//! [`to_vw`] writes Vowpal Wabbit contextual-bandit (`--cb`) lines of the form
+//!
+//! ```text
+//! action:cost:probability |
+//! ```
+//!
+//! VW numbers actions from 1, so arm `i` is written as action `i + 1`, and
+//! VW minimizes cost, so a reward `r` is written as cost `1 − r`. The feature
+//! section is left empty because context-free bandits have no features.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::export::to_vw;
+//!
+//! let log = to_vw(&[(0, 1.0, 0.5), (2, 0.25, 0.1)]);
+//! assert_eq!(log, "1:0:0.5 |\n3:0.75:0.1 |\n");
+//! ```
+
+/// Formats `(arm, reward, selection_probability)` events as VW `--cb` lines.
+///
+/// Each event becomes one newline-terminated line.
+pub fn to_vw(events: &[(usize, f64, f64)]) -> String {
+    events
+        .iter()
+        .map(|&(arm, reward, probability)| {
+            format!("{}:{}:{} |\n", arm + 1, 1.0 - reward, probability)
+        })
+        .collect()
+}
@@ -0,0 +1,113 @@
+//! # Eligibility-Gated Bandit
+//!
+//! Wraps any [`Bandit`] so each selection can be restricted to the arms that
+//! are eligible for the current request (for example, only the models that
+//! support the request's language). Ineligible arms are never returned,
+//! whether the inner strategy is exploring or exploiting.
+//!
+//! Callers that would rather always get an arm can configure a fallback with
+//! [`GatedBandit::set_default_arm`]; it is returned whenever no arm is eligible.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::gated::GatedBandit;
+//! use rustybrain::bandit::ucb1::Ucb1;
+//!
+//! let mut gated = GatedBandit::new(Ucb1::new(3, 1.0));
+//! let arm = gated.select_arm_eligible(&[false, true, true]).unwrap();
+//! assert_ne!(arm, 0);
+//! gated.update(arm, 1.0);
+//! ```
+
+use std::fmt;
+
+use super::Bandit;
+
+/// Reasons a gated selection can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GateError {
+    /// The eligibility mask has no `true` entries.
+    NoEligibleArms,
+    /// The eligibility mask length differs from the number of arms.
+    MaskLength { expected: usize, actual: usize },
+}
+
+impl fmt::Display for GateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GateError::NoEligibleArms => write!(f, "no eligible arms"),
+            GateError::MaskLength { expected, actual } => {
+                write!(f, "eligibility mask has {} entries, expected {}", actual, expected)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GateError {}
+
+/// A [`Bandit`] whose selections are restricted by a per-call eligibility mask.
+#[derive(Debug, Clone)]
+pub struct GatedBandit<B: Bandit> {
+    inner: B,
+    /// Arm returned when no arm is eligible; `None` reports an error instead.
+    default_arm: Option<usize>,
+}
+
+impl<B: Bandit> GatedBandit<B> {
+    /// Wraps `inner`.
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            default_arm: None,
+        }
+    }
+
+    /// Returns `arm` instead of [`GateError::NoEligibleArms`] when the mask
+    /// excludes every arm. The default arm bypasses the mask and the inner strategy.
+    ///
+    /// # Panics
+    /// Panics if `arm` is out of range.
+    pub fn set_default_arm(&mut self, arm: usize) {
+        assert!(arm < self.inner.counts().len(), "default arm out of range");
+        self.default_arm = Some(arm);
+    }
+
+    /// Returns the configured fallback arm, if any.
+    pub fn default_arm(&self) -> Option<usize> {
+        self.default_arm
+    }
+
+    /// Selects an arm among those with `eligible[arm] == true`.
+    ///
+    /// Returns an error if the mask length does not match the number of arms,
+    /// or if no arm is eligible and no [default arm](Self::set_default_arm) is set.
+    pub fn select_arm_eligible(&mut self, eligible: &[bool]) -> Result<usize, GateError> {
+        let num_arms = self.inner.counts().len();
+        if eligible.len() != num_arms {
+            return Err(GateError::MaskLength {
+                expected: num_arms,
+                actual: eligible.len(),
+            });
+        }
+        let candidates: Vec<usize> = (0..num_arms).filter(|&i| eligible[i]).collect();
+        if candidates.is_empty() {
+            return self.default_arm.ok_or(GateError::NoEligibleArms);
+        }
+        Ok(self.inner.select_arm_among(&candidates))
+    }
+
+    /// Reports the reward observed for `arm` to the inner bandit.
+    pub fn update(&mut self, arm: usize, reward: f64) {
+        self.inner.update(arm, reward);
+    }
+
+    /// Returns the wrapped bandit.
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Consumes the wrapper and returns the inner bandit.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
@@ -0,0 +1,288 @@
+//! # Thompson Sampling (Normal-Inverse-Gamma)
+//!
+//! Thompson Sampling for continuous, real-valued rewards such as latency or
+//! revenue. Each arm's rewards are modelled as `N(μ, σ²)` with both `μ` and
+//! `σ²` unknown, under the conjugate Normal-Inverse-Gamma prior
+//! ```text
+//! σ² ~ InvGamma(α, β)      μ | σ² ~ N(μ₀, σ² / κ)
+//! ```
+//! Every arm starts from `μ₀ = 0, κ = 1, α = 1, β = 1` unless
+//! [`with_prior`](GaussianThompson::with_prior) says otherwise.
+//!
+//! * **Selection:** for each arm draw `σ²` and then `μ` from the posterior and
+//!   pick the arm with the largest `μ`.
+//! * **Update:** a reward `x` moves the posterior to
+//!   ```text
+//!   κ' = κ + 1          μ₀' = (κ μ₀ + x) / κ'
+//!   α' = α + ½          β'  = β + κ (x − μ₀)² / 2κ'
+//!   ```
+//!
+//! Unlike [`ThompsonSampling`](super::thompson::ThompsonSampling), rewards are
+//! not clamped, and the posterior variance adapts to how noisy each arm is.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::gaussian_thompson::GaussianThompson;
+//!
+//! let mut agent = GaussianThompson::new(2, 7);
+//! let arm = agent.select_arm();
+//! agent.update(arm, 12.5);
+//! assert!(agent.means()[arm] > 0.0);
+//! ```
+//!
+//! ## Determinism
+//!
+//! As with [`ThompsonSampling`](super::thompson::ThompsonSampling), the seeded
+//! RNG is serialized with the agent, so [`GaussianThompson::from_json`]
+//! resumes the exact random sequence.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use rand_distr::{Distribution, Gamma, Normal};
+use serde::{Deserialize, Serialize};
+
+use super::{check_arm_lengths, check_update, BanditError, BanditSnapshot};
+
+/// Normal-Inverse-Gamma distribution, used for both the prior and each posterior.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NormalInverseGamma {
+    /// Mean `μ₀` of the normal component.
+    pub mean: f64,
+    /// Pseudo-observations behind the mean, `κ`.
+    pub kappa: f64,
+    /// Inverse-gamma shape `α`.
+    pub alpha: f64,
+    /// Inverse-gamma scale `β`.
+    pub beta: f64,
+}
+
+impl Default for NormalInverseGamma {
+    fn default() -> Self {
+        Self {
+            mean: 0.0,
+            kappa: 1.0,
+            alpha: 1.0,
+            beta: 1.0,
+        }
+    }
+}
+
+impl NormalInverseGamma {
+    /// The posterior after observing `reward`.
+    fn updated(&self, reward: f64) -> Self {
+        let kappa = self.kappa + 1.0;
+        let delta = reward - self.mean;
+        Self {
+            mean: self.mean + delta / kappa,
+            kappa,
+            alpha: self.alpha + 0.5,
+            beta: self.beta + self.kappa * delta * delta / (2.0 * kappa),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        self.mean.is_finite()
+            && [self.kappa, self.alpha, self.beta]
+                .iter()
+                .all(|&p| p > 0.0 && p.is_finite())
+    }
+}
+
+/// Normal-Inverse-Gamma Thompson Sampling agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GaussianThompson {
+    /// Prior every arm starts from (and returns to on reset).
+    prior: NormalInverseGamma,
+    /// Current posterior per arm.
+    posteriors: Vec<NormalInverseGamma>,
+    /// Number of updates per arm.
+    counts: Vec<u64>,
+    /// Average observed reward per arm.
+    values: Vec<f64>,
+    /// Deterministic random number generator for reproducibility.
+    rng: ChaCha12Rng,
+}
+
+impl GaussianThompson {
+    /// Creates an agent with `num_arms` arms, the default prior, and a seeded RNG.
+    ///
+    /// # Panics
+    /// Panics if `num_arms == 0`.
+    pub fn new(num_arms: usize, seed: u64) -> Self {
+        assert!(num_arms > 0, "must have at least one arm");
+        let prior = NormalInverseGamma::default();
+        Self {
+            prior,
+            posteriors: vec![prior; num_arms],
+            counts: vec![0; num_arms],
+            values: vec![0.0; num_arms],
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Sets every arm's prior and resets its posterior to it (builder style).
+    ///
+    /// # Panics
+    /// Panics if `mean` is not finite or `kappa`, `alpha`, or `beta` is not
+    /// positive and finite.
+    pub fn with_prior(mut self, prior: NormalInverseGamma) -> Self {
+        assert!(prior.is_valid(), "prior parameters must be finite and positive");
+        self.prior = prior;
+        self.reset();
+        self
+    }
+
+    /// Draws one posterior mean per arm and returns the arm with the largest draw.
+    pub fn select_arm(&mut self) -> usize {
+        let all: Vec<usize> = (0..self.counts.len()).collect();
+        self.select_arm_among(&all)
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but only ever returns one of `candidates`.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    pub fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        assert!(!candidates.is_empty(), "must have at least one candidate arm");
+        let draws = self.sample_means();
+        let mut best = candidates[0];
+        for &i in candidates {
+            if draws[i] > draws[best] {
+                best = i;
+            }
+        }
+        best
+    }
+
+    /// Updates the chosen arm's posterior with a real-valued reward.
+    ///
+    /// `β` grows with the squared reward, so rewards beyond roughly `1e154`
+    /// overflow it. Such an arm is still selectable, with draws falling back
+    /// to its posterior mean; [`try_update`](Self::try_update) rejects those
+    /// rewards instead.
+    pub fn update(&mut self, chosen_arm: usize, reward: f64) {
+        self.posteriors[chosen_arm] = self.posteriors[chosen_arm].updated(reward);
+
+        let n = self.counts[chosen_arm] + 1;
+        self.counts[chosen_arm] = n;
+        self.values[chosen_arm] += (reward - self.values[chosen_arm]) / n as f64;
+    }
+
+    /// Like [`update`](Self::update), but rejects out-of-range arms, non-finite
+    /// rewards, and rewards that would overflow the posterior instead of
+    /// panicking or corrupting it.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::ArmOutOfRange`], [`BanditError::NonFiniteReward`],
+    /// or [`BanditError::RewardOverflow`].
+    pub fn try_update(&mut self, chosen_arm: usize, reward: f64) -> Result<(), BanditError> {
+        self.check_reward(chosen_arm, reward)?;
+        self.update(chosen_arm, reward);
+        Ok(())
+    }
+
+    /// Checks that [`try_update`](Self::try_update) would accept `reward` for
+    /// `arm`, without applying it.
+    ///
+    /// Whether a reward overflows depends on the arm's current posterior, so
+    /// the result only holds for a single update against the current state.
+    /// Checking several rewards up front and applying them afterwards is
+    /// unsound; apply them one by one with `try_update` (to a copy, if all
+    /// must succeed together) instead.
+    pub fn check_reward(&self, arm: usize, reward: f64) -> Result<(), BanditError> {
+        check_update(self.counts.len(), arm, reward)?;
+        if !self.posteriors[arm].updated(reward).is_valid() {
+            return Err(BanditError::RewardOverflow(reward));
+        }
+        Ok(())
+    }
+
+    /// Returns a copy of this agent's posteriors with a freshly seeded RNG.
+    pub fn fork(&self, seed: u64) -> Self {
+        Self {
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            ..self.clone()
+        }
+    }
+
+    /// Resets every posterior to the prior and clears the statistics.
+    ///
+    /// The RNG continues from its current position.
+    pub fn reset(&mut self) {
+        self.posteriors.fill(self.prior);
+        self.counts.fill(0);
+        self.values.fill(0.0);
+    }
+
+    /// Returns the posterior of `arm`.
+    pub fn posterior(&self, arm: usize) -> NormalInverseGamma {
+        self.posteriors[arm]
+    }
+
+    /// Returns the posterior mean `μ₀` of each arm.
+    pub fn means(&self) -> Vec<f64> {
+        self.posteriors.iter().map(|p| p.mean).collect()
+    }
+
+    /// Serializes the posteriors and RNG state to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("GaussianThompson state is always serializable")
+    }
+
+    /// Restores an agent previously serialized with [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    /// Returns an error for malformed JSON, inconsistent per-arm state, or
+    /// invalid prior or posterior parameters.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        use serde::de::Error;
+
+        let agent: Self = serde_json::from_str(json)?;
+        check_arm_lengths(&[agent.posteriors.len(), agent.counts.len(), agent.values.len()])?;
+        if !agent.prior.is_valid() || !agent.posteriors.iter().all(NormalInverseGamma::is_valid) {
+            return Err(serde_json::Error::custom(
+                "prior and posterior parameters must be finite and positive",
+            ));
+        }
+        Ok(agent)
+    }
+
+    /// Returns the number of updates each arm has received.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns the average observed reward of each arm.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns an owned, consistent copy of the per-arm counts and values.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts.clone(),
+            values: self.values.clone(),
+        }
+    }
+
+    /// Draws `σ²` and then `μ` from each arm's posterior, returning the `μ` draws.
+    ///
+    /// A posterior whose parameters overflowed (see [`update`](Self::update))
+    /// cannot be sampled and contributes its mean instead.
+    fn sample_means(&mut self) -> Vec<f64> {
+        self.posteriors
+            .iter()
+            .map(|p| {
+                let precision = match Gamma::new(p.alpha, 1.0 / p.beta) {
+                    Ok(gamma) => gamma.sample(&mut self.rng),
+                    Err(_) => return p.mean,
+                };
+                let std = (1.0 / (precision * p.kappa)).sqrt();
+                match Normal::new(p.mean, std) {
+                    Ok(normal) if std.is_finite() => normal.sample(&mut self.rng),
+                    _ => p.mean,
+                }
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,144 @@
+//! # Gradient (Softmax) Bandit
+//!
+//! Learns a numerical preference `h_i` per arm instead of a value estimate
+//! and selects arms from the softmax distribution
+//! ```text
+//! π_i = exp(h_i) / Σ_j exp(h_j)
+//! ```
+//! After receiving reward `r` for arm `a`, preferences move by stochastic
+//! gradient ascent relative to a running baseline `r̄` (the mean of all
+//! rewards so far):
+//! ```text
+//! h_a += α (r − r̄) (1 − π_a)
+//! h_i −= α (r − r̄) π_i        for i ≠ a
+//! ```
+//! Only reward differences matter, so the strategy works for continuous,
+//! unbounded rewards without any rescaling.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::gradient::GradientBandit;
+//!
+//! let mut agent = GradientBandit::new(3, 0.1, 7);
+//! let arm = agent.select_arm();
+//! agent.update(arm, 12.5);
+//! let p = agent.probabilities();
+//! assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+//! ```
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use super::{sample_weighted, softmax, BanditSnapshot};
+
+/// Softmax policy-gradient bandit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradientBandit {
+    /// Per-arm preferences; only their differences matter.
+    h: Vec<f64>,
+    /// Step size for preference updates.
+    alpha: f64,
+    /// Running mean of all observed rewards.
+    baseline: f64,
+    /// Total number of updates across arms.
+    steps: u64,
+    /// Number of updates per arm.
+    counts: Vec<u64>,
+    /// Average observed reward per arm.
+    values: Vec<f64>,
+    /// Deterministic random number generator for reproducibility.
+    rng: ChaCha12Rng,
+}
+
+impl GradientBandit {
+    /// Creates an agent with `num_arms` equal preferences, learning rate
+    /// `alpha`, and a seeded RNG.
+    ///
+    /// # Panics
+    /// Panics if `num_arms == 0` or `alpha` is not positive.
+    pub fn new(num_arms: usize, alpha: f64, seed: u64) -> Self {
+        assert!(num_arms > 0, "must have at least one arm");
+        assert!(alpha > 0.0, "alpha must be positive");
+        Self {
+            h: vec![0.0; num_arms],
+            alpha,
+            baseline: 0.0,
+            steps: 0,
+            counts: vec![0; num_arms],
+            values: vec![0.0; num_arms],
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Samples an arm from the softmax distribution over the preferences.
+    pub fn select_arm(&mut self) -> usize {
+        let all: Vec<usize> = (0..self.h.len()).collect();
+        self.select_arm_among(&all)
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but samples from the softmax
+    /// restricted to `candidates`.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    pub fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        assert!(!candidates.is_empty(), "must have at least one candidate arm");
+        let h: Vec<f64> = candidates.iter().map(|&i| self.h[i]).collect();
+        sample_weighted(candidates, &softmax(&h), &mut self.rng)
+    }
+
+    /// Applies the gradient bandit rule for `reward` observed on `chosen_arm`,
+    /// then folds the reward into the baseline.
+    pub fn update(&mut self, chosen_arm: usize, reward: f64) {
+        let probs = self.probabilities();
+        let advantage = reward - self.baseline;
+        for (i, (h, p)) in self.h.iter_mut().zip(probs).enumerate() {
+            if i == chosen_arm {
+                *h += self.alpha * advantage * (1.0 - p);
+            } else {
+                *h -= self.alpha * advantage * p;
+            }
+        }
+
+        self.steps += 1;
+        self.baseline += (reward - self.baseline) / self.steps as f64;
+
+        let n = self.counts[chosen_arm] + 1;
+        self.counts[chosen_arm] = n;
+        self.values[chosen_arm] += (reward - self.values[chosen_arm]) / n as f64;
+    }
+
+    /// Returns the current selection probability of each arm.
+    pub fn probabilities(&self) -> Vec<f64> {
+        softmax(&self.h)
+    }
+
+    /// Returns the preference of each arm.
+    pub fn preferences(&self) -> &[f64] {
+        &self.h
+    }
+
+    /// Returns the running mean of all observed rewards.
+    pub fn baseline(&self) -> f64 {
+        self.baseline
+    }
+
+    /// Returns the number of updates each arm has received.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns the average observed reward of each arm.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns an owned, consistent copy of the per-arm counts and values.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
@@ -0,0 +1,316 @@
+//! # Linear Thompson Sampling
+//!
+//! Contextual Thompson Sampling (Agrawal & Goyal, 2013) over the same linear
+//! reward model as [`LinUcb`](super::linucb::LinUcb): with `A = I + Σ x xᵀ`
+//! and `b = Σ r x`, the posterior over the weights is
+//! ```text
+//! θ ~ N(A⁻¹ b, v² A⁻¹)
+//! ```
+//! Selection draws `θ` from that posterior and picks the arm whose features
+//! score highest under the draw, so exploration comes from posterior
+//! uncertainty instead of an explicit confidence bonus. `v` scales the
+//! posterior width; `v = 0` always plays the posterior mean.
+//!
+//! Two models are available:
+//!
+//! * [`LinTs::new`]: one independent regression per arm over the context.
+//! * [`LinTs::shared`]: a single regression over the context plus a one-hot
+//!   arm indicator. Arms share how the context affects the reward and differ
+//!   by a learned offset, so every update informs every arm.
+//!
+//! Both implement [`ContextualBandit`](super::ContextualBandit), so they can
+//! be swapped for [`LinUcb`](super::linucb::LinUcb) behind that trait.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::lints::LinTs;
+//!
+//! let mut agent = LinTs::new(2, 3, 0.5, 7);
+//! let context = [1.0, 0.0, 0.5];
+//! let arm = agent.select_arm(&context);
+//! agent.update(arm, &context, 1.0);
+//! ```
+//!
+//! ## Determinism
+//!
+//! The seeded RNG is serialized with the agent, so [`LinTs::from_json`]
+//! resumes the exact random sequence.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use rand_distr::{Distribution, StandardNormal};
+use serde::{Deserialize, Serialize};
+
+use super::linucb::{add_outer_product, dot, identity, mat_vec};
+use super::{check_arm_lengths, check_update, BanditError, BanditSnapshot};
+
+/// Linear Thompson Sampling over `dim`-dimensional contexts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinTs {
+    /// Context dimension.
+    dim: usize,
+    /// Posterior scale `v`.
+    v: f64,
+    /// Whether one regression over context and arm indicator serves all arms.
+    shared: bool,
+    /// `A⁻¹` per model (one per arm, or one shared), row-major.
+    a_inv: Vec<Vec<f64>>,
+    /// Reward-weighted feature sum `b` per model.
+    b: Vec<Vec<f64>>,
+    /// Number of updates per arm.
+    counts: Vec<u64>,
+    /// Mean observed reward per arm, regardless of context.
+    values: Vec<f64>,
+    /// Deterministic random number generator for reproducibility.
+    rng: ChaCha12Rng,
+}
+
+impl LinTs {
+    /// Creates an agent with an independent regression per arm over
+    /// `dim`-dimensional contexts, posterior scale `v`, and a seeded RNG.
+    ///
+    /// # Panics
+    /// Panics if `num_arms == 0`, `dim == 0`, or `v` is negative.
+    pub fn new(num_arms: usize, dim: usize, v: f64, seed: u64) -> Self {
+        assert!(num_arms > 0, "must have at least one arm");
+        assert!(dim > 0, "context dimension must be positive");
+        assert!(v >= 0.0, "v must be non-negative");
+        Self {
+            dim,
+            v,
+            shared: false,
+            a_inv: vec![identity(dim); num_arms],
+            b: vec![vec![0.0; dim]; num_arms],
+            counts: vec![0; num_arms],
+            values: vec![0.0; num_arms],
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Creates an agent with one regression shared by all arms, over the
+    /// context followed by a one-hot arm indicator.
+    ///
+    /// # Panics
+    /// Under the same conditions as [`LinTs::new`].
+    pub fn shared(num_arms: usize, dim: usize, v: f64, seed: u64) -> Self {
+        let features = dim + num_arms;
+        Self {
+            shared: true,
+            a_inv: vec![identity(features)],
+            b: vec![vec![0.0; features]],
+            ..Self::new(num_arms, dim, v, seed)
+        }
+    }
+
+    /// Draws weights from the posterior and returns the arm scoring highest
+    /// on `context` (the lowest index on ties).
+    ///
+    /// # Panics
+    /// Panics if `context.len()` differs from the context dimension.
+    pub fn select_arm(&mut self, context: &[f64]) -> usize {
+        self.check_context(context);
+        let shared_draw = self.shared.then(|| self.sample_theta(0));
+        let mut best = 0;
+        let mut best_score = f64::NEG_INFINITY;
+        for arm in 0..self.counts.len() {
+            let x = self.features(arm, context);
+            let score = match &shared_draw {
+                Some(theta) => dot(theta, &x),
+                None => dot(&self.sample_theta(arm), &x),
+            };
+            if score > best_score {
+                best_score = score;
+                best = arm;
+            }
+        }
+        best
+    }
+
+    /// Records `reward` for `arm` under `context`.
+    ///
+    /// # Panics
+    /// Panics if `arm` is out of range or `context.len()` differs from the
+    /// context dimension.
+    pub fn update(&mut self, arm: usize, context: &[f64], reward: f64) {
+        self.check_context(context);
+        let x = self.features(arm, context);
+        let model = self.model(arm);
+        add_outer_product(&mut self.a_inv[model], &x);
+        for (b, &x) in self.b[model].iter_mut().zip(&x) {
+            *b += reward * x;
+        }
+        self.counts[arm] += 1;
+        let n = self.counts[arm] as f64;
+        self.values[arm] += (reward - self.values[arm]) / n;
+    }
+
+    /// Like [`update`](Self::update), but rejects an out-of-range arm, a
+    /// non-finite reward, or an invalid context instead of panicking.
+    pub fn try_update(
+        &mut self,
+        arm: usize,
+        context: &[f64],
+        reward: f64,
+    ) -> Result<(), BanditError> {
+        check_update(self.counts.len(), arm, reward)?;
+        self.try_context(context)?;
+        self.update(arm, context, reward);
+        Ok(())
+    }
+
+    /// Checks that `context` has one finite entry per dimension.
+    pub fn try_context(&self, context: &[f64]) -> Result<(), BanditError> {
+        if context.len() != self.dim || !context.iter().all(|x| x.is_finite()) {
+            return Err(BanditError::InvalidContext { dim: self.dim });
+        }
+        Ok(())
+    }
+
+    /// Returns the posterior mean reward of `arm` for `context`.
+    ///
+    /// # Panics
+    /// Panics if `context.len()` differs from the context dimension.
+    pub fn expected_reward(&self, arm: usize, context: &[f64]) -> f64 {
+        self.check_context(context);
+        let model = self.model(arm);
+        let mean = mat_vec(&self.a_inv[model], &self.b[model]);
+        dot(&mean, &self.features(arm, context))
+    }
+
+    /// Forgets every observation, restoring the prior `A = I`, `b = 0`.
+    ///
+    /// The RNG continues from its current position.
+    pub fn reset(&mut self) {
+        let d = self.b[0].len();
+        self.a_inv.fill(identity(d));
+        for b in &mut self.b {
+            b.fill(0.0);
+        }
+        self.counts.fill(0);
+        self.values.fill(0.0);
+    }
+
+    /// Returns a copy of this agent's posteriors with a freshly seeded RNG.
+    pub fn fork(&self, seed: u64) -> Self {
+        Self {
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the context dimension.
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Returns whether all arms share one regression (see [`shared`](Self::shared)).
+    pub fn is_shared(&self) -> bool {
+        self.shared
+    }
+
+    /// Returns the number of updates each arm has received.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns each arm's mean observed reward, ignoring context (`0.0` if none).
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns an owned, consistent copy of the counts and context-free means.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts.clone(),
+            values: self.values.clone(),
+        }
+    }
+
+    /// Serializes the agent's full state, including the RNG, to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("LinTs state is always serializable")
+    }
+
+    /// Restores an agent previously serialized with [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    /// Returns an error for malformed JSON or posteriors that do not match
+    /// the context dimension and number of arms.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        use serde::de::Error;
+
+        let agent: Self = serde_json::from_str(json)?;
+        check_arm_lengths(&[agent.counts.len(), agent.values.len()])?;
+        let (models, d) = if agent.shared {
+            (1, agent.dim + agent.counts.len())
+        } else {
+            (agent.counts.len(), agent.dim)
+        };
+        if agent.dim == 0
+            || agent.a_inv.len() != models
+            || agent.b.len() != models
+            || agent.a_inv.iter().any(|a| a.len() != d * d)
+            || agent.b.iter().any(|b| b.len() != d)
+        {
+            return Err(serde_json::Error::custom("state does not match the context dimension"));
+        }
+        Ok(agent)
+    }
+
+    /// Index of the regression that models `arm`.
+    fn model(&self, arm: usize) -> usize {
+        if self.shared {
+            0
+        } else {
+            arm
+        }
+    }
+
+    /// Regression features of `arm` under `context`.
+    fn features(&self, arm: usize, context: &[f64]) -> Vec<f64> {
+        if !self.shared {
+            return context.to_vec();
+        }
+        let mut x = context.to_vec();
+        x.resize(self.dim + self.counts.len(), 0.0);
+        x[self.dim + arm] = 1.0;
+        x
+    }
+
+    /// Draws `θ ~ N(A⁻¹ b, v² A⁻¹)` for `model`.
+    fn sample_theta(&mut self, model: usize) -> Vec<f64> {
+        let a_inv = &self.a_inv[model];
+        let d = self.b[model].len();
+        let mut theta = mat_vec(a_inv, &self.b[model]);
+        let l = cholesky(a_inv, d);
+        let z: Vec<f64> = (0..d).map(|_| StandardNormal.sample(&mut self.rng)).collect();
+        for (i, t) in theta.iter_mut().enumerate() {
+            *t += self.v * dot(&l[i * d..i * d + i + 1], &z[..=i]);
+        }
+        theta
+    }
+
+    fn check_context(&self, context: &[f64]) {
+        assert_eq!(
+            context.len(),
+            self.dim,
+            "context must have one entry per dimension"
+        );
+    }
+}
+
+/// Lower-triangular `L` (row-major) with `L Lᵀ = m` for a symmetric positive
+/// semi-definite `d × d` matrix `m`. Pivots lost to rounding are treated as zero.
+fn cholesky(m: &[f64], d: usize) -> Vec<f64> {
+    let mut l = vec![0.0; d * d];
+    for j in 0..d {
+        let pivot = m[j * d + j] - dot(&l[j * d..j * d + j], &l[j * d..j * d + j]);
+        let pivot = pivot.max(0.0).sqrt();
+        l[j * d + j] = pivot;
+        for i in j + 1..d {
+            let s = m[i * d + j] - dot(&l[i * d..i * d + j], &l[j * d..j * d + j]);
+            l[i * d + j] = if pivot > 0.0 { s / pivot } else { 0.0 };
+        }
+    }
+    l
+}
@@ -0,0 +1,263 @@
+//! # LinUCB Contextual Bandit
+//!
+//! Disjoint LinUCB (Li et al., 2010): each arm models its expected reward
+//! as a linear function of a per-request context vector `x`,
+//! ```text
+//! E[r | x, arm a] = θ_a · x,       θ_a = A_a⁻¹ b_a
+//! ```
+//! where `A_a = I + Σ x xᵀ` and `b_a = Σ r x` accumulate the contexts and
+//! rewards observed for arm `a`. Selection maximizes the upper confidence bound
+//! ```text
+//! score_a = θ_a · x + α · sqrt(xᵀ A_a⁻¹ x)
+//! ```
+//! Rather than inverting `A_a` on every selection, the agent keeps `A_a⁻¹`
+//! up to date with the Sherman–Morrison formula, so both selection and
+//! update are O(arms · dim²) without any linear-algebra dependency.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::linucb::LinUcb;
+//!
+//! let mut agent = LinUcb::new(2, 3, 1.0);
+//! let context = [1.0, 0.0, 0.5];
+//! let arm = agent.select_arm(&context);
+//! agent.update(arm, &context, 1.0);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::{check_arm_lengths, check_update, BanditError, BanditSnapshot};
+
+/// Disjoint linear UCB over `dim`-dimensional contexts.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinUcb {
+    /// Context dimension.
+    dim: usize,
+    /// Exploration parameter.
+    alpha: f64,
+    /// `A⁻¹` per arm, row-major `dim × dim`.
+    a_inv: Vec<Vec<f64>>,
+    /// Reward-weighted context sum `b` per arm.
+    b: Vec<Vec<f64>>,
+    /// Number of updates per arm.
+    counts: Vec<u64>,
+    /// Mean observed reward per arm, regardless of context.
+    values: Vec<f64>,
+}
+
+impl LinUcb {
+    /// Creates an agent with `num_arms` arms over `dim`-dimensional contexts
+    /// and exploration factor `alpha`. Every `A` starts as the identity.
+    ///
+    /// # Panics
+    /// Panics if `num_arms == 0`, `dim == 0`, or `alpha` is negative.
+    pub fn new(num_arms: usize, dim: usize, alpha: f64) -> Self {
+        assert!(num_arms > 0, "must have at least one arm");
+        assert!(dim > 0, "context dimension must be positive");
+        assert!(alpha >= 0.0, "alpha must be non-negative");
+        Self {
+            dim,
+            alpha,
+            a_inv: vec![identity(dim); num_arms],
+            b: vec![vec![0.0; dim]; num_arms],
+            counts: vec![0; num_arms],
+            values: vec![0.0; num_arms],
+        }
+    }
+
+    /// Forgets every observation, restoring `A = I` and `b = 0` for all arms.
+    pub fn reset(&mut self) {
+        self.a_inv.fill(identity(self.dim));
+        for b in &mut self.b {
+            b.fill(0.0);
+        }
+        self.counts.fill(0);
+        self.values.fill(0.0);
+    }
+
+    /// Returns the arm with the highest upper confidence bound for `context`
+    /// (the lowest index on ties).
+    ///
+    /// # Panics
+    /// Panics if `context.len()` differs from the context dimension.
+    pub fn select_arm(&mut self, context: &[f64]) -> usize {
+        let scores = self.scores(context);
+        let mut best = 0;
+        for (i, &score) in scores.iter().enumerate() {
+            if score > scores[best] {
+                best = i;
+            }
+        }
+        best
+    }
+
+    /// Returns up to `k` distinct arms ranked by their upper confidence bound
+    /// for `context`, best first (lower index first on ties).
+    ///
+    /// # Panics
+    /// Panics if `context.len()` differs from the context dimension.
+    pub fn select_arms(&mut self, context: &[f64], k: usize) -> Vec<usize> {
+        let scores = self.scores(context);
+        let mut ranked: Vec<usize> = (0..scores.len()).collect();
+        ranked.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+        ranked.truncate(k);
+        ranked
+    }
+
+    /// Returns every arm's upper confidence bound for `context`.
+    ///
+    /// # Panics
+    /// Panics if `context.len()` differs from the context dimension.
+    pub fn scores(&self, context: &[f64]) -> Vec<f64> {
+        self.check_context(context);
+        (0..self.counts.len())
+            .map(|arm| {
+                let theta = self.theta(arm);
+                let ax = self.a_inv_times(arm, context);
+                let width = dot(context, &ax).max(0.0).sqrt();
+                dot(&theta, context) + self.alpha * width
+            })
+            .collect()
+    }
+
+    /// Records `reward` for `arm` under `context`: `A += x xᵀ`, `b += r x`.
+    ///
+    /// # Panics
+    /// Panics if `arm` is out of range or `context.len()` differs from the
+    /// context dimension.
+    pub fn update(&mut self, arm: usize, context: &[f64], reward: f64) {
+        self.check_context(context);
+        add_outer_product(&mut self.a_inv[arm], context);
+        for (b, &x) in self.b[arm].iter_mut().zip(context) {
+            *b += reward * x;
+        }
+        self.counts[arm] += 1;
+        let n = self.counts[arm] as f64;
+        self.values[arm] += (reward - self.values[arm]) / n;
+    }
+
+    /// Like [`update`](Self::update), but rejects an out-of-range arm, a
+    /// non-finite reward, or an invalid context instead of panicking.
+    pub fn try_update(
+        &mut self,
+        arm: usize,
+        context: &[f64],
+        reward: f64,
+    ) -> Result<(), BanditError> {
+        check_update(self.counts.len(), arm, reward)?;
+        self.try_context(context)?;
+        self.update(arm, context, reward);
+        Ok(())
+    }
+
+    /// Checks that `context` has one finite entry per dimension.
+    pub fn try_context(&self, context: &[f64]) -> Result<(), BanditError> {
+        if context.len() != self.dim || !context.iter().all(|x| x.is_finite()) {
+            return Err(BanditError::InvalidContext { dim: self.dim });
+        }
+        Ok(())
+    }
+
+    /// Returns the current coefficient estimate `θ = A⁻¹ b` for `arm`.
+    pub fn theta(&self, arm: usize) -> Vec<f64> {
+        self.a_inv_times(arm, &self.b[arm])
+    }
+
+    /// Returns the context dimension.
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Returns the number of updates each arm has received.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns each arm's mean observed reward, ignoring context (`0.0` if none).
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns an owned, consistent copy of the counts and context-free means.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts.clone(),
+            values: self.values.clone(),
+        }
+    }
+
+    /// Serializes the agent's full state to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("LinUcb state is always serializable")
+    }
+
+    /// Restores an agent previously serialized with [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    /// Returns an error for malformed JSON or per-arm state that does not
+    /// match the context dimension.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        use serde::de::Error;
+
+        let agent: Self = serde_json::from_str(json)?;
+        check_arm_lengths(&[
+            agent.a_inv.len(),
+            agent.b.len(),
+            agent.counts.len(),
+            agent.values.len(),
+        ])?;
+        let d = agent.dim;
+        if d == 0
+            || agent.a_inv.iter().any(|a| a.len() != d * d)
+            || agent.b.iter().any(|b| b.len() != d)
+        {
+            return Err(serde_json::Error::custom("state does not match the context dimension"));
+        }
+        Ok(agent)
+    }
+
+    /// `A⁻¹ v` for `arm`.
+    fn a_inv_times(&self, arm: usize, v: &[f64]) -> Vec<f64> {
+        mat_vec(&self.a_inv[arm], v)
+    }
+
+    fn check_context(&self, context: &[f64]) {
+        assert_eq!(
+            context.len(),
+            self.dim,
+            "context must have one entry per dimension"
+        );
+    }
+}
+
+/// Row-major `dim × dim` identity matrix.
+pub(super) fn identity(dim: usize) -> Vec<f64> {
+    let mut identity = vec![0.0; dim * dim];
+    for i in 0..dim {
+        identity[i * dim + i] = 1.0;
+    }
+    identity
+}
+
+/// Row-major square matrix `m` times `v`.
+pub(super) fn mat_vec(m: &[f64], v: &[f64]) -> Vec<f64> {
+    m.chunks(v.len()).map(|row| dot(row, v)).collect()
+}
+
+/// Replaces the symmetric inverse `A⁻¹` (row-major) with `(A + x xᵀ)⁻¹`.
+///
+/// Sherman–Morrison: `(A + x xᵀ)⁻¹ = A⁻¹ − (A⁻¹x)(A⁻¹x)ᵀ / (1 + xᵀA⁻¹x)`.
+pub(super) fn add_outer_product(a_inv: &mut [f64], x: &[f64]) {
+    let ax = mat_vec(a_inv, x);
+    let denom = 1.0 + dot(x, &ax);
+    let d = x.len();
+    for i in 0..d {
+        for j in 0..d {
+            a_inv[i * d + j] -= ax[i] * ax[j] / denom;
+        }
+    }
+}
+
+pub(super) fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
@@ -0,0 +1,665 @@
+//! Multi-armed bandit algorithms.
+//!
+//! Each strategy lives in its own submodule and exposes the same
+//! `select_arm` / `update` / `counts` / `values` surface. Strategies with a
+//! scalar reward also implement the [`Bandit`] trait so generic helpers such
+//! as the [`sim`] module can drive any of them. The contextual [`linucb`]
+//! and [`lints`] bandits instead take a feature vector on every select and
+//! update, and share the [`ContextualBandit`] trait. The [`dueling`] bandit
+//! learns from pairwise preferences rather than rewards. The REST service
+//! holds both kinds behind the object-safe [`BanditPolicy`] trait.
+
+use std::fmt;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+mod cooldown;
+
+pub mod aggregation;
+pub mod bayes_ucb;
+pub mod budgeted;
+pub mod dueling;
+pub mod epsilon_greedy;
+pub mod export;
+pub mod gaussian_thompson;
+pub mod gated;
+pub mod gradient;
+pub mod kl_ucb;
+pub mod linucb;
+pub mod lints;
+pub mod nonstationary;
+pub mod pareto;
+pub mod policy;
+pub mod sim;
+pub mod sw_ucb;
+pub mod thompson;
+pub mod ucb1;
+
+/// Reasons a fallible bandit operation is rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BanditError {
+    /// The arm index is not below the number of arms.
+    ArmOutOfRange { arm: usize, num_arms: usize },
+    /// The reward is NaN or infinite.
+    NonFiniteReward(f64),
+    /// A contextual bandit's context is missing, has the wrong length, or
+    /// contains a non-finite entry.
+    InvalidContext { dim: usize },
+    /// No arm carries the given label.
+    UnknownArm(String),
+    /// No arm fits in the remaining budget.
+    BudgetExhausted,
+    /// The reward is finite but too large for the model to absorb without
+    /// overflowing its statistics.
+    RewardOverflow(f64),
+    /// Another arm already carries the given label.
+    DuplicateLabel(String),
+    /// Prior counts and values differ in length, or fail [`check_priors`].
+    InvalidPriors(&'static str),
+}
+
+impl fmt::Display for BanditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BanditError::ArmOutOfRange { arm, num_arms } => {
+                write!(f, "arm {} out of range for {} arms", arm, num_arms)
+            }
+            BanditError::NonFiniteReward(r) => write!(f, "reward {} is not finite", r),
+            BanditError::InvalidContext { dim } => {
+                write!(f, "context must have {} finite entries", dim)
+            }
+            BanditError::UnknownArm(label) => write!(f, "no arm is labeled '{}'", label),
+            BanditError::BudgetExhausted => write!(f, "budget exhausted"),
+            BanditError::RewardOverflow(r) => write!(f, "reward {} overflows the model", r),
+            BanditError::DuplicateLabel(label) => {
+                write!(f, "an arm is already labeled '{}'", label)
+            }
+            BanditError::InvalidPriors(msg) => write!(f, "invalid priors: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BanditError {}
+
+/// Validates an `(arm, reward)` update against a bandit with `num_arms` arms.
+pub(crate) fn check_update(num_arms: usize, arm: usize, reward: f64) -> Result<(), BanditError> {
+    if arm >= num_arms {
+        return Err(BanditError::ArmOutOfRange { arm, num_arms });
+    }
+    if !reward.is_finite() {
+        return Err(BanditError::NonFiniteReward(reward));
+    }
+    Ok(())
+}
+
+/// How an agent chooses among arms tied for the best score.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TieBreak {
+    /// The lowest tied index.
+    #[default]
+    Lowest,
+    /// The highest tied index.
+    Highest,
+    /// A uniformly random tied arm, drawn from the agent's seeded RNG.
+    Random,
+}
+
+impl TieBreak {
+    /// Picks one arm out of the non-empty `tied`.
+    ///
+    /// `Random` only consumes randomness when there is an actual tie.
+    pub(crate) fn pick<R: Rng>(self, tied: &[usize], rng: &mut R) -> usize {
+        match self {
+            TieBreak::Lowest => *tied.iter().min().expect("no tied arms"),
+            TieBreak::Highest => *tied.iter().max().expect("no tied arms"),
+            TieBreak::Random if tied.len() > 1 => tied[rng.gen_range(0..tied.len())],
+            TieBreak::Random => tied[0],
+        }
+    }
+}
+
+/// Returns the candidates sharing the highest `score`, or just the first
+/// candidate if no score is comparable (all NaN).
+pub(crate) fn max_ties(candidates: &[usize], score: impl Fn(usize) -> f64) -> Vec<usize> {
+    let best = candidates
+        .iter()
+        .map(|&i| score(i))
+        .fold(f64::NEG_INFINITY, |m, s| if s > m { s } else { m });
+    let tied: Vec<usize> = candidates.iter().copied().filter(|&i| score(i) == best).collect();
+    if tied.is_empty() {
+        vec![candidates[0]]
+    } else {
+        tied
+    }
+}
+
+/// Numerically stable softmax (shifts by the maximum before exponentiating).
+pub(crate) fn softmax(h: &[f64]) -> Vec<f64> {
+    let max = h.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let exps: Vec<f64> = h.iter().map(|&x| (x - max).exp()).collect();
+    let total: f64 = exps.iter().sum();
+    exps.into_iter().map(|e| e / total).collect()
+}
+
+/// Samples one of `candidates`, where `probs[k]` is the probability of `candidates[k]`.
+pub(crate) fn sample_weighted<R: Rng>(candidates: &[usize], probs: &[f64], rng: &mut R) -> usize {
+    let u: f64 = rng.gen();
+    let mut cumulative = 0.0;
+    for (&arm, p) in candidates.iter().zip(probs) {
+        cumulative += p;
+        if u < cumulative {
+            return arm;
+        }
+    }
+    // Rounding can leave the cumulative sum just below 1.0.
+    candidates[candidates.len() - 1]
+}
+
+/// Owned copy of a bandit's per-arm statistics taken in a single read.
+///
+/// Useful for computing stats outside of a lock: take the snapshot while
+/// holding it, release, then work on the copy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BanditSnapshot {
+    /// Number of times each arm has been updated.
+    pub counts: Vec<u64>,
+    /// Estimated mean reward of each arm.
+    pub values: Vec<f64>,
+}
+
+/// Checks that deserialized per-arm vectors all describe the same, non-zero
+/// number of arms.
+pub(crate) fn check_arm_lengths(lengths: &[usize]) -> Result<(), serde_json::Error> {
+    use serde::de::Error;
+
+    match lengths.first() {
+        Some(&n) if n > 0 && lengths.iter().all(|&len| len == n) => Ok(()),
+        _ => Err(serde_json::Error::custom("inconsistent per-arm state")),
+    }
+}
+
+/// Whether no two arms share a label.
+pub(crate) fn labels_unique(labels: &[String]) -> bool {
+    let mut seen = std::collections::HashSet::new();
+    labels.iter().all(|label| seen.insert(label))
+}
+
+/// Label for a new arm at index `arm`: the index itself, suffixed with
+/// `_1`, `_2`, … if another arm already carries that name.
+pub(crate) fn index_label(labels: &[String], arm: usize) -> String {
+    let mut label = arm.to_string();
+    let mut suffix = 0;
+    while labels.contains(&label) {
+        suffix += 1;
+        label = format!("{}_{}", arm, suffix);
+    }
+    label
+}
+
+/// Whether every entry of deserialized per-arm statistics is finite.
+pub(crate) fn all_finite(values: &[f64]) -> bool {
+    values.iter().all(|v| v.is_finite())
+}
+
+/// Largest prior count `with_priors` constructors accept, so that no count,
+/// nor the total over any realistic number of arms, can overflow a `u64`.
+pub const MAX_PRIOR_COUNT: u64 = u32::MAX as u64;
+
+/// Checks `(value, count)` priors: values must be finite and counts at most
+/// [`MAX_PRIOR_COUNT`].
+pub(crate) fn check_priors(priors: &[(f64, u64)]) -> Result<(), &'static str> {
+    if !priors.iter().all(|(v, _)| v.is_finite()) {
+        return Err("prior values must be finite");
+    }
+    if priors.iter().any(|&(_, n)| n > MAX_PRIOR_COUNT) {
+        return Err("prior counts must be at most MAX_PRIOR_COUNT");
+    }
+    Ok(())
+}
+
+/// Pairs per-arm prior `values` with `counts` after checking them.
+pub(crate) fn zip_priors(counts: &[u64], values: &[f64]) -> Result<Vec<(f64, u64)>, BanditError> {
+    if counts.len() != values.len() {
+        return Err(BanditError::InvalidPriors("counts and values differ in length"));
+    }
+    let priors: Vec<(f64, u64)> = values.iter().copied().zip(counts.iter().copied()).collect();
+    check_priors(&priors).map_err(BanditError::InvalidPriors)?;
+    Ok(priors)
+}
+
+/// Adds `reward` to a Welford running count, mean and sum of squared
+/// deviations.
+pub(crate) fn welford_push(n: &mut u64, mean: &mut f64, m2: &mut f64, reward: f64) {
+    *n += 1;
+    let delta = reward - *mean;
+    *mean += delta / *n as f64;
+    *m2 += delta * (reward - *mean);
+}
+
+/// Unbiased sample variance from a Welford sum of squared deviations `m2`
+/// over `n` samples, or `None` when fewer than two samples exist.
+pub(crate) fn sample_variance(m2: f64, n: u64) -> Option<f64> {
+    (n >= 2).then(|| m2 / (n - 1) as f64)
+}
+
+/// `mean ± z * sqrt(var / n)`, or an unbounded interval when `n < 2`.
+pub(crate) fn confidence_interval(mean: f64, m2: f64, n: u64, z: f64) -> (f64, f64) {
+    match sample_variance(m2, n) {
+        Some(var) => {
+            let half_width = z * (var / n as f64).sqrt();
+            (mean - half_width, mean + half_width)
+        }
+        None => (f64::NEG_INFINITY, f64::INFINITY),
+    }
+}
+
+/// KL divergence between Bernoulli distributions with means `p` and `q`.
+pub(crate) fn bernoulli_kl(p: f64, q: f64) -> f64 {
+    const EPS: f64 = 1e-12;
+    let p = p.clamp(EPS, 1.0 - EPS);
+    let q = q.clamp(EPS, 1.0 - EPS);
+    p * (p / q).ln() + (1.0 - p) * ((1.0 - p) / (1.0 - q)).ln()
+}
+
+/// Common interface for context-free bandits with scalar rewards.
+///
+/// The service layer holds bandits through [`BanditPolicy`] instead, which
+/// also covers contextual strategies.
+pub trait Bandit {
+    /// Selects the next arm to pull.
+    fn select_arm(&mut self) -> usize;
+
+    /// Selects the next arm to pull from `candidates` only.
+    ///
+    /// The default keeps [`select_arm`](Self::select_arm)'s choice when it is
+    /// a candidate, and otherwise falls back to the candidate with the highest
+    /// estimated value (the lowest index on ties). Strategies override it to
+    /// explore within the candidates as well.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        assert!(!candidates.is_empty(), "must have at least one candidate arm");
+        let arm = self.select_arm();
+        if candidates.contains(&arm) {
+            return arm;
+        }
+        let values = self.values();
+        let mut best = candidates[0];
+        for &i in candidates {
+            if values[i] > values[best] {
+                best = i;
+            }
+        }
+        best
+    }
+
+    /// Selects a slate of up to `k` distinct arms, in selection order.
+    ///
+    /// Each slot is filled by [`select_arm_among`](Self::select_arm_among)
+    /// over the arms not chosen yet, so the strategy's own exploration
+    /// applies to every slot, not just the first.
+    fn select_arms(&mut self, k: usize) -> Vec<usize> {
+        let mut remaining: Vec<usize> = (0..self.counts().len()).collect();
+        let mut slate = Vec::with_capacity(k.min(remaining.len()));
+        while slate.len() < k && !remaining.is_empty() {
+            let arm = self.select_arm_among(&remaining);
+            remaining.retain(|&i| i != arm);
+            slate.push(arm);
+        }
+        slate
+    }
+
+    /// Reports the reward observed for `arm`.
+    fn update(&mut self, arm: usize, reward: f64);
+
+    /// Number of times each arm has been updated.
+    fn counts(&self) -> &[u64];
+
+    /// Current estimated mean reward of each arm.
+    fn values(&self) -> &[f64];
+
+    /// Highest estimated mean reward across arms (`0.0` if there are no arms).
+    fn best_value(&self) -> f64 {
+        self.values().iter().copied().reduce(f64::max).unwrap_or(0.0)
+    }
+
+    /// Lowest estimated mean reward across arms (`0.0` if there are no arms).
+    fn worst_value(&self) -> f64 {
+        self.values().iter().copied().reduce(f64::min).unwrap_or(0.0)
+    }
+
+    /// Owned copy of [`counts`](Self::counts) and [`values`](Self::values).
+    fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts().to_vec(),
+            values: self.values().to_vec(),
+        }
+    }
+
+    /// Index of the arm with the most updates, preferring the lowest index on ties.
+    fn most_pulled(&self) -> usize {
+        let mut best = 0;
+        for (i, &n) in self.counts().iter().enumerate() {
+            if n > self.counts()[best] {
+                best = i;
+            }
+        }
+        best
+    }
+}
+
+pub use self::policy::BanditPolicy;
+
+pub use self::nonstationary::{DiscountedUcb, Ucb1Windowed};
+
+impl Bandit for epsilon_greedy::EpsilonGreedy {
+    fn select_arm(&mut self) -> usize {
+        self.select_arm()
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.select_arm_among(candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.update(arm, reward)
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+impl Bandit for ucb1::Ucb1 {
+    fn select_arm(&mut self) -> usize {
+        self.select_arm_mut()
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.select_arm_among_mut(candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.update(arm, reward)
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+impl Bandit for thompson::ThompsonSampling {
+    fn select_arm(&mut self) -> usize {
+        self.select_arm()
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.select_arm_among(candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.update(arm, reward)
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+impl Bandit for gaussian_thompson::GaussianThompson {
+    fn select_arm(&mut self) -> usize {
+        self.select_arm()
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.select_arm_among(candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.update(arm, reward)
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+impl Bandit for gradient::GradientBandit {
+    fn select_arm(&mut self) -> usize {
+        self.select_arm()
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.select_arm_among(candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.update(arm, reward)
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+impl Bandit for bayes_ucb::BayesUcb {
+    fn select_arm(&mut self) -> usize {
+        self.select_arm()
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.select_arm_among(candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.update(arm, reward)
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+impl Bandit for kl_ucb::KlUcb {
+    fn select_arm(&mut self) -> usize {
+        self.select_arm()
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.select_arm_among(candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.update(arm, reward)
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+impl Bandit for Ucb1Windowed {
+    fn select_arm(&mut self) -> usize {
+        self.select_arm()
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.select_arm_among(candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.update(arm, reward)
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+impl Bandit for DiscountedUcb {
+    fn select_arm(&mut self) -> usize {
+        DiscountedUcb::select_arm(self)
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        DiscountedUcb::select_arm_among(self, candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.update(arm, reward)
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+impl Bandit for sw_ucb::SlidingWindowUcb {
+    fn select_arm(&mut self) -> usize {
+        self.select_arm()
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.select_arm_among(candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.update(arm, reward)
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+/// Common interface for contextual bandits, which see a feature vector with
+/// every selection and update.
+pub trait ContextualBandit {
+    /// Selects the next arm to pull for `context`.
+    fn select_arm(&mut self, context: &[f64]) -> usize;
+
+    /// Reports the reward observed for `arm` under `context`.
+    fn update(&mut self, arm: usize, context: &[f64], reward: f64);
+
+    /// Like [`update`](Self::update), but rejects an out-of-range arm, a
+    /// non-finite reward, or an invalid context instead of panicking.
+    fn try_update(
+        &mut self,
+        arm: usize,
+        context: &[f64],
+        reward: f64,
+    ) -> Result<(), BanditError>;
+
+    /// Length every context must have.
+    fn dim(&self) -> usize;
+
+    /// Number of times each arm has been updated.
+    fn counts(&self) -> &[u64];
+
+    /// Mean observed reward of each arm, ignoring context.
+    fn values(&self) -> &[f64];
+}
+
+impl ContextualBandit for linucb::LinUcb {
+    fn select_arm(&mut self, context: &[f64]) -> usize {
+        self.select_arm(context)
+    }
+
+    fn update(&mut self, arm: usize, context: &[f64], reward: f64) {
+        self.update(arm, context, reward)
+    }
+
+    fn try_update(
+        &mut self,
+        arm: usize,
+        context: &[f64],
+        reward: f64,
+    ) -> Result<(), BanditError> {
+        self.try_update(arm, context, reward)
+    }
+
+    fn dim(&self) -> usize {
+        self.dim()
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
+
+impl ContextualBandit for lints::LinTs {
+    fn select_arm(&mut self, context: &[f64]) -> usize {
+        self.select_arm(context)
+    }
+
+    fn update(&mut self, arm: usize, context: &[f64], reward: f64) {
+        self.update(arm, context, reward)
+    }
+
+    fn try_update(
+        &mut self,
+        arm: usize,
+        context: &[f64],
+        reward: f64,
+    ) -> Result<(), BanditError> {
+        self.try_update(arm, context, reward)
+    }
+
+    fn dim(&self) -> usize {
+        self.dim()
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+}
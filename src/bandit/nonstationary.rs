@@ -0,0 +1,145 @@
+//! # UCB1 for Non-Stationary Rewards
+//!
+//! Plain [`Ucb1`] averages every reward it has ever seen, so it is slow to
+//! notice when an arm's mean drifts or shifts abruptly. The two agents here
+//! forget old rewards instead:
+//!
+//! * [`Ucb1Windowed`] scores each arm on its last `window` rewards only
+//!   (a per-arm [`SlidingWindowUcb`]).
+//! * [`DiscountedUcb`] scores each arm on counts and reward sums that decay
+//!   by `gamma` on every update (a discounted [`Ucb1`]).
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::{DiscountedUcb, Ucb1Windowed};
+//!
+//! let mut windowed = Ucb1Windowed::new(2, 1.0, 50);
+//! let mut discounted = DiscountedUcb::new(2, 1.0, 0.98);
+//! let arm = windowed.select_arm();
+//! windowed.update(arm, 1.0);
+//! let arm = discounted.select_arm();
+//! discounted.update(arm, 1.0);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::sw_ucb::SlidingWindowUcb;
+use super::ucb1::Ucb1;
+use super::BanditSnapshot;
+
+/// UCB1 over the last `window` rewards of each arm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Ucb1Windowed(SlidingWindowUcb);
+
+impl Ucb1Windowed {
+    /// Creates an agent that scores each arm on its last `window` rewards.
+    ///
+    /// # Panics
+    /// Panics if `num_arms == 0`, `c` is negative, or `window == 0`.
+    pub fn new(num_arms: usize, c: f64, window: usize) -> Self {
+        Self(SlidingWindowUcb::per_arm(num_arms, c, window))
+    }
+
+    /// Selects the arm with the highest windowed UCB score.
+    pub fn select_arm(&mut self) -> usize {
+        self.0.select_arm()
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but only ever returns one of `candidates`.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    pub fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.0.select_arm_among(candidates)
+    }
+
+    /// Records `reward` for `chosen_arm`, evicting the arm's oldest reward
+    /// once its window is full.
+    pub fn update(&mut self, chosen_arm: usize, reward: f64) {
+        self.0.update(chosen_arm, reward)
+    }
+
+    /// Returns the window length.
+    pub fn window(&self) -> usize {
+        self.0.window()
+    }
+
+    /// Returns the number of rewards per arm inside the window.
+    pub fn counts(&self) -> &[u64] {
+        self.0.counts()
+    }
+
+    /// Returns the mean reward per arm inside the window (`0.0` if none).
+    pub fn values(&self) -> &[f64] {
+        self.0.values()
+    }
+
+    /// Returns an owned, consistent copy of the windowed counts and values.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        self.0.snapshot()
+    }
+
+    /// Returns the underlying sliding-window agent.
+    pub fn inner(&self) -> &SlidingWindowUcb {
+        &self.0
+    }
+}
+
+/// UCB1 over counts and reward sums discounted by `gamma` on every update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct DiscountedUcb(Ucb1);
+
+impl DiscountedUcb {
+    /// Creates an agent that forgets old rewards at rate `gamma`.
+    ///
+    /// # Panics
+    /// Panics if `gamma` is not in `(0, 1]`, or under the same conditions as [`Ucb1::new`].
+    pub fn new(num_arms: usize, c: f64, gamma: f64) -> Self {
+        Self(Ucb1::with_discount(num_arms, c, gamma))
+    }
+
+    /// Selects the arm with the highest discounted UCB score.
+    pub fn select_arm(&self) -> usize {
+        self.0.select_arm()
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but only ever returns one of `candidates`.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    pub fn select_arm_among(&self, candidates: &[usize]) -> usize {
+        self.0.select_arm_among(candidates)
+    }
+
+    /// Decays every arm's statistics by `gamma`, then records `reward` for `chosen_arm`.
+    pub fn update(&mut self, chosen_arm: usize, reward: f64) {
+        self.0.update(chosen_arm, reward)
+    }
+
+    /// Returns the discount factor.
+    pub fn gamma(&self) -> f64 {
+        self.0.gamma()
+    }
+
+    /// Returns the undiscounted number of pulls per arm.
+    pub fn counts(&self) -> &[u64] {
+        self.0.counts()
+    }
+
+    /// Returns the undiscounted mean reward per arm.
+    pub fn values(&self) -> &[f64] {
+        self.0.values()
+    }
+
+    /// Returns an owned, consistent copy of the undiscounted counts and values.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        self.0.snapshot()
+    }
+
+    /// Returns the underlying discounted UCB1 agent.
+    pub fn inner(&self) -> &Ucb1 {
+        &self.0
+    }
+}
@@ -0,0 +1,108 @@
+//! # Multi-Objective Pareto Bandit
+//!
+//! A bandit for arms whose rewards have several dimensions (e.g. accuracy
+//! and speed) that should not be collapsed into a single scalar.
+//!
+//! Each arm keeps a running mean **vector** of its rewards. An arm *i* is
+//! dominated by arm *j* when `mean_j ≥ mean_i` on every objective and
+//! `mean_j > mean_i` on at least one. The **Pareto front** is the set of
+//! arms that no other arm dominates.
+//!
+//! Selection first tries every arm once, then picks uniformly at random
+//! among the current Pareto front.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::pareto::ParetoBandit;
+//!
+//! let mut agent = ParetoBandit::new(2, 2);
+//! agent.update(0, &[0.9, 0.2]); // accurate but slow
+//! agent.update(1, &[0.6, 0.8]); // less accurate but fast
+//! assert_eq!(agent.pareto_front(), vec![0, 1]);
+//! ```
+//!
+//! ## Determinism
+//!
+//! The internal RNG (`StdRng`) is seeded with a fixed value for reproducible tests.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// Multi-objective bandit selecting among non-dominated arms.
+#[derive(Debug, Clone)]
+pub struct ParetoBandit {
+    /// Number of reward dimensions per observation.
+    num_objectives: usize,
+    /// Number of updates received by each arm.
+    counts: Vec<u64>,
+    /// Running mean reward vector for each arm.
+    means: Vec<Vec<f64>>,
+    /// Deterministic random number generator for reproducibility.
+    rng: StdRng,
+}
+
+impl ParetoBandit {
+    /// Creates a new agent with `num_arms` arms and `num_objectives` reward dimensions.
+    ///
+    /// # Panics
+    /// - If `num_arms == 0`
+    /// - If `num_objectives == 0`
+    pub fn new(num_arms: usize, num_objectives: usize) -> Self {
+        assert!(num_arms > 0, "must have at least one arm");
+        assert!(num_objectives > 0, "must have at least one objective");
+        Self {
+            num_objectives,
+            counts: vec![0; num_arms],
+            means: vec![vec![0.0; num_objectives]; num_arms],
+            rng: StdRng::seed_from_u64(42), // deterministic seed for reproducibility
+        }
+    }
+
+    /// Selects an arm: untried arms first, then uniformly among the Pareto front.
+    pub fn select_arm(&mut self) -> usize {
+        if let Some(idx) = self.counts.iter().position(|&n| n == 0) {
+            return idx;
+        }
+        let front = self.pareto_front();
+        front[self.rng.gen_range(0..front.len())]
+    }
+
+    /// Updates the mean reward vector of `chosen_arm` with a new observation.
+    ///
+    /// # Panics
+    /// Panics if `rewards.len()` differs from the number of objectives.
+    pub fn update(&mut self, chosen_arm: usize, rewards: &[f64]) {
+        assert_eq!(
+            rewards.len(),
+            self.num_objectives,
+            "reward vector length must match number of objectives"
+        );
+        let n = self.counts[chosen_arm] + 1;
+        for (mean, &r) in self.means[chosen_arm].iter_mut().zip(rewards) {
+            *mean += (r - *mean) / n as f64;
+        }
+        self.counts[chosen_arm] = n;
+    }
+
+    /// Returns the indices of all arms not dominated by any other arm.
+    pub fn pareto_front(&self) -> Vec<usize> {
+        (0..self.means.len())
+            .filter(|&i| !(0..self.means.len()).any(|j| j != i && self.dominates(j, i)))
+            .collect()
+    }
+
+    /// Returns the number of updates received by each arm.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns the mean reward vector of each arm.
+    pub fn means(&self) -> &[Vec<f64>] {
+        &self.means
+    }
+
+    /// Returns `true` if arm `a` Pareto-dominates arm `b`.
+    fn dominates(&self, a: usize, b: usize) -> bool {
+        let (ma, mb) = (&self.means[a], &self.means[b]);
+        ma.iter().zip(mb).all(|(x, y)| x >= y) && ma.iter().zip(mb).any(|(x, y)| x > y)
+    }
+}
@@ -0,0 +1,485 @@
+//! # Bandit Policies
+//!
+//! [`BanditPolicy`] is the object-safe interface the REST service holds every
+//! registered bandit through, as a `Box<dyn BanditPolicy>`. It covers both
+//! context-free strategies, which implement it on top of [`Bandit`], and
+//! contextual ones, which implement it on top of [`ContextualBandit`] and
+//! require a context on every select and update.
+//!
+//! Features only some strategies have (arm labels, an exploration rate,
+//! confidence bonuses, growing and shrinking the arm set) are methods with
+//! defaults that report them as unsupported, so handlers never need to know
+//! which strategy they are talking to.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+//! use rustybrain::bandit::linucb::LinUcb;
+//! use rustybrain::bandit::BanditPolicy;
+//!
+//! let mut policies: Vec<Box<dyn BanditPolicy>> =
+//!     vec![Box::new(EpsilonGreedy::new(2, 0.1)), Box::new(LinUcb::new(2, 1, 1.0))];
+//! for policy in &mut policies {
+//!     let arm = policy.select_arm(Some(&[1.0])).unwrap();
+//!     policy.try_update(arm, 1.0, Some(&[1.0])).unwrap();
+//! }
+//! ```
+
+use super::epsilon_greedy::EpsilonGreedy;
+use super::gaussian_thompson::GaussianThompson;
+use super::linucb::LinUcb;
+use super::thompson::ThompsonSampling;
+use super::ucb1::Ucb1;
+use super::{check_update, Bandit, BanditError, BanditSnapshot, ContextualBandit};
+
+/// A bandit strategy behind a uniform, object-safe interface.
+pub trait BanditPolicy: Send {
+    /// Name of the strategy, as used in REST requests and exports.
+    fn name(&self) -> &'static str;
+
+    /// Number of times each arm has been updated.
+    fn counts(&self) -> &[u64];
+
+    /// Current estimated mean reward of each arm.
+    fn values(&self) -> &[f64];
+
+    /// Owned copy of [`counts`](Self::counts) and [`values`](Self::values).
+    fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts().to_vec(),
+            values: self.values().to_vec(),
+        }
+    }
+
+    /// The strategy as a context-free [`Bandit`], or `None` for contextual
+    /// strategies.
+    fn as_bandit_mut(&mut self) -> Option<&mut dyn Bandit> {
+        None
+    }
+
+    /// Selects an arm. Contextual strategies require `context`; others ignore it.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::InvalidContext`] if a required context is missing
+    /// or invalid.
+    fn select_arm(&mut self, context: Option<&[f64]>) -> Result<usize, BanditError>;
+
+    /// Like [`select_arm`](Self::select_arm), but selects a slate of up to `k`
+    /// distinct arms.
+    ///
+    /// # Errors
+    /// As for [`select_arm`](Self::select_arm).
+    fn select_arms(
+        &mut self,
+        k: usize,
+        context: Option<&[f64]>,
+    ) -> Result<Vec<usize>, BanditError>;
+
+    /// Checks `context` for an update or selection; context-free strategies
+    /// accept anything.
+    fn check_context(&self, _context: Option<&[f64]>) -> Result<(), BanditError> {
+        Ok(())
+    }
+
+    /// Checks that [`try_update`](Self::try_update) would accept `reward` for
+    /// `arm`, without applying it.
+    ///
+    /// The result only holds for a single update against the current state;
+    /// see [`GaussianThompson::check_reward`].
+    fn check_reward(&self, arm: usize, reward: f64) -> Result<(), BanditError> {
+        check_update(self.counts().len(), arm, reward)
+    }
+
+    /// Reports the reward observed for `arm`, rejecting invalid input
+    /// instead of panicking.
+    ///
+    /// # Errors
+    /// Returns the [`BanditError`] describing the rejected arm, reward or context.
+    fn try_update(
+        &mut self,
+        arm: usize,
+        reward: f64,
+        context: Option<&[f64]>,
+    ) -> Result<(), BanditError>;
+
+    /// Clears the learned statistics, keeping the configuration.
+    fn reset(&mut self);
+
+    /// Index of the arm named `label`, for strategies with arm labels.
+    fn arm_index(&self, _label: &str) -> Option<usize> {
+        None
+    }
+
+    /// Name of `arm`, for strategies with arm labels.
+    fn arm_label(&self, _arm: usize) -> Option<&str> {
+        None
+    }
+
+    /// Current exploration rate, for strategies that have one.
+    fn current_epsilon(&self) -> Option<f64> {
+        None
+    }
+
+    /// Per-arm exploration bonus, for strategies that have one.
+    fn bonuses(&self) -> Option<Vec<f64>> {
+        None
+    }
+
+    /// Appends an untried arm, optionally labeled, or returns `None` if the
+    /// strategy cannot grow.
+    fn add_arm(&mut self, _label: Option<String>) -> Option<Result<usize, BanditError>> {
+        None
+    }
+
+    /// Removes `arm`, or returns `false` if the strategy cannot shrink.
+    fn remove_arm(&mut self, _arm: usize) -> bool {
+        false
+    }
+
+    /// A copy of the learned state; randomized strategies reseed their RNG
+    /// with `seed` so the copy explores independently.
+    fn fork(&self, _seed: u64) -> Box<dyn BanditPolicy> {
+        self.clone_box()
+    }
+
+    /// An exact copy of the strategy, RNG state included.
+    fn clone_box(&self) -> Box<dyn BanditPolicy>;
+
+    /// Serializes the full strategy state to JSON.
+    fn to_json(&self) -> String;
+}
+
+impl Clone for Box<dyn BanditPolicy> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl BanditPolicy for EpsilonGreedy {
+    fn name(&self) -> &'static str {
+        "epsilon_greedy"
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+
+    fn as_bandit_mut(&mut self) -> Option<&mut dyn Bandit> {
+        Some(self)
+    }
+
+    fn select_arm(&mut self, _context: Option<&[f64]>) -> Result<usize, BanditError> {
+        Ok(Bandit::select_arm(self))
+    }
+
+    fn select_arms(
+        &mut self,
+        k: usize,
+        _context: Option<&[f64]>,
+    ) -> Result<Vec<usize>, BanditError> {
+        Ok(Bandit::select_arms(self, k))
+    }
+
+    fn try_update(
+        &mut self,
+        arm: usize,
+        reward: f64,
+        _context: Option<&[f64]>,
+    ) -> Result<(), BanditError> {
+        self.try_update(arm, reward)
+    }
+
+    fn reset(&mut self) {
+        self.reset()
+    }
+
+    fn arm_index(&self, label: &str) -> Option<usize> {
+        self.arm_index(label)
+    }
+
+    fn arm_label(&self, arm: usize) -> Option<&str> {
+        self.label(arm)
+    }
+
+    fn current_epsilon(&self) -> Option<f64> {
+        Some(self.current_epsilon())
+    }
+
+    fn add_arm(&mut self, label: Option<String>) -> Option<Result<usize, BanditError>> {
+        Some(match label {
+            Some(label) => self.try_add_labeled_arm(label),
+            None => Ok(self.add_arm()),
+        })
+    }
+
+    fn remove_arm(&mut self, arm: usize) -> bool {
+        self.remove_arm(arm);
+        true
+    }
+
+    fn fork(&self, seed: u64) -> Box<dyn BanditPolicy> {
+        Box::new(self.fork(seed))
+    }
+
+    fn clone_box(&self) -> Box<dyn BanditPolicy> {
+        Box::new(self.clone())
+    }
+
+    fn to_json(&self) -> String {
+        self.to_json()
+    }
+}
+
+impl BanditPolicy for Ucb1 {
+    fn name(&self) -> &'static str {
+        "ucb1"
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+
+    fn as_bandit_mut(&mut self) -> Option<&mut dyn Bandit> {
+        Some(self)
+    }
+
+    fn select_arm(&mut self, _context: Option<&[f64]>) -> Result<usize, BanditError> {
+        Ok(self.select_arm_mut())
+    }
+
+    fn select_arms(
+        &mut self,
+        k: usize,
+        _context: Option<&[f64]>,
+    ) -> Result<Vec<usize>, BanditError> {
+        Ok(Bandit::select_arms(self, k))
+    }
+
+    fn try_update(
+        &mut self,
+        arm: usize,
+        reward: f64,
+        _context: Option<&[f64]>,
+    ) -> Result<(), BanditError> {
+        self.try_update(arm, reward)
+    }
+
+    fn reset(&mut self) {
+        self.reset()
+    }
+
+    fn arm_index(&self, label: &str) -> Option<usize> {
+        self.arm_index(label)
+    }
+
+    fn arm_label(&self, arm: usize) -> Option<&str> {
+        self.label(arm)
+    }
+
+    fn bonuses(&self) -> Option<Vec<f64>> {
+        Some(self.confidence_bounds().into_iter().map(|(_, bonus)| bonus).collect())
+    }
+
+    fn add_arm(&mut self, label: Option<String>) -> Option<Result<usize, BanditError>> {
+        Some(match label {
+            Some(label) => self.try_add_labeled_arm(label),
+            None => Ok(self.add_arm()),
+        })
+    }
+
+    fn remove_arm(&mut self, arm: usize) -> bool {
+        self.remove_arm(arm);
+        true
+    }
+
+    fn clone_box(&self) -> Box<dyn BanditPolicy> {
+        Box::new(self.clone())
+    }
+
+    fn to_json(&self) -> String {
+        self.to_json()
+    }
+}
+
+impl BanditPolicy for ThompsonSampling {
+    fn name(&self) -> &'static str {
+        "thompson"
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+
+    fn as_bandit_mut(&mut self) -> Option<&mut dyn Bandit> {
+        Some(self)
+    }
+
+    fn select_arm(&mut self, _context: Option<&[f64]>) -> Result<usize, BanditError> {
+        Ok(Bandit::select_arm(self))
+    }
+
+    fn select_arms(
+        &mut self,
+        k: usize,
+        _context: Option<&[f64]>,
+    ) -> Result<Vec<usize>, BanditError> {
+        Ok(Bandit::select_arms(self, k))
+    }
+
+    fn try_update(
+        &mut self,
+        arm: usize,
+        reward: f64,
+        _context: Option<&[f64]>,
+    ) -> Result<(), BanditError> {
+        self.try_update(arm, reward)
+    }
+
+    fn reset(&mut self) {
+        self.reset()
+    }
+
+    fn fork(&self, seed: u64) -> Box<dyn BanditPolicy> {
+        Box::new(self.fork(seed))
+    }
+
+    fn clone_box(&self) -> Box<dyn BanditPolicy> {
+        Box::new(self.clone())
+    }
+
+    fn to_json(&self) -> String {
+        self.to_json()
+    }
+}
+
+impl BanditPolicy for GaussianThompson {
+    fn name(&self) -> &'static str {
+        "thompson_gaussian"
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.values()
+    }
+
+    fn as_bandit_mut(&mut self) -> Option<&mut dyn Bandit> {
+        Some(self)
+    }
+
+    fn select_arm(&mut self, _context: Option<&[f64]>) -> Result<usize, BanditError> {
+        Ok(Bandit::select_arm(self))
+    }
+
+    fn select_arms(
+        &mut self,
+        k: usize,
+        _context: Option<&[f64]>,
+    ) -> Result<Vec<usize>, BanditError> {
+        Ok(Bandit::select_arms(self, k))
+    }
+
+    fn check_reward(&self, arm: usize, reward: f64) -> Result<(), BanditError> {
+        self.check_reward(arm, reward)
+    }
+
+    fn try_update(
+        &mut self,
+        arm: usize,
+        reward: f64,
+        _context: Option<&[f64]>,
+    ) -> Result<(), BanditError> {
+        self.try_update(arm, reward)
+    }
+
+    fn reset(&mut self) {
+        self.reset()
+    }
+
+    fn fork(&self, seed: u64) -> Box<dyn BanditPolicy> {
+        Box::new(self.fork(seed))
+    }
+
+    fn clone_box(&self) -> Box<dyn BanditPolicy> {
+        Box::new(self.clone())
+    }
+
+    fn to_json(&self) -> String {
+        self.to_json()
+    }
+}
+
+impl BanditPolicy for LinUcb {
+    fn name(&self) -> &'static str {
+        "linucb"
+    }
+
+    fn counts(&self) -> &[u64] {
+        ContextualBandit::counts(self)
+    }
+
+    fn values(&self) -> &[f64] {
+        ContextualBandit::values(self)
+    }
+
+    fn select_arm(&mut self, context: Option<&[f64]>) -> Result<usize, BanditError> {
+        let context = required_context(self, context)?;
+        Ok(ContextualBandit::select_arm(self, context))
+    }
+
+    fn select_arms(
+        &mut self,
+        k: usize,
+        context: Option<&[f64]>,
+    ) -> Result<Vec<usize>, BanditError> {
+        let context = required_context(self, context)?;
+        Ok(self.select_arms(context, k))
+    }
+
+    fn check_context(&self, context: Option<&[f64]>) -> Result<(), BanditError> {
+        required_context(self, context).map(|_| ())
+    }
+
+    fn try_update(
+        &mut self,
+        arm: usize,
+        reward: f64,
+        context: Option<&[f64]>,
+    ) -> Result<(), BanditError> {
+        let context = context.ok_or(BanditError::InvalidContext { dim: self.dim() })?;
+        ContextualBandit::try_update(self, arm, context, reward)
+    }
+
+    fn reset(&mut self) {
+        self.reset()
+    }
+
+    fn clone_box(&self) -> Box<dyn BanditPolicy> {
+        Box::new(self.clone())
+    }
+
+    fn to_json(&self) -> String {
+        self.to_json()
+    }
+}
+
+/// Returns `context` if it is present and valid for `agent`.
+fn required_context<'a>(agent: &LinUcb, context: Option<&'a [f64]>) -> Result<&'a [f64], BanditError> {
+    let context = context.ok_or(BanditError::InvalidContext { dim: agent.dim() })?;
+    agent.try_context(context)?;
+    Ok(context)
+}
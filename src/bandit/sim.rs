@@ -0,0 +1,324 @@
+//! # Bandit Simulation
+//!
+//! Offline helpers for running a [`Bandit`] against a synthetic environment
+//! with known arm means, so strategies can be compared by regret without
+//! the HTTP layer.
+//!
+//! Regret is reported as **pseudo-regret**: the sum of the gaps
+//! `μ* − μ_i` of the arms actually chosen. It is deterministic given the
+//! bandit and seed, and avoids the noise of realized rewards.
+//!
+//! ## Lai-Robbins Lower Bound
+//!
+//! For Bernoulli arms, any consistent strategy incurs asymptotic regret of
+//! at least
+//!
+//! ```text
+//! Σ_{i: Δ_i > 0}  Δ_i / KL(μ_i, μ*)  ·  ln T
+//! ```
+//!
+//! where `Δ_i = μ* − μ_i` and `KL` is the Bernoulli KL divergence.
+//! [`SimResult::lower_bound`] reports this value for the simulated horizon
+//! so empirical regret can be put in context.
+//!
+//! ## Delayed Feedback
+//!
+//! [`simulate_delayed`] holds each reward back for `delay` steps before
+//! passing it to the bandit, modelling systems where feedback arrives late.
+//! [`delay_impact`] runs the same problem with and without delay on fresh
+//! bandits so the extra regret can be reported.
+//!
+//! ## Cancellation
+//!
+//! [`simulate_cancellable`] checks a shared stop flag before every step, so
+//! a long run can be stopped from another thread. The returned
+//! [`SimResult`] covers only the steps that actually ran.
+//!
+//! ## Custom Reward Streams
+//!
+//! [`run_simulation`] drives a bandit with arbitrary reward samplers, one
+//! closure per arm, such as those built by [`bernoulli_arm`] and
+//! [`gaussian_arm`]. Since the samplers don't expose their means, the
+//! returned [`SimReport`] only records rewards and pulls; its
+//! [`regret`](SimReport::regret) is the realized regret against a known
+//! best-arm mean.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::sim::{simulate, BernoulliEnv};
+//! use rustybrain::bandit::ucb1::Ucb1;
+//!
+//! let env = BernoulliEnv::new(vec![0.2, 0.8]);
+//! let mut agent = Ucb1::new(2, 1.0);
+//! let result = simulate(&mut agent, &env, 500, 7);
+//! assert_eq!(result.steps, 500);
+//! assert!(result.regret >= 0.0);
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::{Distribution, Normal};
+
+use super::{bernoulli_kl, Bandit};
+
+/// Bernoulli reward environment with known arm success probabilities.
+#[derive(Debug, Clone)]
+pub struct BernoulliEnv {
+    means: Vec<f64>,
+}
+
+impl BernoulliEnv {
+    /// Creates an environment whose arm `i` pays `1.0` with probability `means[i]`.
+    ///
+    /// # Panics
+    /// - If `means` is empty
+    /// - If any mean is outside `[0.0, 1.0]`
+    pub fn new(means: Vec<f64>) -> Self {
+        assert!(!means.is_empty(), "must have at least one arm");
+        assert!(
+            means.iter().all(|m| (0.0..=1.0).contains(m)),
+            "Bernoulli means must be between 0.0 and 1.0"
+        );
+        Self { means }
+    }
+
+    /// Returns the true mean reward of each arm.
+    pub fn means(&self) -> &[f64] {
+        &self.means
+    }
+
+    /// Returns the mean reward of the best arm.
+    pub fn best_mean(&self) -> f64 {
+        self.means.iter().cloned().fold(f64::NEG_INFINITY, f64::max)
+    }
+
+    /// Draws a `0.0`/`1.0` reward for `arm`.
+    pub fn pull(&self, arm: usize, rng: &mut StdRng) -> f64 {
+        if rng.gen::<f64>() < self.means[arm] {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Outcome of a simulation run.
+#[derive(Debug, Clone)]
+pub struct SimResult {
+    /// Number of steps executed.
+    pub steps: usize,
+    /// Sum of realized rewards.
+    pub total_reward: f64,
+    /// Cumulative pseudo-regret after the final step.
+    pub regret: f64,
+    /// Cumulative pseudo-regret after each step.
+    pub regret_curve: Vec<f64>,
+    /// Lai-Robbins asymptotic regret lower bound for `steps`.
+    pub lower_bound: f64,
+}
+
+/// Runs `bandit` against `env` for `steps` pulls using a seeded reward stream.
+pub fn simulate(bandit: &mut dyn Bandit, env: &BernoulliEnv, steps: usize, seed: u64) -> SimResult {
+    simulate_delayed(bandit, env, steps, seed, 0)
+}
+
+/// Like [`simulate`], but the reward for a selection made at step `t` is only
+/// passed to the bandit at step `t + delay`.
+///
+/// Rewards still pending when the run ends are delivered after the last step;
+/// they do not affect the reported regret.
+pub fn simulate_delayed(
+    bandit: &mut dyn Bandit,
+    env: &BernoulliEnv,
+    steps: usize,
+    seed: u64,
+    delay: usize,
+) -> SimResult {
+    run(bandit, env, steps, seed, delay, None)
+}
+
+/// Like [`simulate`], but stops early once `stop` is set.
+///
+/// The flag is checked before each step; the result's `steps` field holds
+/// the number of steps that completed, and the regret curve and lower bound
+/// cover only those steps.
+pub fn simulate_cancellable(
+    bandit: &mut dyn Bandit,
+    env: &BernoulliEnv,
+    steps: usize,
+    seed: u64,
+    stop: &AtomicBool,
+) -> SimResult {
+    run(bandit, env, steps, seed, 0, Some(stop))
+}
+
+/// Shared simulation loop; `stop` is polled before every step when given.
+fn run(
+    bandit: &mut dyn Bandit,
+    env: &BernoulliEnv,
+    steps: usize,
+    seed: u64,
+    delay: usize,
+    stop: Option<&AtomicBool>,
+) -> SimResult {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let best = env.best_mean();
+
+    let mut pending: VecDeque<(usize, f64)> = VecDeque::with_capacity(delay + 1);
+    let mut total_reward = 0.0;
+    let mut regret = 0.0;
+    // A cancellable run may be given a huge horizon, so grow on demand instead.
+    let mut regret_curve = match stop {
+        Some(_) => Vec::new(),
+        None => Vec::with_capacity(steps),
+    };
+
+    for _ in 0..steps {
+        if stop.is_some_and(|flag| flag.load(Ordering::Relaxed)) {
+            break;
+        }
+        let arm = bandit.select_arm();
+        let reward = env.pull(arm, &mut rng);
+        pending.push_back((arm, reward));
+        if pending.len() > delay {
+            let (ready_arm, ready_reward) = pending.pop_front().unwrap();
+            bandit.update(ready_arm, ready_reward);
+        }
+
+        total_reward += reward;
+        regret += best - env.means()[arm];
+        regret_curve.push(regret);
+    }
+    for (arm, reward) in pending {
+        bandit.update(arm, reward);
+    }
+
+    let completed = regret_curve.len();
+    SimResult {
+        steps: completed,
+        total_reward,
+        regret,
+        regret_curve,
+        lower_bound: lai_robbins_bound(env.means(), completed),
+    }
+}
+
+/// Regret of the same problem with and without feedback delay.
+#[derive(Debug, Clone)]
+pub struct DelayImpact {
+    /// Result with immediate feedback.
+    pub baseline: SimResult,
+    /// Result with delayed feedback.
+    pub delayed: SimResult,
+}
+
+impl DelayImpact {
+    /// Additional cumulative regret caused by the delay.
+    pub fn extra_regret(&self) -> f64 {
+        self.delayed.regret - self.baseline.regret
+    }
+}
+
+/// Runs two fresh bandits from `make` on the same seeded problem, one with
+/// immediate feedback and one with `delay` steps of feedback delay.
+pub fn delay_impact<B: Bandit>(
+    make: impl Fn() -> B,
+    env: &BernoulliEnv,
+    steps: usize,
+    seed: u64,
+    delay: usize,
+) -> DelayImpact {
+    DelayImpact {
+        baseline: simulate(&mut make(), env, steps, seed),
+        delayed: simulate_delayed(&mut make(), env, steps, seed, delay),
+    }
+}
+
+/// Reward sampler for one arm of [`run_simulation`].
+pub type SimArm = Box<dyn Fn(&mut StdRng) -> f64>;
+
+/// Returns an arm paying `1.0` with probability `p` and `0.0` otherwise.
+///
+/// # Panics
+/// Panics if `p` is outside `[0.0, 1.0]`.
+pub fn bernoulli_arm(p: f64) -> SimArm {
+    assert!((0.0..=1.0).contains(&p), "Bernoulli mean must be between 0.0 and 1.0");
+    Box::new(move |rng: &mut StdRng| if rng.gen::<f64>() < p { 1.0 } else { 0.0 })
+}
+
+/// Returns an arm drawing rewards from a normal distribution.
+///
+/// # Panics
+/// Panics if `std_dev` is negative or not finite.
+pub fn gaussian_arm(mean: f64, std_dev: f64) -> SimArm {
+    let normal = Normal::new(mean, std_dev).expect("std_dev must be finite and non-negative");
+    Box::new(move |rng: &mut StdRng| normal.sample(rng))
+}
+
+/// Outcome of [`run_simulation`].
+#[derive(Debug, Clone)]
+pub struct SimReport {
+    /// Number of steps executed.
+    pub steps: usize,
+    /// Sum of realized rewards.
+    pub cumulative_reward: f64,
+    /// Number of times each arm was pulled.
+    pub pulls: Vec<u64>,
+}
+
+impl SimReport {
+    /// Realized cumulative regret `steps × best_mean − cumulative_reward`
+    /// against the known mean of the best arm.
+    pub fn regret(&self, best_mean: f64) -> f64 {
+        self.steps as f64 * best_mean - self.cumulative_reward
+    }
+}
+
+/// Runs `strategy` for `steps` pulls, sampling each reward from the chosen
+/// arm of `arms` with a single RNG seeded from `seed`.
+///
+/// # Panics
+/// Panics if `arms` is empty or the strategy selects an arm outside `arms`.
+pub fn run_simulation(
+    strategy: &mut dyn Bandit,
+    arms: &[SimArm],
+    steps: usize,
+    seed: u64,
+) -> SimReport {
+    assert!(!arms.is_empty(), "must have at least one arm");
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut pulls = vec![0; arms.len()];
+    let mut cumulative_reward = 0.0;
+
+    for _ in 0..steps {
+        let arm = strategy.select_arm();
+        let reward = arms[arm](&mut rng);
+        strategy.update(arm, reward);
+        pulls[arm] += 1;
+        cumulative_reward += reward;
+    }
+
+    SimReport {
+        steps,
+        cumulative_reward,
+        pulls,
+    }
+}
+
+/// Computes the Lai-Robbins regret lower bound for Bernoulli arms over `steps` pulls.
+///
+/// Returns `0.0` for horizons shorter than two steps, where `ln T ≤ 0`.
+pub fn lai_robbins_bound(means: &[f64], steps: usize) -> f64 {
+    if steps < 2 {
+        return 0.0;
+    }
+    let best = means.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let coefficient: f64 = means
+        .iter()
+        .filter(|&&m| m < best)
+        .map(|&m| (best - m) / bernoulli_kl(m, best))
+        .sum();
+    coefficient * (steps as f64).ln()
+}
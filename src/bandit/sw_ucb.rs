@@ -0,0 +1,190 @@
+//! # Sliding-Window UCB
+//!
+//! UCB1 restricted to the last `W` pulls, for rewards that shift abruptly.
+//! A shared timeline records which arm each of the last `W` pulls went to;
+//! once it is full, every update evicts the oldest observation, whichever
+//! arm it belongs to. Scores use only the windowed statistics:
+//! ```text
+//! score_i = mean_i(W) + c * sqrt(2 * ln min(t, W) / n_i(W))
+//! ```
+//! Arms with no observation inside the window are treated as untried and
+//! selected first (lowest index first).
+//!
+//! [`SlidingWindowUcb::per_arm`] instead keeps the last `W` rewards of each
+//! arm, however long ago they were observed. An arm that stops being pulled
+//! keeps its statistics, so `t` in the bonus is then the total number of
+//! pulls: the bonus of a neglected arm keeps growing until it is retried.
+//!
+//! Unlike [`Ucb1::with_discount`](super::ucb1::Ucb1::with_discount), which
+//! gradually fades old rewards, an observation counts fully until it leaves
+//! the window and not at all afterwards.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::sw_ucb::SlidingWindowUcb;
+//!
+//! let mut agent = SlidingWindowUcb::new(2, 1.0, 50);
+//! let arm = agent.select_arm();
+//! agent.update(arm, 1.0);
+//! assert_eq!(agent.counts()[arm], 1);
+//! ```
+
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::BanditSnapshot;
+
+/// UCB1 over a sliding window of the most recent pulls.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlidingWindowUcb {
+    /// Exploration parameter.
+    c: f64,
+    /// Number of most recent pulls (across all arms) that are remembered.
+    window: usize,
+    /// Arm of each remembered pull, oldest first.
+    timeline: VecDeque<usize>,
+    /// Remembered rewards per arm, oldest first.
+    recent: Vec<VecDeque<f64>>,
+    /// Number of remembered pulls per arm.
+    counts: Vec<u64>,
+    /// Mean of the remembered rewards per arm.
+    values: Vec<f64>,
+    /// Whether the window applies to each arm's pulls rather than to all pulls.
+    #[serde(default)]
+    per_arm: bool,
+    /// Total number of updates, including evicted ones.
+    #[serde(default)]
+    pulls: u64,
+}
+
+impl SlidingWindowUcb {
+    /// Creates an agent with `num_arms` arms, exploration factor `c`, and a
+    /// window of the last `window` pulls.
+    ///
+    /// # Panics
+    /// Panics if `num_arms == 0`, `c` is negative, or `window == 0`.
+    pub fn new(num_arms: usize, c: f64, window: usize) -> Self {
+        assert!(num_arms > 0, "must have at least one arm");
+        assert!(c >= 0.0, "c must be non-negative");
+        assert!(window > 0, "window must be positive");
+        Self {
+            c,
+            window,
+            timeline: VecDeque::with_capacity(window),
+            recent: vec![VecDeque::new(); num_arms],
+            counts: vec![0; num_arms],
+            values: vec![0.0; num_arms],
+            per_arm: false,
+            pulls: 0,
+        }
+    }
+
+    /// Creates an agent that remembers the last `window` rewards of each arm.
+    ///
+    /// # Panics
+    /// Panics if `num_arms == 0`, `c` is negative, or `window == 0`.
+    pub fn per_arm(num_arms: usize, c: f64, window: usize) -> Self {
+        Self {
+            timeline: VecDeque::new(),
+            per_arm: true,
+            ..Self::new(num_arms, c, window)
+        }
+    }
+
+    /// Selects the arm with the highest windowed UCB score.
+    pub fn select_arm(&mut self) -> usize {
+        let all: Vec<usize> = (0..self.counts.len()).collect();
+        self.select_arm_among(&all)
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but only ever returns one of `candidates`.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    pub fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        assert!(!candidates.is_empty(), "must have at least one candidate arm");
+        if let Some(&idx) = candidates.iter().find(|&&i| self.counts[i] == 0) {
+            return idx;
+        }
+
+        let t = if self.per_arm {
+            self.pulls as f64
+        } else {
+            self.timeline.len() as f64
+        };
+        let mut best_arm = candidates[0];
+        let mut best_score = f64::NEG_INFINITY;
+        for &i in candidates {
+            let n = self.counts[i] as f64;
+            let score = self.values[i] + self.c * (2.0 * t.ln() / n).sqrt();
+            if score > best_score {
+                best_score = score;
+                best_arm = i;
+            }
+        }
+        best_arm
+    }
+
+    /// Records `reward` for `chosen_arm`, evicting the oldest pull once the
+    /// window is full.
+    pub fn update(&mut self, chosen_arm: usize, reward: f64) {
+        self.pulls += 1;
+        if self.per_arm {
+            let rewards = &mut self.recent[chosen_arm];
+            if rewards.len() == self.window {
+                rewards.pop_front();
+            }
+            rewards.push_back(reward);
+            self.refresh(chosen_arm);
+            return;
+        }
+        if self.timeline.len() == self.window {
+            let oldest = self.timeline.pop_front().expect("window is non-empty");
+            self.recent[oldest].pop_front();
+            self.refresh(oldest);
+        }
+        self.timeline.push_back(chosen_arm);
+        self.recent[chosen_arm].push_back(reward);
+        self.refresh(chosen_arm);
+    }
+
+    /// Recomputes `arm`'s windowed count and mean from its remembered rewards.
+    fn refresh(&mut self, arm: usize) {
+        let rewards = &self.recent[arm];
+        self.counts[arm] = rewards.len() as u64;
+        self.values[arm] = if rewards.is_empty() {
+            0.0
+        } else {
+            rewards.iter().sum::<f64>() / rewards.len() as f64
+        };
+    }
+
+    /// Returns the window length.
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns whether the window applies per arm (see [`per_arm`](Self::per_arm)).
+    pub fn is_per_arm(&self) -> bool {
+        self.per_arm
+    }
+
+    /// Returns the number of pulls per arm inside the window.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns the mean reward per arm inside the window (`0.0` if none).
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns an owned, consistent copy of the windowed counts and values.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
@@ -0,0 +1,227 @@
+//! # Thompson Sampling (Beta-Bernoulli)
+//!
+//! Bayesian bandit that keeps a `Beta(α, β)` posterior over each arm's
+//! success probability. Every arm starts from the uniform prior `Beta(1, 1)`.
+//!
+//! * **Selection:** draw one sample from each arm's posterior and pick the
+//!   arm with the largest draw.
+//! * **Update:** a reward `r ∈ [0, 1]` is treated as a fractional success,
+//!   so `α += r` and `β += 1 − r`.
+//!
+//! [`ThompsonSampling::probability_best`] estimates, by posterior sampling,
+//! the probability that each arm is truly the best one.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::thompson::ThompsonSampling;
+//!
+//! let mut agent = ThompsonSampling::new(2, 7);
+//! let arm = agent.select_arm();
+//! agent.update(arm, 1.0);
+//! let p = agent.probability_best(1000);
+//! assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+//! ```
+//!
+//! ## Determinism
+//!
+//! The internal RNG (ChaCha12, the generator behind `StdRng`) is seeded at
+//! construction for reproducible tests. It is serialized with the agent, so
+//! [`ThompsonSampling::from_json`] resumes the exact random sequence.
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use rand_distr::{Beta, Distribution};
+use serde::{Deserialize, Serialize};
+
+use super::{check_arm_lengths, check_update, BanditError, BanditSnapshot};
+
+/// Beta-Bernoulli Thompson Sampling agent.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThompsonSampling {
+    /// Posterior α (prior 1 + accumulated successes) per arm.
+    alpha: Vec<f64>,
+    /// Posterior β (prior 1 + accumulated failures) per arm.
+    beta: Vec<f64>,
+    /// Number of updates per arm.
+    counts: Vec<u64>,
+    /// Average (clamped) reward per arm.
+    values: Vec<f64>,
+    /// Deterministic random number generator for reproducibility.
+    rng: ChaCha12Rng,
+}
+
+impl ThompsonSampling {
+    /// Creates an agent with `num_arms` arms and a seeded RNG.
+    ///
+    /// # Panics
+    /// Panics if `num_arms == 0`.
+    pub fn new(num_arms: usize, seed: u64) -> Self {
+        assert!(num_arms > 0, "must have at least one arm");
+        Self {
+            alpha: vec![1.0; num_arms],
+            beta: vec![1.0; num_arms],
+            counts: vec![0; num_arms],
+            values: vec![0.0; num_arms],
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Draws one posterior sample per arm and returns the arm with the largest draw.
+    pub fn select_arm(&mut self) -> usize {
+        let draws = self.sample_posteriors();
+        argmax(&draws)
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but only ever returns one of `candidates`.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    pub fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        assert!(!candidates.is_empty(), "must have at least one candidate arm");
+        let draws = self.sample_posteriors();
+        let mut best = candidates[0];
+        for &i in candidates {
+            if draws[i] > draws[best] {
+                best = i;
+            }
+        }
+        best
+    }
+
+    /// Updates the chosen arm's posterior with a reward in `[0, 1]`.
+    ///
+    /// Rewards outside that range are clamped.
+    pub fn update(&mut self, chosen_arm: usize, reward: f64) {
+        let r = reward.clamp(0.0, 1.0);
+        self.alpha[chosen_arm] += r;
+        self.beta[chosen_arm] += 1.0 - r;
+
+        let n = self.counts[chosen_arm] + 1;
+        self.counts[chosen_arm] = n;
+        self.values[chosen_arm] += (r - self.values[chosen_arm]) / n as f64;
+    }
+
+    /// Like [`update`](Self::update), but rejects out-of-range arms and
+    /// non-finite rewards instead of panicking or corrupting the estimates.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::ArmOutOfRange`] or [`BanditError::NonFiniteReward`].
+    pub fn try_update(&mut self, chosen_arm: usize, reward: f64) -> Result<(), BanditError> {
+        check_update(self.counts.len(), chosen_arm, reward)?;
+        self.update(chosen_arm, reward);
+        Ok(())
+    }
+
+    /// Returns a copy of this agent's posteriors with a freshly seeded RNG.
+    pub fn fork(&self, seed: u64) -> Self {
+        Self {
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            ..self.clone()
+        }
+    }
+
+    /// Resets every posterior to the uniform prior and clears the statistics.
+    ///
+    /// The seed is not stored, so the RNG continues from its current position;
+    /// use [`fork`](Self::fork) on the reset agent to pin a seed.
+    pub fn reset(&mut self) {
+        self.alpha.fill(1.0);
+        self.beta.fill(1.0);
+        self.counts.fill(0);
+        self.values.fill(0.0);
+    }
+
+    /// Estimates `P(arm i is optimal)` for every arm.
+    ///
+    /// Draws `samples` joint posterior samples and returns, per arm, the
+    /// fraction of draws in which it had the largest sampled value.
+    /// Returns all zeros when `samples == 0`.
+    pub fn probability_best(&mut self, samples: usize) -> Vec<f64> {
+        let mut wins = vec![0usize; self.alpha.len()];
+        for _ in 0..samples {
+            let draws = self.sample_posteriors();
+            wins[argmax(&draws)] += 1;
+        }
+        wins.into_iter()
+            .map(|w| if samples == 0 { 0.0 } else { w as f64 / samples as f64 })
+            .collect()
+    }
+
+    /// Returns the posterior α parameter of each arm.
+    pub fn alpha(&self) -> &[f64] {
+        &self.alpha
+    }
+
+    /// Returns the posterior β parameter of each arm.
+    pub fn beta(&self) -> &[f64] {
+        &self.beta
+    }
+
+    /// Serializes the posteriors and RNG state to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("ThompsonSampling state is always serializable")
+    }
+
+    /// Restores an agent previously serialized with [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    /// Returns an error for malformed JSON, inconsistent per-arm state, or
+    /// non-positive posterior parameters.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        use serde::de::Error;
+
+        let agent: Self = serde_json::from_str(json)?;
+        check_arm_lengths(&[
+            agent.alpha.len(),
+            agent.beta.len(),
+            agent.counts.len(),
+            agent.values.len(),
+        ])?;
+        if agent.alpha.iter().chain(&agent.beta).any(|&p| p <= 0.0) {
+            return Err(serde_json::Error::custom("posterior parameters must be positive"));
+        }
+        Ok(agent)
+    }
+
+    /// Returns the number of updates each arm has received.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// Returns the average observed reward of each arm (after clamping).
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns an owned, consistent copy of the per-arm counts and values.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts.clone(),
+            values: self.values.clone(),
+        }
+    }
+
+    /// Draws one sample from each arm's Beta posterior.
+    fn sample_posteriors(&mut self) -> Vec<f64> {
+        self.alpha
+            .iter()
+            .zip(&self.beta)
+            .map(|(&a, &b)| {
+                Beta::new(a, b)
+                    .expect("posterior parameters are always positive")
+                    .sample(&mut self.rng)
+            })
+            .collect()
+    }
+}
+
+/// Index of the largest value, preferring the lowest index on ties.
+fn argmax(values: &[f64]) -> usize {
+    let mut best = 0;
+    for (i, &v) in values.iter().enumerate() {
+        if v > values[best] {
+            best = i;
+        }
+    }
+    best
+}
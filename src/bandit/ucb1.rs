@@ -9,6 +9,40 @@
 //! ```
 //! where t is the total number of pulls.
 //!
+//! The bonus assumes rewards in `[0, 1]`. For other reward ranges,
+//! [`Ucb1::with_range`] scales the bonus by `reward_max − reward_min` so
+//! exploration keeps pace with the reward magnitude.
+//!
+//! An optional cool-down ([`Ucb1::set_cooldown`]) excludes a freshly selected
+//! arm from the next few selections; if every arm is cooling down, the
+//! least-recently selected arm is returned. Selections only count towards the
+//! cool-down when made through [`Ucb1::select_arm_mut`] (or the
+//! [`Bandit`](super::Bandit) trait); [`Ucb1::select_arm`] never changes the agent.
+//!
+//! Untried arms are visited in index order unless
+//! [`Ucb1::set_exploration_priority`] supplies a prior ranking.
+//! [`Ucb1::set_tie_break`] changes how equal scores (and the untried arms
+//! themselves) are ordered; by default the lowest index wins.
+//!
+//! [`Ucb1::new_tuned`] implements UCB1-Tuned, which replaces the constant 2
+//! with `min(1/4, V_i)` where `V_i = σ²_i + sqrt(2 ln t / n_i)` and `σ²_i` is
+//! the arm's observed reward variance:
+//! ```text
+//! score_i = value_i + c * sqrt(ln t / n_i * min(1/4, V_i))
+//! ```
+//! Low-variance arms then get a smaller bonus and are exploited sooner.
+//!
+//! For drifting reward distributions, [`Ucb1::with_discount`] enables
+//! discounted UCB: before each update every arm's effective count and reward
+//! sum are multiplied by `γ`, so the score uses
+//! ```text
+//! N_i = Σ γ^(t−s) 1[arm_s = i]     mean_i = Σ γ^(t−s) r_s 1[arm_s = i] / N_i
+//! ```
+//! and `t` in the bonus becomes `Σ N_i`. Recent rewards dominate and old data
+//! is gradually forgotten.
+//!
+//! The agent implements `Serialize`/`Deserialize`; see [`Ucb1::to_json`].
+//!
 //! ## Example
 //! ```
 //! use rustybrain::bandit::ucb1::Ucb1;
@@ -19,18 +53,101 @@
 //! ```
 
 use std::f64;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use super::aggregation::{aggregate, AggregationPolicy};
+use super::cooldown::Cooldown;
+use super::{
+    all_finite, check_arm_lengths, check_priors, check_update, confidence_interval, index_label,
+    labels_unique, max_ties, sample_variance, welford_push, zip_priors, BanditError,
+    BanditSnapshot, TieBreak,
+};
+use crate::metrics::reward_tracker::RewardTracker;
 
 /// UCB1 Bandit implementation.
 ///
 /// Deterministic exploration-exploitation balance using confidence intervals.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Ucb1 {
     /// Exploration parameter (controls aggressiveness of exploration).
     c: f64,
+    /// Width of the expected reward range; multiplies the confidence bonus.
+    reward_scale: f64,
     /// Number of pulls for each arm.
     counts: Vec<u64>,
     /// Average reward for each arm.
     values: Vec<f64>,
+    /// Sum of squared deviations of each arm's observed rewards from
+    /// `observed_means` (Welford's M2).
+    m2: Vec<f64>,
+    /// Rewards observed per arm, excluding seeded prior counts.
+    #[serde(default)]
+    observed: Vec<u64>,
+    /// Mean of each arm's observed rewards, excluding seeded priors.
+    #[serde(default)]
+    observed_means: Vec<f64>,
+    /// Per-arm cool-down state (disabled by default).
+    cooldown: Cooldown,
+    /// Optional bounded reward history per arm (disabled by default).
+    arm_history: Option<Vec<RewardTracker>>,
+    /// Pulls every arm needs before [`Ucb1::try_select_arm`] commits (0 = none).
+    min_pulls: u64,
+    /// Optional ordering for the initial exploration phase (higher first).
+    exploration_priority: Option<Vec<f64>>,
+    /// Use the UCB1-Tuned variance-aware bonus.
+    #[serde(default)]
+    tuned: bool,
+    /// Discounted statistics; `None` for plain (undiscounted) UCB1.
+    #[serde(default)]
+    discount: Option<Discounted>,
+    /// Optional human-readable name per arm.
+    #[serde(default)]
+    labels: Option<Vec<String>>,
+    /// How selection chooses among arms with equal scores.
+    #[serde(default)]
+    tie_break: TieBreak,
+    /// Only consumed by [`TieBreak::Random`].
+    #[serde(default = "tie_rng")]
+    rng: TieRng,
+}
+
+/// Tie-breaking RNG behind a `Mutex`, so [`Ucb1::select_arm`] can draw from
+/// it through `&self` while the agent stays `Sync`.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+struct TieRng(Mutex<ChaCha12Rng>);
+
+impl TieRng {
+    fn lock(&self) -> MutexGuard<'_, ChaCha12Rng> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clone for TieRng {
+    fn clone(&self) -> Self {
+        TieRng(Mutex::new(self.lock().clone()))
+    }
+}
+
+/// Seed of the RNG behind [`TieBreak::Random`]; UCB1 is otherwise deterministic.
+const TIE_SEED: u64 = 42;
+
+fn tie_rng() -> TieRng {
+    TieRng(Mutex::new(ChaCha12Rng::seed_from_u64(TIE_SEED)))
+}
+
+/// Per-arm statistics that decay by `gamma` on every update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Discounted {
+    gamma: f64,
+    /// Discounted number of pulls per arm.
+    counts: Vec<f64>,
+    /// Discounted reward sum per arm.
+    sums: Vec<f64>,
 }
 
 impl Ucb1 {
@@ -40,37 +157,503 @@ impl Ucb1 {
         assert!(c >= 0.0, "c must be non-negative");
         Self {
             c,
+            reward_scale: 1.0,
             counts: vec![0; num_arms],
             values: vec![0.0; num_arms],
+            m2: vec![0.0; num_arms],
+            observed: vec![0; num_arms],
+            observed_means: vec![0.0; num_arms],
+            cooldown: Cooldown::new(num_arms),
+            arm_history: None,
+            min_pulls: 0,
+            exploration_priority: None,
+            tuned: false,
+            discount: None,
+            labels: None,
+            tie_break: TieBreak::Lowest,
+            rng: tie_rng(),
+        }
+    }
+
+    /// Create a UCB1 agent with one arm per label.
+    ///
+    /// # Panics
+    /// Under the same conditions as [`Ucb1::new`], with `num_arms = labels.len()`.
+    pub fn with_labels(c: f64, labels: Vec<String>) -> Self {
+        let mut agent = Self::new(labels.len(), c);
+        agent.set_labels(labels);
+        agent
+    }
+
+    /// Names the arms, one label per arm.
+    ///
+    /// # Panics
+    /// Panics if `labels.len()` differs from the number of arms or two arms
+    /// share a label.
+    pub fn set_labels(&mut self, labels: Vec<String>) {
+        assert_eq!(labels.len(), self.counts.len(), "labels must have one entry per arm");
+        assert!(labels_unique(&labels), "labels must be unique");
+        self.labels = Some(labels);
+    }
+
+    /// Returns the label of `arm`, or `None` if the arms are unlabeled.
+    pub fn label(&self, arm: usize) -> Option<&str> {
+        self.labels.as_ref()?.get(arm).map(String::as_str)
+    }
+
+    /// Returns the index of the arm labeled `label`.
+    pub fn arm_index(&self, label: &str) -> Option<usize> {
+        self.labels.as_ref()?.iter().position(|l| l == label)
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but also returns the arm's label.
+    pub fn select_labeled(&mut self) -> (usize, Option<&str>) {
+        let arm = self.select_arm();
+        (arm, self.label(arm))
+    }
+
+    /// Records `reward` for the arm labeled `label`.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::UnknownArm`] if no arm has that label, or
+    /// [`BanditError::NonFiniteReward`].
+    pub fn update_labeled(&mut self, label: &str, reward: f64) -> Result<(), BanditError> {
+        let arm = self
+            .arm_index(label)
+            .ok_or_else(|| BanditError::UnknownArm(label.to_string()))?;
+        self.try_update(arm, reward)
+    }
+
+    /// Sets how selection chooses among arms with equal scores, including
+    /// untried arms without an exploration priority (default [`TieBreak::Lowest`]).
+    ///
+    /// [`TieBreak::Random`] draws from an internal RNG seeded with a fixed
+    /// seed, so selections stay reproducible.
+    pub fn set_tie_break(&mut self, tie_break: TieBreak) {
+        self.tie_break = tie_break;
+    }
+
+    /// Returns the tie-breaking rule.
+    pub fn tie_break(&self) -> TieBreak {
+        self.tie_break
+    }
+
+    /// Create a UCB1-Tuned agent, whose bonus shrinks for arms with low
+    /// observed reward variance.
+    ///
+    /// # Panics
+    /// Same conditions as [`Ucb1::new`].
+    pub fn new_tuned(num_arms: usize, c: f64) -> Self {
+        Self {
+            tuned: true,
+            ..Self::new(num_arms, c)
+        }
+    }
+
+    /// Create a discounted UCB1 agent that forgets old observations at rate `gamma`.
+    ///
+    /// Selection uses the discounted counts and means; [`values`](Self::values)
+    /// and [`counts`](Self::counts) still report undiscounted statistics.
+    /// `gamma == 1.0` behaves exactly like [`Ucb1::new`].
+    ///
+    /// # Panics
+    /// Panics if `gamma` is not in `(0, 1]`, or under the same conditions as [`Ucb1::new`].
+    pub fn with_discount(num_arms: usize, c: f64, gamma: f64) -> Self {
+        assert!(gamma > 0.0 && gamma <= 1.0, "gamma must be in (0, 1]");
+        let discount = (gamma < 1.0).then(|| Discounted {
+            gamma,
+            counts: vec![0.0; num_arms],
+            sums: vec![0.0; num_arms],
+        });
+        Self {
+            discount,
+            ..Self::new(num_arms, c)
+        }
+    }
+
+    /// Returns the discount factor (`1.0` when discounting is disabled).
+    pub fn gamma(&self) -> f64 {
+        self.discount.as_ref().map_or(1.0, |d| d.gamma)
+    }
+
+    /// Enables a per-arm reward history holding the last `window` rewards of each arm.
+    ///
+    /// History is off by default so memory stays bounded by the arm count.
+    ///
+    /// # Panics
+    /// Panics if `window == 0`.
+    pub fn with_arm_history(mut self, window: usize) -> Self {
+        self.arm_history = Some(vec![RewardTracker::new(window); self.values.len()]);
+        self
+    }
+
+    /// Returns `(mean, min, max, count)` over the recent rewards of `arm`,
+    /// or `None` if per-arm history is disabled.
+    pub fn arm_stats(&self, arm: usize) -> Option<(f64, f64, f64, usize)> {
+        let t = &self.arm_history.as_ref()?[arm];
+        Some((t.mean(), t.min(), t.max(), t.count()))
+    }
+
+    /// Create a UCB1 agent whose arms start from historical `(value, count)`
+    /// priors, one pair per arm.
+    ///
+    /// Updates continue the running mean from the seeded counts, and arms
+    /// with a non-zero prior count are not treated as untried. Variance
+    /// estimates, including UCB1-Tuned's, only cover rewards observed after
+    /// construction.
+    ///
+    /// # Panics
+    /// Under the same conditions as [`Ucb1::new`], with `num_arms = priors.len()`,
+    /// or if a prior value is not finite or a prior count exceeds
+    /// [`MAX_PRIOR_COUNT`](super::MAX_PRIOR_COUNT).
+    pub fn with_priors(c: f64, priors: Vec<(f64, u64)>) -> Self {
+        if let Err(msg) = check_priors(&priors) {
+            panic!("{}", msg);
+        }
+        let mut agent = Self::new(priors.len(), c);
+        (agent.values, agent.counts) = priors.into_iter().unzip();
+        agent
+    }
+
+    /// Like [`with_priors`](Self::with_priors), but takes the prior counts and
+    /// values as separate per-arm slices and reports bad priors as an error.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::InvalidPriors`] if `counts` and `values` differ in
+    /// length, a value is not finite, or a count exceeds
+    /// [`MAX_PRIOR_COUNT`](super::MAX_PRIOR_COUNT).
+    ///
+    /// # Panics
+    /// Under the same conditions as [`Ucb1::new`], with `num_arms = counts.len()`.
+    pub fn try_with_prior_stats(
+        c: f64,
+        counts: &[u64],
+        values: &[f64],
+    ) -> Result<Self, BanditError> {
+        zip_priors(counts, values).map(|priors| Self::with_priors(c, priors))
+    }
+
+    /// Create a UCB1 agent for rewards in `[reward_min, reward_max]`.
+    ///
+    /// The confidence bonus is scaled by `reward_max - reward_min`.
+    ///
+    /// # Panics
+    /// Panics if `reward_min >= reward_max`, or under the same conditions as [`Ucb1::new`].
+    pub fn with_range(num_arms: usize, c: f64, reward_min: f64, reward_max: f64) -> Self {
+        assert!(reward_min < reward_max, "reward_min must be < reward_max");
+        Self {
+            reward_scale: reward_max - reward_min,
+            ..Self::new(num_arms, c)
+        }
+    }
+
+    /// Clears all learned statistics, including any priors, and re-seeds the
+    /// tie-breaking RNG.
+    ///
+    /// Configuration (`c`, reward range, discount, labels, cool-down period,
+    /// history window, warm-up, exploration priority) is kept, so the agent
+    /// replays exactly as if freshly constructed.
+    pub fn reset(&mut self) {
+        self.counts.fill(0);
+        self.values.fill(0.0);
+        self.m2.fill(0.0);
+        self.observed.fill(0);
+        self.observed_means.fill(0.0);
+        self.rng = tie_rng();
+        self.cooldown.reset();
+        if let Some(history) = &mut self.arm_history {
+            for t in history.iter_mut() {
+                *t = RewardTracker::new(t.window());
+            }
+        }
+        if let Some(discount) = &mut self.discount {
+            discount.counts.fill(0.0);
+            discount.sums.fill(0.0);
+        }
+    }
+
+    /// Appends a new, untried arm and returns its index. Existing arms keep
+    /// their statistics.
+    ///
+    /// On a labeled agent the new arm is labeled with its index (suffixed
+    /// with `_1`, `_2`, … if another arm already has that name); use
+    /// [`add_labeled_arm`](Self::add_labeled_arm) to name it.
+    pub fn add_arm(&mut self) -> usize {
+        let arm = self.counts.len();
+        self.counts.push(0);
+        self.values.push(0.0);
+        self.m2.push(0.0);
+        self.observed.push(0);
+        self.observed_means.push(0.0);
+        self.cooldown.add_arm();
+        if let Some(history) = &mut self.arm_history {
+            history.push(RewardTracker::new(history[0].window()));
+        }
+        if let Some(priority) = &mut self.exploration_priority {
+            // New arms are explored after every prioritized arm.
+            priority.push(f64::NEG_INFINITY);
+        }
+        if let Some(discount) = &mut self.discount {
+            discount.counts.push(0.0);
+            discount.sums.push(0.0);
+        }
+        if let Some(labels) = &mut self.labels {
+            labels.push(index_label(labels, arm));
+        }
+        arm
+    }
+
+    /// Like [`add_arm`](Self::add_arm), but names the new arm `label`.
+    ///
+    /// If the agent was unlabeled, its existing arms are labeled with their indices.
+    ///
+    /// # Panics
+    /// Panics if another arm already carries `label`; see
+    /// [`try_add_labeled_arm`](Self::try_add_labeled_arm).
+    pub fn add_labeled_arm(&mut self, label: String) -> usize {
+        self.try_add_labeled_arm(label).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Like [`add_labeled_arm`](Self::add_labeled_arm), but rejects a label
+    /// already in use, counting the index labels an unlabeled agent would get.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::DuplicateLabel`] and leaves the agent unchanged.
+    pub fn try_add_labeled_arm(&mut self, label: String) -> Result<usize, BanditError> {
+        let n = self.counts.len();
+        let taken = match &self.labels {
+            Some(labels) => labels.contains(&label),
+            None => (0..n).any(|i| i.to_string() == label),
+        };
+        if taken {
+            return Err(BanditError::DuplicateLabel(label));
+        }
+        self.labels.get_or_insert_with(|| (0..n).map(|i| i.to_string()).collect());
+        let arm = self.add_arm();
+        if let Some(labels) = &mut self.labels {
+            labels[arm] = label;
         }
+        Ok(arm)
+    }
+
+    /// Removes `arm`; every later arm shifts down by one index and keeps its
+    /// statistics.
+    ///
+    /// # Panics
+    /// Panics if `arm` is out of range or is the only arm.
+    pub fn remove_arm(&mut self, arm: usize) {
+        assert!(arm < self.counts.len(), "arm out of range");
+        assert!(self.counts.len() > 1, "must have at least one arm");
+        self.counts.remove(arm);
+        self.values.remove(arm);
+        self.m2.remove(arm);
+        self.observed.remove(arm);
+        self.observed_means.remove(arm);
+        self.cooldown.remove_arm(arm);
+        if let Some(history) = &mut self.arm_history {
+            history.remove(arm);
+        }
+        if let Some(priority) = &mut self.exploration_priority {
+            priority.remove(arm);
+        }
+        if let Some(discount) = &mut self.discount {
+            discount.counts.remove(arm);
+            discount.sums.remove(arm);
+        }
+        if let Some(labels) = &mut self.labels {
+            labels.remove(arm);
+        }
+    }
+
+    /// Excludes each selected arm from the next `cooldown` selections.
+    ///
+    /// Arms keep accepting updates while cooling down. `0` disables the cool-down.
+    pub fn set_cooldown(&mut self, cooldown: usize) {
+        self.cooldown.set_period(cooldown);
+    }
+
+    /// Returns the configured cool-down period.
+    pub fn cooldown(&self) -> usize {
+        self.cooldown.period()
+    }
+
+    /// Sets how many updates every arm needs before [`try_select_arm`](Self::try_select_arm)
+    /// returns a selection. `0` disables the warm-up.
+    pub fn set_min_pulls(&mut self, min_pulls: u64) {
+        self.min_pulls = min_pulls;
+    }
+
+    /// Returns the configured warm-up pull count.
+    pub fn min_pulls(&self) -> u64 {
+        self.min_pulls
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but returns `None` without
+    /// selecting while any arm has fewer than [`min_pulls`](Self::min_pulls) updates.
+    pub fn try_select_arm(&self) -> Option<usize> {
+        if self.counts.iter().any(|&n| n < self.min_pulls) {
+            return None;
+        }
+        Some(self.select_arm())
+    }
+
+    /// Visits untried arms in descending `priority` order instead of index order.
+    ///
+    /// Ties keep index order. Once every arm has been tried the priority has no effect.
+    ///
+    /// # Panics
+    /// Panics if `priority.len()` differs from the number of arms.
+    pub fn set_exploration_priority(&mut self, priority: Vec<f64>) {
+        assert_eq!(
+            priority.len(),
+            self.counts.len(),
+            "priority must have one entry per arm"
+        );
+        self.exploration_priority = Some(priority);
     }
 
     /// Selects the next arm index based on UCB1 formula.
+    ///
+    /// Arms that are cooling down are skipped, but the selection itself is
+    /// not recorded; use [`select_arm_mut`](Self::select_arm_mut) when a
+    /// [cool-down](Self::set_cooldown) is set.
     pub fn select_arm(&self) -> usize {
+        let all: Vec<usize> = (0..self.counts.len()).collect();
+        self.select_arm_among(&all)
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but only ever returns one of `candidates`.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    pub fn select_arm_among(&self, candidates: &[usize]) -> usize {
+        assert!(!candidates.is_empty(), "must have at least one candidate arm");
+        let eligible = self.cooldown.eligible(candidates);
+        if eligible.is_empty() {
+            return self.cooldown.least_recent(candidates);
+        }
+        let tied = self.best_arms(&eligible);
+        self.tie_break.pick(&tied, &mut *self.rng.lock())
+    }
+
+    /// Like [`select_arm`](Self::select_arm), but also starts the selected
+    /// arm's cool-down.
+    pub fn select_arm_mut(&mut self) -> usize {
+        let all: Vec<usize> = (0..self.counts.len()).collect();
+        self.select_arm_among_mut(&all)
+    }
+
+    /// Like [`select_arm_among`](Self::select_arm_among), but also starts the
+    /// selected arm's cool-down.
+    ///
+    /// # Panics
+    /// Panics if `candidates` is empty.
+    pub fn select_arm_among_mut(&mut self, candidates: &[usize]) -> usize {
+        let arm = self.select_arm_among(candidates);
+        self.cooldown.record(arm);
+        arm
+    }
+
+    /// Returns the candidate arms tied for the highest UCB1 score.
+    fn best_arms(&self, candidates: &[usize]) -> Vec<usize> {
+        if let Some(discount) = &self.discount {
+            return self.best_discounted_arms(candidates, discount);
+        }
+
         // total pulls so far
         let total: u64 = self.counts.iter().sum();
 
-        // If any arm hasn't been tried yet, pick it first.
-        if let Some((idx, _)) = self.counts.iter().enumerate().find(|(_, &n)| n == 0) {
-            return idx;
+        // If any arm hasn't been tried yet, pick among those first.
+        if let Some(untried) = self.untried_arms(candidates) {
+            return untried;
         }
 
         // Compute UCB1 score for each arm
         let t = total as f64;
-        let mut best_arm = 0;
-        let mut best_score = f64::NEG_INFINITY;
-
-        for i in 0..self.values.len() {
-            let mean = self.values[i];
-            let n = self.counts[i] as f64;
-            let bonus = self.c * (2.0 * t.ln() / n).sqrt();
-            let score = mean + bonus;
-            if score > best_score {
-                best_score = score;
-                best_arm = i;
-            }
+        max_ties(candidates, |i| self.values[i] + self.arm_bonus(i, t))
+    }
+
+    /// Like [`best_arms`](Self::best_arms), but scores arms with discounted statistics.
+    fn best_discounted_arms(&self, candidates: &[usize], discount: &Discounted) -> Vec<usize> {
+        if let Some(untried) = self.untried_arms(candidates) {
+            return untried;
+        }
+
+        // A long-unplayed arm's weight can underflow to zero: treat it as untried.
+        let faded: Vec<usize> = candidates
+            .iter()
+            .copied()
+            .filter(|&i| discount.counts[i] <= 0.0)
+            .collect();
+        if !faded.is_empty() {
+            return faded;
+        }
+
+        let t: f64 = discount.counts.iter().sum();
+        max_ties(candidates, |i| {
+            let n = discount.counts[i];
+            discount.sums[i] / n + self.bonus(t, n)
+        })
+    }
+
+    /// Confidence bonus for an arm with `n` (possibly discounted) pulls out of `t`.
+    fn bonus(&self, t: f64, n: f64) -> f64 {
+        self.c * self.reward_scale * (2.0 * t.ln().max(0.0) / n).sqrt()
+    }
+
+    /// Undiscounted confidence bonus of pulled arm `i` out of `t` total pulls.
+    fn arm_bonus(&self, i: usize, t: f64) -> f64 {
+        let n = self.counts[i] as f64;
+        if !self.tuned {
+            return self.bonus(t, n);
+        }
+        // Variance of rewards rescaled to [0, 1], so the 1/4 cap applies. Arms
+        // seeded from priors assume the worst case until they observe rewards.
+        let variance = match self.observed[i] {
+            0 => 0.25,
+            o => self.m2[i] / o as f64 / (self.reward_scale * self.reward_scale),
+        };
+        let v = variance + (2.0 * t.ln() / n).sqrt();
+        self.c * self.reward_scale * (t.ln() / n * v.min(0.25)).sqrt()
+    }
+
+    /// Returns `(mean, bonus)` per arm as used by the current selection score,
+    /// so `mean + bonus` is the arm's upper confidence bound.
+    ///
+    /// Never-pulled arms report a bonus of `0.0`. Discounted agents report
+    /// their discounted means and bonuses.
+    pub fn confidence_bounds(&self) -> Vec<(f64, f64)> {
+        let total: u64 = self.counts.iter().sum();
+        (0..self.counts.len())
+            .map(|i| {
+                if self.counts[i] == 0 {
+                    return (self.values[i], 0.0);
+                }
+                match &self.discount {
+                    None => (self.values[i], self.arm_bonus(i, total as f64)),
+                    Some(d) if d.counts[i] > 0.0 => {
+                        let t: f64 = d.counts.iter().sum();
+                        (d.sums[i] / d.counts[i], self.bonus(t, d.counts[i]))
+                    }
+                    // Weight underflowed to zero: treated as never pulled.
+                    Some(_) => (0.0, 0.0),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the untried candidates to explore next, if any: the single
+    /// highest-priority one when an exploration priority is set, otherwise all
+    /// of them (for the tie-break to choose from).
+    fn untried_arms(&self, candidates: &[usize]) -> Option<Vec<usize>> {
+        let untried = candidates.iter().copied().filter(|&i| self.counts[i] == 0);
+        if let Some(priority) = &self.exploration_priority {
+            let best = untried.reduce(|b, i| if priority[i] > priority[b] { i } else { b });
+            best.map(|i| vec![i])
+        } else {
+            let untried: Vec<usize> = untried.collect();
+            (!untried.is_empty()).then_some(untried)
         }
-        best_arm
     }
 
     /// Updates the reward statistics for the selected arm.
@@ -80,6 +663,136 @@ impl Ucb1 {
         let new_value = old_value + (reward - old_value) / n as f64;
         self.counts[chosen_arm] = n;
         self.values[chosen_arm] = new_value;
+        welford_push(
+            &mut self.observed[chosen_arm],
+            &mut self.observed_means[chosen_arm],
+            &mut self.m2[chosen_arm],
+            reward,
+        );
+
+        if let Some(discount) = &mut self.discount {
+            for (n, sum) in discount.counts.iter_mut().zip(&mut discount.sums) {
+                *n *= discount.gamma;
+                *sum *= discount.gamma;
+            }
+            discount.counts[chosen_arm] += 1.0;
+            discount.sums[chosen_arm] += reward;
+        }
+
+        if let Some(history) = &mut self.arm_history {
+            history[chosen_arm].update(reward);
+        }
+    }
+
+    /// Like [`update`](Self::update), but rejects out-of-range arms and
+    /// non-finite rewards instead of panicking or corrupting the estimates.
+    ///
+    /// # Errors
+    /// Returns [`BanditError::ArmOutOfRange`] or [`BanditError::NonFiniteReward`].
+    pub fn try_update(&mut self, chosen_arm: usize, reward: f64) -> Result<(), BanditError> {
+        check_update(self.counts.len(), chosen_arm, reward)?;
+        self.update(chosen_arm, reward);
+        Ok(())
+    }
+
+    /// Returns the unbiased sample variance of the rewards `arm` has
+    /// observed, or `None` with fewer than two observations.
+    ///
+    /// Seeded [priors](Self::with_priors) carry no spread, so they are not
+    /// counted as observations.
+    pub fn arm_variance(&self, arm: usize) -> Option<f64> {
+        sample_variance(self.m2[arm], self.observed[arm])
+    }
+
+    /// Returns `mean ± z * sqrt(var / n)` over the rewards `arm` has observed,
+    /// excluding seeded priors.
+    ///
+    /// The interval is `(-inf, inf)` while the arm has fewer than two observations.
+    pub fn arm_confidence_interval(&self, arm: usize, z: f64) -> (f64, f64) {
+        let (n, mean) = (self.observed[arm], self.observed_means[arm]);
+        confidence_interval(mean, self.m2[arm], n, z)
+    }
+
+    /// Applies a batch of `(arm, reward)` updates, combining rewards for the
+    /// same arm according to `policy` first.
+    ///
+    /// # Example
+    /// ```
+    /// use rustybrain::bandit::aggregation::AggregationPolicy;
+    ///
+    /// let mut agent = rustybrain::bandit::ucb1::Ucb1::new(1, 2.0);
+    /// agent.update_batch(&[(0, 1.0), (0, 3.0)], AggregationPolicy::Mean);
+    /// assert_eq!(agent.counts()[0], 1);
+    /// assert_eq!(agent.values()[0], 2.0);
+    /// ```
+    pub fn update_batch(&mut self, updates: &[(usize, f64)], policy: AggregationPolicy) {
+        for (arm, reward) in aggregate(updates, policy) {
+            self.update(arm, reward);
+        }
+    }
+
+    /// Serializes the full agent state to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Ucb1 state is always serializable")
+    }
+
+    /// Restores an agent previously serialized with [`to_json`](Self::to_json).
+    ///
+    /// # Errors
+    /// Returns an error for malformed JSON, inconsistent per-arm state,
+    /// non-finite statistics, or configuration the constructors would reject
+    /// (`c`, reward range, discount factor).
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        use serde::de::Error;
+
+        let mut agent: Self = serde_json::from_str(json)?;
+        if agent.observed.is_empty() && agent.observed_means.is_empty() {
+            // Saved before priors were tracked apart from observations.
+            agent.observed = agent.counts.clone();
+            agent.observed_means = agent.values.clone();
+        }
+        let mut lengths = vec![
+            agent.counts.len(),
+            agent.values.len(),
+            agent.m2.len(),
+            agent.observed.len(),
+            agent.observed_means.len(),
+            agent.cooldown.num_arms(),
+        ];
+        lengths.extend(agent.arm_history.as_ref().map(Vec::len));
+        lengths.extend(agent.exploration_priority.as_ref().map(Vec::len));
+        lengths.extend(agent.labels.as_ref().map(Vec::len));
+        if agent.labels.as_deref().is_some_and(|labels| !labels_unique(labels)) {
+            return Err(serde_json::Error::custom("labels must be unique"));
+        }
+        if let Some(discount) = &agent.discount {
+            lengths.extend([discount.counts.len(), discount.sums.len()]);
+        }
+        check_arm_lengths(&lengths)?;
+        agent.check_config().map_err(serde_json::Error::custom)?;
+        let discounted = agent.discount.iter().flat_map(|d| [&d.counts, &d.sums]);
+        let stats = [&agent.values, &agent.m2, &agent.observed_means];
+        if !stats.into_iter().chain(discounted).all(|v| all_finite(v)) {
+            return Err(serde_json::Error::custom("per-arm statistics must be finite"));
+        }
+        if agent.observed.iter().zip(&agent.counts).any(|(o, n)| o > n) {
+            return Err(serde_json::Error::custom("observed counts exceed total counts"));
+        }
+        Ok(agent)
+    }
+
+    /// Checks the invariants the constructors enforce.
+    fn check_config(&self) -> Result<(), &'static str> {
+        if !(self.c >= 0.0 && self.c.is_finite()) {
+            return Err("c must be non-negative");
+        }
+        if !(self.reward_scale > 0.0 && self.reward_scale.is_finite()) {
+            return Err("reward_min must be < reward_max");
+        }
+        if self.discount.as_ref().is_some_and(|d| !(d.gamma > 0.0 && d.gamma <= 1.0)) {
+            return Err("gamma must be in (0, 1]");
+        }
+        Ok(())
     }
 
     /// Returns total number of selections per arm.
@@ -91,4 +804,12 @@ impl Ucb1 {
     pub fn values(&self) -> &[f64] {
         &self.values
     }
-}
\ No newline at end of file
+
+    /// Returns an owned, consistent copy of the per-arm counts and values.
+    pub fn snapshot(&self) -> BanditSnapshot {
+        BanditSnapshot {
+            counts: self.counts.clone(),
+            values: self.values.clone(),
+        }
+    }
+}
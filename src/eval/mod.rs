@@ -0,0 +1,6 @@
+//! Offline evaluation of bandit policies against logged data.
+//!
+//! - [`replay`]: replay-based estimation of a policy's reward from a log of
+//!   `(context, arm, reward)` events.
+
+pub mod replay;
@@ -0,0 +1,133 @@
+//! # Replay Evaluation
+//!
+//! Estimates how a candidate policy would have performed on historical
+//! traffic, following Li et al. (2011), "Unbiased offline evaluation of
+//! contextual-bandit-based news article recommendation algorithms".
+//!
+//! Events are streamed through the policy in log order. For each event the
+//! policy selects an arm; if it matches the logged arm, the logged reward is
+//! credited and fed back to the policy, otherwise the event is discarded and
+//! the policy learns nothing from it. The mean reward over matched events
+//! estimates the policy's online reward.
+//!
+//! The estimate is unbiased when the log was collected by a policy that chose
+//! arms uniformly at random; about `1/K` of the events then match for `K`
+//! arms, so logs need to be correspondingly long.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::bandit::ucb1::Ucb1;
+//! use rustybrain::eval::replay::{replay, LoggedEvent};
+//!
+//! let log = vec![
+//!     LoggedEvent::new(0, 1.0),
+//!     LoggedEvent::new(1, 0.0),
+//!     LoggedEvent::new(1, 1.0),
+//! ];
+//! let report = replay(&mut Ucb1::new(2, 1.0), &log);
+//! assert_eq!(report.events, 3);
+//! assert!(report.matched >= 1);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::bandit::{Bandit, ContextualBandit};
+
+/// One logged decision: the arm that was shown and the reward it earned.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoggedEvent {
+    /// Features observed before the decision; empty for context-free logs.
+    #[serde(default)]
+    pub context: Vec<f64>,
+    /// Arm chosen by the logging policy.
+    pub arm: usize,
+    /// Reward observed for that arm.
+    pub reward: f64,
+}
+
+impl LoggedEvent {
+    /// A context-free event.
+    pub fn new(arm: usize, reward: f64) -> Self {
+        Self {
+            context: Vec::new(),
+            arm,
+            reward,
+        }
+    }
+
+    /// An event with the `context` the decision was made in.
+    pub fn with_context(context: Vec<f64>, arm: usize, reward: f64) -> Self {
+        Self { context, arm, reward }
+    }
+}
+
+/// Outcome of a replay run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayReport {
+    /// Number of logged events replayed.
+    pub events: usize,
+    /// Events where the policy chose the logged arm.
+    pub matched: usize,
+    /// Sum of the rewards of matched events.
+    pub total_reward: f64,
+}
+
+impl ReplayReport {
+    /// Estimated mean reward per decision, or `None` if no event matched.
+    pub fn mean_reward(&self) -> Option<f64> {
+        (self.matched > 0).then(|| self.total_reward / self.matched as f64)
+    }
+
+    /// Fraction of events that matched (`0.0` for an empty log).
+    pub fn match_rate(&self) -> f64 {
+        if self.events == 0 {
+            return 0.0;
+        }
+        self.matched as f64 / self.events as f64
+    }
+}
+
+/// Replays `log` through a context-free `policy`; event contexts are ignored.
+///
+/// Logged arms the policy doesn't have never match.
+pub fn replay(policy: &mut dyn Bandit, log: &[LoggedEvent]) -> ReplayReport {
+    run(log, |event| {
+        let arm = policy.select_arm();
+        let matched = arm == event.arm;
+        if matched {
+            policy.update(arm, event.reward);
+        }
+        matched
+    })
+}
+
+/// Replays `log` through a contextual `policy`, using each event's context.
+///
+/// # Panics
+/// Panics if an event's context length differs from the policy's dimension.
+pub fn replay_contextual(policy: &mut dyn ContextualBandit, log: &[LoggedEvent]) -> ReplayReport {
+    run(log, |event| {
+        let arm = policy.select_arm(&event.context);
+        let matched = arm == event.arm;
+        if matched {
+            policy.update(arm, &event.context, event.reward);
+        }
+        matched
+    })
+}
+
+/// Shared replay loop; `step` returns whether the policy matched the event.
+fn run(log: &[LoggedEvent], mut step: impl FnMut(&LoggedEvent) -> bool) -> ReplayReport {
+    let mut report = ReplayReport {
+        events: log.len(),
+        matched: 0,
+        total_reward: 0.0,
+    };
+    for event in log {
+        if step(event) {
+            report.matched += 1;
+            report.total_reward += event.reward;
+        }
+    }
+    report
+}
@@ -4,15 +4,13 @@
 //! stabilizes reward values in online-learning scenarios (e.g. bandits).
 
 pub mod reward_normalizer;
+mod rolling_window;
 pub mod service;
 
-pub mod metrics {
-    pub mod reward_tracker;
-}
+pub mod metrics;
 
-pub mod bandit {
-    pub mod epsilon_greedy;
-    pub mod ucb1;
-}
+pub mod bandit;
+
+pub mod eval;
 
 pub mod optimizer;
\ No newline at end of file
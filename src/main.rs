@@ -1,15 +1,49 @@
+use std::path::PathBuf;
+
 use axum::Router;
-use rustybrain::service::{bandit_api, optimizer_api, training_api};
+use rustybrain::service::{
+    bandit_api::{self, BanditApiConfig, BanditRegistry},
+    dueling_api, init_tracing, metrics_api, optimizer_api,
+    training_api::{self, TrainingApiConfig},
+    AppState,
+};
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    init_tracing();
+
+    let (train_routes, train_shutdown) =
+        training_api::routes_with_shutdown(TrainingApiConfig::default());
+    // Restore bandits saved via POST /bandit/snapshot, if configured.
+    let mut state = AppState::default();
+    if let Some(path) = std::env::var_os("RUSTYBRAIN_SNAPSHOT").map(PathBuf::from) {
+        if path.exists() {
+            state.bandits = BanditRegistry::load_from_path(&path)?;
+            tracing::info!(path = %path.display(), "restored bandit snapshot");
+        }
+    }
+    // POST /bandit/snapshot is only enabled with a directory to write into.
+    let bandit_config = BanditApiConfig {
+        snapshot_dir: std::env::var_os("RUSTYBRAIN_SNAPSHOT_DIR").map(PathBuf::from),
+        ..BanditApiConfig::default()
+    };
     let app = Router::new()
-        .nest("/bandit", bandit_api::routes())
+        .nest(
+            "/bandit",
+            bandit_api::routes_with_registry(bandit_config, state.bandits.clone()),
+        )
+        .nest("/duel", dueling_api::routes())
+        .nest("/metrics", metrics_api::routes(state))
         .nest("/optimizer", optimizer_api::routes())
-        .nest("/train", training_api::routes());
+        .nest("/train", train_routes);
 
     let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
-    println!("🚀 rustybrain orchestrator running at http://127.0.0.1:8080");
-    axum::serve(listener, app).await?;
+    tracing::info!("rustybrain orchestrator running at http://127.0.0.1:8080");
+    axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            let _ = tokio::signal::ctrl_c().await;
+            train_shutdown.drain().await;
+        })
+        .await?;
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,70 @@
+//! CUSUM change-point detection over a [`RewardTracker`] window.
+//!
+//! For window values `x_0 … x_{n-1}` with mean `x̄`, the cumulative sum
+//! `S_k = Σ_{i≤k} (x_i − x̄)` drifts away from zero on one side of a level
+//! shift and returns on the other, so `|S_k|` peaks at the last sample
+//! before the shift.
+//!
+//! The peak is scaled by `σ · √n`, where `σ` is estimated from successive
+//! differences so that the level shift itself does not inflate it. Under a
+//! stable mean the scaled peak follows the supremum of a Brownian bridge,
+//! which exceeds [`CUSUM_THRESHOLD`] only about 5% of the time.
+
+use super::reward_tracker::RewardTracker;
+
+/// Critical value of the scaled CUSUM statistic (≈ 95th percentile of the
+/// Kolmogorov distribution).
+const CUSUM_THRESHOLD: f64 = 1.358;
+
+/// Minimum window length for which detection is attempted.
+const MIN_SAMPLES: usize = 4;
+
+/// Returns the window index at which a significant mean shift begins, or
+/// `None` if the windowed rewards look stationary.
+///
+/// The index refers to [`RewardTracker::values`]: values before it belong to
+/// the old level, values from it onwards to the new one. Windows shorter than
+/// four samples, or with no sample-to-sample variation, yield `None`.
+///
+/// # Example
+/// ```
+/// use rustybrain::metrics::change_point;
+/// use rustybrain::metrics::reward_tracker::RewardTracker;
+///
+/// let mut tracker = RewardTracker::new(20);
+/// for i in 0..20 {
+///     let noise = if i % 2 == 0 { 0.1 } else { -0.1 };
+///     tracker.update(if i < 12 { 1.0 } else { 3.0 } + noise);
+/// }
+/// assert_eq!(change_point(&tracker), Some(12));
+/// ```
+pub fn change_point(tracker: &RewardTracker) -> Option<usize> {
+    let values = tracker.values();
+    let n = values.len();
+    if n < MIN_SAMPLES {
+        return None;
+    }
+
+    let mean = values.iter().sum::<f64>() / n as f64;
+    let mut cusum: f64 = 0.0;
+    let mut peak = 0.0;
+    let mut peak_index = 0;
+    for (i, &x) in values.iter().enumerate() {
+        cusum += x - mean;
+        if cusum.abs() > peak {
+            peak = cusum.abs();
+            peak_index = i;
+        }
+    }
+
+    // Successive differences are unaffected by a single level shift (except
+    // the one difference that spans it).
+    let diff_sq: f64 = values.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum();
+    let sigma = (diff_sq / (2.0 * (n - 1) as f64)).sqrt();
+    if sigma == 0.0 {
+        return None;
+    }
+
+    let statistic = peak / (sigma * (n as f64).sqrt());
+    (statistic > CUSUM_THRESHOLD).then_some(peak_index + 1)
+}
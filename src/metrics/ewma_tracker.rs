@@ -0,0 +1,69 @@
+//! Exponentially Weighted Moving Average Tracker
+//!
+//! Tracks a recency-weighted mean of a reward stream in O(1) memory:
+//!
+//! ```text
+//! ewma ← alpha · reward + (1 − alpha) · ewma
+//! ```
+//!
+//! Unlike [`RewardTracker`](super::reward_tracker::RewardTracker), no sample
+//! is ever dropped outright; old rewards fade geometrically instead. Higher
+//! `alpha` reacts faster to changes, lower `alpha` smooths more.
+//!
+//! ## Example
+//! ```
+//! use rustybrain::metrics::ewma_tracker::EwmaTracker;
+//!
+//! let mut t = EwmaTracker::new(0.5);
+//! t.update(4.0);
+//! t.update(2.0);
+//! assert_eq!(t.value(), 3.0);
+//! assert_eq!(t.count(), 2);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EwmaTracker {
+    /// Weight of the newest reward.
+    alpha: f64,
+    /// Current moving average (`0.0` before the first update).
+    ewma: f64,
+    /// Number of updates seen.
+    count: usize,
+}
+
+impl EwmaTracker {
+    /// Creates a tracker with smoothing factor `alpha`.
+    ///
+    /// # Panics
+    /// Panics unless `0 < alpha <= 1`.
+    pub fn new(alpha: f64) -> Self {
+        assert!(alpha > 0.0 && alpha <= 1.0, "alpha must be in (0, 1]");
+        Self {
+            alpha,
+            ewma: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Folds `reward` into the average. The first reward initializes it directly.
+    pub fn update(&mut self, reward: f64) {
+        self.ewma = if self.count == 0 {
+            reward
+        } else {
+            self.alpha * reward + (1.0 - self.alpha) * self.ewma
+        };
+        self.count += 1;
+    }
+
+    /// Returns the current moving average, or `0.0` before any update.
+    pub fn value(&self) -> f64 {
+        self.ewma
+    }
+
+    /// Returns the number of updates seen.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
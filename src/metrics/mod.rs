@@ -0,0 +1,15 @@
+//! Online metrics for monitoring rewards and strategy performance.
+//!
+//! - [`reward_tracker`]: rolling-window statistics over recent rewards.
+//! - [`ewma_tracker`]: exponentially weighted moving average of all rewards.
+//! - [`regret`]: cumulative regret against a known or estimated
+//!   optimum, optionally recorded by wrapping a bandit.
+//! - [`change_point`]: CUSUM detection of a mean shift inside a tracker's window.
+
+mod change_point;
+
+pub mod ewma_tracker;
+pub mod regret;
+pub mod reward_tracker;
+
+pub use change_point::change_point;
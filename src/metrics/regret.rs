@@ -0,0 +1,242 @@
+//! Cumulative Regret Tracker
+//!
+//! Accumulates the regret `optimal_mean − reward` of each received reward
+//! against the known expected reward of the best arm. This is mainly useful
+//! for comparing strategies offline, where the true arm means are known.
+//!
+//! Raw regret scales with the reward units. [`RegretTracker::normalized_regret`]
+//! divides it by `steps × (reward_max − reward_min)`, which makes runs on
+//! differently scaled problems comparable.
+//!
+//! With noisy observed rewards a single step can beat the optimum and yield
+//! negative regret; [`RegretTracker::new_clamped`] floors each step at zero.
+//! Every step's regret is kept (see [`RegretTracker::per_step`]), so memory
+//! grows linearly with the number of recorded steps.
+//!
+//! ## Tracking a Bandit
+//!
+//! [`RegretTracked`] wraps any [`Bandit`] and records a step on every update,
+//! so simulation and benchmark code can read a regret curve
+//! ([`RegretTracker::curve`]) without extra bookkeeping. The optimum comes
+//! from one of:
+//! - [`RegretTracker::with_arm_means`]: the true arm means are known, and each
+//!   step records the pseudo-regret `μ* − μ_arm` of the pulled arm;
+//! - [`RegretTracker::new`]: only the optimal mean is known;
+//! - [`RegretTracker::online`]: nothing is known, and each step is measured
+//!   against the bandit's best estimated value at the time of the update.
+
+use crate::bandit::Bandit;
+
+#[derive(Debug, Clone)]
+pub struct RegretTracker {
+    optimal_mean: f64,
+    /// Width of the reward range; rewards are assumed to lie in `[0, 1]` by default.
+    reward_scale: f64,
+    /// Whether negative per-step regret is floored at zero.
+    clamped: bool,
+    cumulative: f64,
+    per_step: Vec<f64>,
+    /// True mean of every arm, when known.
+    arm_means: Option<Vec<f64>>,
+    /// Whether the optimum follows the tracked bandit's best estimate.
+    online: bool,
+}
+
+impl RegretTracker {
+    /// Creates a tracker for a problem whose best arm has expected reward `optimal_mean`.
+    pub fn new(optimal_mean: f64) -> Self {
+        Self {
+            optimal_mean,
+            reward_scale: 1.0,
+            clamped: false,
+            cumulative: 0.0,
+            per_step: Vec::new(),
+            arm_means: None,
+            online: false,
+        }
+    }
+
+    /// Creates a tracker for a problem with known arm means; the optimum is
+    /// the largest of them.
+    ///
+    /// # Panics
+    /// Panics if `arm_means` is empty.
+    pub fn with_arm_means(arm_means: Vec<f64>) -> Self {
+        let optimal_mean = arm_means
+            .iter()
+            .copied()
+            .reduce(f64::max)
+            .expect("must have at least one arm");
+        Self {
+            arm_means: Some(arm_means),
+            ..Self::new(optimal_mean)
+        }
+    }
+
+    /// Creates a tracker without a known optimum. Inside [`RegretTracked`] it
+    /// measures each reward against the bandit's best estimated value;
+    /// standalone, set the estimate with [`set_optimal_mean`](Self::set_optimal_mean).
+    pub fn online() -> Self {
+        Self {
+            online: true,
+            ..Self::new(0.0)
+        }
+    }
+
+    /// Returns the expected reward of the best arm (the current estimate for
+    /// an online tracker).
+    pub fn optimal_mean(&self) -> f64 {
+        self.optimal_mean
+    }
+
+    /// Replaces the optimum that later steps are measured against.
+    pub fn set_optimal_mean(&mut self, optimal_mean: f64) {
+        self.optimal_mean = optimal_mean;
+    }
+
+    /// Records a step in which `arm` was pulled and paid `received_reward`.
+    ///
+    /// With known arm means this records the pseudo-regret of `arm`, which
+    /// ignores reward noise; otherwise it behaves like [`record`](Self::record).
+    ///
+    /// # Panics
+    /// Panics if arm means are known and `arm` is out of range.
+    pub fn record_arm(&mut self, arm: usize, received_reward: f64) {
+        let reward = match &self.arm_means {
+            Some(means) => means[arm],
+            None => received_reward,
+        };
+        self.record(reward);
+    }
+
+    /// Like [`new`](Self::new), but records `max(0, optimal_mean − reward)`
+    /// per step so noise above the optimum does not cancel real regret.
+    pub fn new_clamped(optimal_mean: f64) -> Self {
+        Self {
+            clamped: true,
+            ..Self::new(optimal_mean)
+        }
+    }
+
+    /// Declares that rewards lie in `[reward_min, reward_max]`, used by
+    /// [`normalized_regret`](Self::normalized_regret).
+    ///
+    /// # Panics
+    /// Panics if `reward_min >= reward_max`.
+    pub fn with_reward_range(mut self, reward_min: f64, reward_max: f64) -> Self {
+        assert!(reward_min < reward_max, "reward_min must be < reward_max");
+        self.reward_scale = reward_max - reward_min;
+        self
+    }
+
+    /// Records one step's received reward.
+    pub fn record(&mut self, received_reward: f64) {
+        let mut regret = self.optimal_mean - received_reward;
+        if self.clamped {
+            regret = regret.max(0.0);
+        }
+        self.cumulative += regret;
+        self.per_step.push(regret);
+    }
+
+    /// Returns the total regret accumulated so far.
+    pub fn cumulative(&self) -> f64 {
+        self.cumulative
+    }
+
+    /// Returns the number of recorded steps.
+    pub fn steps(&self) -> u64 {
+        self.per_step.len() as u64
+    }
+
+    /// Returns the mean regret per step (`0.0` before any step is recorded).
+    pub fn average(&self) -> f64 {
+        if self.per_step.is_empty() {
+            return 0.0;
+        }
+        self.cumulative / self.per_step.len() as f64
+    }
+
+    /// Returns the regret of every recorded step, oldest first.
+    pub fn per_step(&self) -> &[f64] {
+        &self.per_step
+    }
+
+    /// Returns the cumulative regret after every recorded step, oldest first.
+    pub fn curve(&self) -> Vec<f64> {
+        self.per_step
+            .iter()
+            .scan(0.0, |total, &regret| {
+                *total += regret;
+                Some(*total)
+            })
+            .collect()
+    }
+
+    /// Returns cumulative regret divided by `steps × reward range`.
+    ///
+    /// For pseudo-regret this lies in `[0, 1]` regardless of the reward scale.
+    /// Returns `0.0` before any step is recorded.
+    pub fn normalized_regret(&self) -> f64 {
+        self.average() / self.reward_scale
+    }
+}
+
+/// A bandit that feeds every update into a [`RegretTracker`].
+///
+/// Implements [`Bandit`] itself, so it can stand in for the wrapped bandit,
+/// e.g. in [`sim`](crate::bandit::sim) runs.
+#[derive(Debug, Clone)]
+pub struct RegretTracked<B> {
+    bandit: B,
+    tracker: RegretTracker,
+}
+
+impl<B: Bandit> RegretTracked<B> {
+    /// Wraps `bandit`, recording its updates in `tracker`.
+    pub fn new(bandit: B, tracker: RegretTracker) -> Self {
+        Self { bandit, tracker }
+    }
+
+    /// Returns the wrapped bandit.
+    pub fn bandit(&self) -> &B {
+        &self.bandit
+    }
+
+    /// Returns the regret recorded so far.
+    pub fn tracker(&self) -> &RegretTracker {
+        &self.tracker
+    }
+
+    /// Unwraps into the bandit and its tracker.
+    pub fn into_parts(self) -> (B, RegretTracker) {
+        (self.bandit, self.tracker)
+    }
+}
+
+impl<B: Bandit> Bandit for RegretTracked<B> {
+    fn select_arm(&mut self) -> usize {
+        self.bandit.select_arm()
+    }
+
+    fn select_arm_among(&mut self, candidates: &[usize]) -> usize {
+        self.bandit.select_arm_among(candidates)
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        if self.tracker.online {
+            // Measure against the estimate the bandit acted on.
+            self.tracker.set_optimal_mean(self.bandit.best_value());
+        }
+        self.tracker.record_arm(arm, reward);
+        self.bandit.update(arm, reward);
+    }
+
+    fn counts(&self) -> &[u64] {
+        self.bandit.counts()
+    }
+
+    fn values(&self) -> &[f64] {
+        self.bandit.values()
+    }
+}
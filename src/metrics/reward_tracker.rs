@@ -4,11 +4,48 @@
 //! exposing mean, min, max, and count. This is useful for
 //! tracking recent performance trends or stabilizing feedback
 //! in adaptive systems.
+//!
+//! The tracker also remembers when the windowed mean first reached new
+//! highs, so [`RewardTracker::steps_to_reach`] can report how many updates
+//! it took to hit a convergence target. One entry is kept per new high, so on
+//! a steadily rising reward stream this history grows by one entry per update
+//! (O(updates) memory) rather than being bounded by the window size.
+//!
+//! A tracker built with [`RewardTracker::hybrid`] additionally weights the
+//! samples inside its window exponentially by recency. This gives EWMA-like
+//! responsiveness while evicted samples have no influence at all.
+//!
+//! Every stored sample carries a logical sequence number. [`RewardTracker::update`]
+//! numbers samples by the tracker's own update count; [`RewardTracker::update_at`]
+//! takes an explicit number (e.g. a global event id), so the windows of several
+//! per-shard trackers can be recombined in global order with
+//! [`RewardTracker::merge_ordered`].
+//!
+//! An empty tracker reports `0.0` for its mean, min and max. NaN rewards are
+//! skipped by [`RewardTracker::update`] and [`RewardTracker::update_at`]: they
+//! are not stored and do not count as updates, so a single NaN can never
+//! poison the window's statistics.
+
+use std::collections::VecDeque;
 
-#[derive(Debug, Clone)]
+use serde::{Deserialize, Serialize};
+
+use crate::rolling_window::RollingWindow;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RewardTracker {
-    window: usize,
-    values: Vec<f64>,
+    /// Most recent rewards, oldest first; eviction is O(1) amortized.
+    values: RollingWindow,
+    /// Sequence number of each stored reward, parallel to `values`.
+    #[serde(default)]
+    seqs: VecDeque<u64>,
+    /// Total number of updates seen, including evicted values.
+    total_updates: u64,
+    /// `(total_updates, mean)` each time the windowed mean set a new high.
+    /// Unbounded: grows by one entry per new high (see the module docs).
+    mean_highs: Vec<(u64, f64)>,
+    /// Recency decay for [`RewardTracker::hybrid_mean`]; `None` weights all samples equally.
+    alpha: Option<f64>,
 }
 
 impl RewardTracker {
@@ -16,17 +53,52 @@ impl RewardTracker {
     pub fn new(window: usize) -> Self {
         assert!(window > 0, "window size must be > 0");
         Self {
-            window,
-            values: Vec::with_capacity(window),
+            values: RollingWindow::new(window),
+            seqs: VecDeque::with_capacity(window),
+            total_updates: 0,
+            mean_highs: Vec::new(),
+            alpha: None,
+        }
+    }
+
+    /// Creates a tracker whose [`hybrid_mean`](Self::hybrid_mean) weights the
+    /// windowed samples by `(1 - alpha)^age`, newest first.
+    ///
+    /// # Panics
+    /// - If `window == 0`
+    /// - If `alpha` is outside `(0.0, 1.0]`
+    pub fn hybrid(window: usize, alpha: f64) -> Self {
+        assert!(alpha > 0.0 && alpha <= 1.0, "alpha must be in (0.0, 1.0]");
+        Self {
+            alpha: Some(alpha),
+            ..Self::new(window)
         }
     }
 
     /// Adds a new reward to the tracker, evicting the oldest if full.
+    ///
+    /// The reward's sequence number is the number of updates seen before it.
     pub fn update(&mut self, reward: f64) {
-        if self.values.len() == self.window {
-            self.values.remove(0);
+        self.update_at(reward, self.total_updates);
+    }
+
+    /// Adds a new reward tagged with the logical sequence number `seq`.
+    ///
+    /// NaN rewards are ignored (see the module docs).
+    pub fn update_at(&mut self, reward: f64, seq: u64) {
+        if reward.is_nan() {
+            return;
+        }
+        if self.values.push(reward).is_some() {
+            self.seqs.pop_front();
+        }
+        self.seqs.push_back(seq);
+        self.total_updates += 1;
+
+        let mean = self.mean();
+        if self.mean_highs.last().is_none_or(|&(_, high)| mean > high) {
+            self.mean_highs.push((self.total_updates, mean));
         }
-        self.values.push(reward);
     }
 
     /// Returns the mean of stored rewards.
@@ -37,26 +109,164 @@ impl RewardTracker {
         self.values.iter().sum::<f64>() / self.values.len() as f64
     }
 
-    /// Returns the minimum reward seen in the current window.
+    /// Returns the recency-weighted mean of the window.
+    ///
+    /// Equals [`mean`](Self::mean) for trackers not built with [`hybrid`](Self::hybrid).
+    pub fn hybrid_mean(&self) -> f64 {
+        let Some(alpha) = self.alpha else {
+            return self.mean();
+        };
+        if self.values.is_empty() {
+            return 0.0;
+        }
+
+        let decay = 1.0 - alpha;
+        let mut weight = 1.0;
+        let mut weighted_sum = 0.0;
+        let mut total_weight = 0.0;
+        for &x in self.values.iter().rev() {
+            weighted_sum += weight * x;
+            total_weight += weight;
+            weight *= decay;
+        }
+        weighted_sum / total_weight
+    }
+
+    /// Returns the z-score of the most recent reward against the values
+    /// stored before it in the window.
+    ///
+    /// Uses the sample standard deviation of the earlier values. Returns
+    /// `0.0` when fewer than two earlier values exist or they have zero spread.
+    pub fn latest_zscore(&self) -> f64 {
+        let Some((&latest, history)) = self.values.split_last() else {
+            return 0.0;
+        };
+        if history.len() < 2 {
+            return 0.0;
+        }
+        let n = history.len() as f64;
+        let mean = history.iter().sum::<f64>() / n;
+        let var = history.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        if var == 0.0 {
+            return 0.0;
+        }
+        (latest - mean) / var.sqrt()
+    }
+
+    /// Returns the lag-`lag` autocorrelation of the window: the Pearson
+    /// correlation between the series and itself shifted by `lag` updates.
+    ///
+    /// Returns `0.0` when the window holds fewer than `lag + 2` values or
+    /// either overlapping segment has zero spread.
+    pub fn autocorrelation(&self, lag: usize) -> f64 {
+        let n = self.values.len();
+        if n < lag + 2 {
+            return 0.0;
+        }
+        let head = &self.values[..n - lag];
+        let tail = &self.values[lag..];
+        let m = head.len() as f64;
+        let mean_h = head.iter().sum::<f64>() / m;
+        let mean_t = tail.iter().sum::<f64>() / m;
+
+        let mut cov = 0.0;
+        let mut var_h = 0.0;
+        let mut var_t = 0.0;
+        for (&a, &b) in head.iter().zip(tail) {
+            cov += (a - mean_h) * (b - mean_t);
+            var_h += (a - mean_h).powi(2);
+            var_t += (b - mean_t).powi(2);
+        }
+        if var_h == 0.0 || var_t == 0.0 {
+            return 0.0;
+        }
+        cov / (var_h * var_t).sqrt()
+    }
+
+    /// Recommends the fraction of interactions to record so the mean of a
+    /// window's worth of recorded rewards has standard error `target_se`.
+    ///
+    /// With sample variance `σ²`, a standard error of `target_se` needs
+    /// `σ² / target_se²` samples; the result is that count divided by the
+    /// number of rewards currently in the window, clamped to `[0.0, 1.0]`.
+    /// Returns `1.0` (record everything) with fewer than two rewards or a
+    /// non-positive target.
+    pub fn recommended_sample_rate(&self, target_se: f64) -> f64 {
+        let n = self.values.len();
+        if n < 2 || target_se <= 0.0 {
+            return 1.0;
+        }
+        let mean = self.mean();
+        let var = self.values.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+        let needed = var / (target_se * target_se);
+        (needed / n as f64).clamp(0.0, 1.0)
+    }
+
+    /// Returns the minimum reward in the current window (`0.0` when empty).
     pub fn min(&self) -> f64 {
-        self.values
-            .iter()
-            .cloned()
-            .fold(f64::INFINITY, f64::min)
-            .is_finite()
-            .then(|| self.values.iter().cloned().fold(f64::INFINITY, f64::min))
-            .unwrap_or(0.0)
+        self.values.iter().copied().reduce(f64::min).unwrap_or(0.0)
     }
 
-    /// Returns the maximum reward seen in the current window.
+    /// Returns the maximum reward in the current window (`0.0` when empty).
     pub fn max(&self) -> f64 {
-        self.values
-            .iter()
-            .cloned()
-            .fold(f64::NEG_INFINITY, f64::max)
-            .is_finite()
-            .then(|| self.values.iter().cloned().fold(f64::NEG_INFINITY, f64::max))
-            .unwrap_or(0.0)
+        self.values.iter().copied().reduce(f64::max).unwrap_or(0.0)
+    }
+
+    /// Returns the `q`-quantile of the current window (`0.0` when empty).
+    ///
+    /// Uses linear interpolation between the closest ranks (type 7, as in
+    /// NumPy's default): position `q · (n − 1)` in the sorted window. So
+    /// `percentile(0.0)` is [`min`](Self::min) and `percentile(1.0)` is
+    /// [`max`](Self::max). This sorts a copy of the window, so it is O(N log N).
+    ///
+    /// # Panics
+    /// Panics if `q` is outside `[0.0, 1.0]`.
+    ///
+    /// # Example
+    /// ```
+    /// let mut rt = rustybrain::metrics::reward_tracker::RewardTracker::new(10);
+    /// for r in 1..=10 {
+    ///     rt.update(r as f64);
+    /// }
+    /// assert!((rt.percentile(0.9) - 9.1).abs() < 1e-12);
+    /// ```
+    pub fn percentile(&self, q: f64) -> f64 {
+        assert!((0.0..=1.0).contains(&q), "q must be in [0.0, 1.0]");
+        if self.values.is_empty() {
+            return 0.0;
+        }
+        let mut sorted = self.values.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let pos = q * (sorted.len() - 1) as f64;
+        let lo = pos.floor() as usize;
+        let hi = pos.ceil() as usize;
+        sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+    }
+
+    /// Returns the median of the current window (`0.0` when empty).
+    pub fn median(&self) -> f64 {
+        self.percentile(0.5)
+    }
+
+    /// Returns the maximum number of rewards the window holds.
+    pub fn window(&self) -> usize {
+        self.values.window()
+    }
+
+    /// Returns the number of rewards the backing buffer holds without reallocating.
+    ///
+    /// The buffer is allocated in full at construction (twice the window),
+    /// so this stays constant across updates unless [`reserve`](Self::reserve) is called.
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Pre-allocates `additional` extra buffer slots.
+    ///
+    /// Updates never allocate. Evicted values are reclaimed by an occasional
+    /// O(window) compaction; extra slots make compactions proportionally rarer.
+    pub fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional);
     }
 
     /// Returns the number of rewards currently stored.
@@ -64,6 +274,76 @@ impl RewardTracker {
         self.values.len()
     }
 
+    /// Returns the total number of updates seen, including evicted values.
+    pub fn total_updates(&self) -> u64 {
+        self.total_updates
+    }
+
+    /// Returns the update count at which the windowed mean first met or
+    /// exceeded `target`, or `None` if it never has.
+    pub fn steps_to_reach(&self, target: f64) -> Option<u64> {
+        self.mean_highs
+            .iter()
+            .find(|&&(_, mean)| mean >= target)
+            .map(|&(step, _)| step)
+    }
+
+    /// Returns at most `target_points` values summarizing the window for plotting.
+    ///
+    /// The window is split into `target_points` contiguous buckets of nearly
+    /// equal size and each bucket is replaced by its average, preserving the
+    /// overall shape. Returns the raw values when the window is already small
+    /// enough, and an empty vector when `target_points == 0`.
+    pub fn downsample(&self, target_points: usize) -> Vec<f64> {
+        let n = self.values.len();
+        if n <= target_points {
+            return self.values.to_vec();
+        }
+        (0..target_points)
+            .map(|b| {
+                let start = b * n / target_points;
+                let end = (b + 1) * n / target_points;
+                let bucket = &self.values[start..end];
+                bucket.iter().sum::<f64>() / bucket.len() as f64
+            })
+            .collect()
+    }
+
+    /// Returns the windowed samples of all `trackers` combined and sorted by
+    /// sequence number.
+    ///
+    /// Samples with equal sequence numbers keep the order of `trackers`.
+    ///
+    /// # Example
+    /// ```
+    /// use rustybrain::metrics::reward_tracker::RewardTracker;
+    ///
+    /// let mut a = RewardTracker::new(10);
+    /// let mut b = RewardTracker::new(10);
+    /// a.update_at(1.0, 0);
+    /// b.update_at(2.0, 1);
+    /// a.update_at(3.0, 2);
+    /// assert_eq!(RewardTracker::merge_ordered(&[&a, &b]), vec![1.0, 2.0, 3.0]);
+    /// ```
+    pub fn merge_ordered(trackers: &[&RewardTracker]) -> Vec<f64> {
+        let mut samples: Vec<(u64, f64)> = trackers
+            .iter()
+            .flat_map(|t| t.seqs.iter().copied().zip(t.values.iter().copied()))
+            .collect();
+        samples.sort_by_key(|&(seq, _)| seq);
+        samples.into_iter().map(|(_, reward)| reward).collect()
+    }
+
+    /// Serializes the tracker, including its window contents, to JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("RewardTracker state is always serializable")
+    }
+
+    /// Restores a tracker previously serialized with [`to_json`](Self::to_json).
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
     /// Returns all stored rewards (for debugging/inspection).
     pub fn values(&self) -> &[f64] {
         &self.values
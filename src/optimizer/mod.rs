@@ -3,6 +3,23 @@
 //! This module provides a deterministic, 1-D hill-climbing optimizer that
 //! improves a single parameter `x` based on observed rewards. It’s designed
 //! for tight unit tests and future expansion (e.g., multi-D, annealing).
+//!
+//! [`HillClimberND`] applies the same rules to a parameter vector through
+//! the [`OptimizerND`] trait, perturbing one coordinate per suggestion.
+//!
+//! [`SimulatedAnnealing1D`] is a seeded, stochastic alternative for
+//! multimodal reward surfaces where hill climbing stalls on a local peak.
+//!
+//! [`OuterLoop`] uses a [`HillClimber1D`] to tune a parameter that affects
+//! the rewards of a bandit experiment.
+
+mod outer_loop;
+
+pub use outer_loop::OuterLoop;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use rand_distr::{Distribution, Normal};
 
 /// A minimal interface for iterative optimization of a single parameter.
 pub trait Optimizer {
@@ -26,6 +43,9 @@ pub trait Optimizer {
 /// - If reward improves, move further in same direction and grow step slightly.
 /// - If reward worsens, reverse direction and shrink step.
 /// - Stops shrinking below `min_step` but remains deterministic.
+/// - With bounds ([`HillClimber1D::with_bounds`]), proposals are clamped into
+///   range; if a clamped proposal would not move `x` (it is already at the
+///   wall), the direction reverses instead of re-evaluating the same point.
 #[derive(Debug, Clone)]
 pub struct HillClimber1D {
     x: f64,
@@ -34,6 +54,8 @@ pub struct HillClimber1D {
     min_step: f64,
     grow: f64,
     shrink: f64,
+    /// Inclusive `(lower, upper)` range for suggestions.
+    bounds: Option<(f64, f64)>,
     last_reward: Option<f64>,
     last_suggested: Option<f64>,
 }
@@ -58,10 +80,41 @@ impl HillClimber1D {
             min_step,
             grow,
             shrink,
+            bounds: None,
             last_reward: None,
             last_suggested: None,
         }
     }
+
+    /// Like [`with_params`](Self::with_params), but every suggestion stays
+    /// within `[lower, upper]`.
+    ///
+    /// # Panics
+    /// Panics if `lower >= upper` or `x0` lies outside the bounds.
+    pub fn with_bounds(
+        x0: f64,
+        step: f64,
+        min_step: f64,
+        grow: f64,
+        shrink: f64,
+        lower: f64,
+        upper: f64,
+    ) -> Self {
+        assert!(lower < upper, "lower must be < upper");
+        assert!((lower..=upper).contains(&x0), "x0 must lie within bounds");
+        Self {
+            bounds: Some((lower, upper)),
+            ..Self::with_params(x0, step, min_step, grow, shrink)
+        }
+    }
+
+    /// Clamps `x` into the bounds, if any.
+    fn clamp(&self, x: f64) -> f64 {
+        match self.bounds {
+            Some((lower, upper)) => x.clamp(lower, upper),
+            None => x,
+        }
+    }
 }
 
 impl Optimizer for HillClimber1D {
@@ -69,7 +122,15 @@ impl Optimizer for HillClimber1D {
         // First suggestion is the current x.
         let s = match self.last_suggested {
             None => self.x,
-            Some(_) => self.x + self.dir * self.step,
+            Some(_) => {
+                let mut s = self.clamp(self.x + self.dir * self.step);
+                if s == self.x {
+                    // Pinned against a bound: head back into the range instead.
+                    self.dir = -self.dir;
+                    s = self.clamp(self.x + self.dir * self.step);
+                }
+                s
+            }
         };
         self.last_suggested = Some(s);
         s
@@ -105,10 +166,233 @@ impl Optimizer for HillClimber1D {
     }
 
     fn reset(&mut self, x0: f64) {
+        if let Some((lower, upper)) = self.bounds {
+            assert!((lower..=upper).contains(&x0), "x0 must lie within bounds");
+        }
         self.x = x0;
         self.dir = 1.0;
         self.step = self.step.max(self.min_step);
         self.last_reward = None;
         self.last_suggested = None;
     }
-}
\ No newline at end of file
+}
+
+/// Seeded 1-D simulated annealing.
+///
+/// Strategy:
+/// - The first suggestion is `x0`; later ones jitter the current `x` by
+///   `N(0, temp)`.
+/// - A better (or equal) reward always moves `x` to the suggestion; a worse
+///   one moves it with probability `exp((reward − last_reward) / temp)`.
+/// - After every observation the temperature is multiplied by `cooling`.
+/// - [`Optimizer::param`] returns the best suggestion observed so far.
+#[derive(Debug, Clone)]
+pub struct SimulatedAnnealing1D {
+    x: f64,
+    temp: f64,
+    temp0: f64,
+    cooling: f64,
+    seed: u64,
+    rng: ChaCha12Rng,
+    last_reward: Option<f64>,
+    last_suggested: Option<f64>,
+    best: Option<(f64, f64)>,
+}
+
+impl SimulatedAnnealing1D {
+    /// Create an annealer starting at `x0` with initial temperature `temp0`.
+    /// - `cooling`: factor applied to the temperature after each observation (0..1]
+    /// - `seed`: RNG seed; equal seeds give identical runs
+    pub fn new(x0: f64, temp0: f64, cooling: f64, seed: u64) -> Self {
+        assert!(temp0 > 0.0 && temp0.is_finite(), "temp0 must be positive");
+        assert!(cooling > 0.0 && cooling <= 1.0, "cooling must be in (0, 1]");
+        Self {
+            x: x0,
+            temp: temp0,
+            temp0,
+            cooling,
+            seed,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            last_reward: None,
+            last_suggested: None,
+            best: None,
+        }
+    }
+
+    /// Current temperature.
+    pub fn temperature(&self) -> f64 {
+        self.temp
+    }
+}
+
+impl Optimizer for SimulatedAnnealing1D {
+    fn suggest(&mut self) -> f64 {
+        let s = match self.last_reward {
+            None => self.x,
+            Some(_) => {
+                let jitter = Normal::new(0.0, self.temp).expect("temperature is finite");
+                self.x + jitter.sample(&mut self.rng)
+            }
+        };
+        self.last_suggested = Some(s);
+        s
+    }
+
+    fn observe(&mut self, reward: f64) {
+        let Some(s) = self.last_suggested.take() else {
+            return; // Should not happen; safe no-op.
+        };
+        let accept = match self.last_reward {
+            None => true,
+            Some(prev_r) if reward >= prev_r => true,
+            Some(prev_r) => {
+                let p: f64 = ((reward - prev_r) / self.temp).exp();
+                self.rng.gen::<f64>() < p
+            }
+        };
+        if accept {
+            self.x = s;
+            self.last_reward = Some(reward);
+        }
+        if self.best.is_none_or(|(_, best_r)| reward > best_r) {
+            self.best = Some((s, reward));
+        }
+        self.temp *= self.cooling;
+    }
+
+    fn param(&self) -> f64 {
+        self.best.map_or(self.x, |(x, _)| x)
+    }
+
+    fn reset(&mut self, x0: f64) {
+        self.x = x0;
+        self.temp = self.temp0;
+        self.rng = ChaCha12Rng::seed_from_u64(self.seed);
+        self.last_reward = None;
+        self.last_suggested = None;
+        self.best = None;
+    }
+}
+
+/// A minimal interface for iterative optimization of a parameter vector.
+pub trait OptimizerND {
+    /// Propose the next parameter vector to evaluate.
+    fn suggest(&mut self) -> Vec<f64>;
+
+    /// Report the reward obtained from evaluating the most recent suggestion.
+    fn observe(&mut self, reward: f64);
+
+    /// Current best-known parameters.
+    fn params(&self) -> &[f64];
+
+    /// Reset the optimizer to a fresh starting vector.
+    fn reset(&mut self, x0: Vec<f64>);
+}
+
+/// Deterministic N-D hill climber using coordinate-wise step adaptation.
+///
+/// Strategy:
+/// - Each suggestion after the first perturbs a single coordinate, cycling
+///   through the coordinates in order.
+/// - Each coordinate keeps its own direction and step size, updated with the
+///   same rules as [`HillClimber1D`]: grow on improvement, reverse and shrink
+///   (down to `min_step`) on failure.
+#[derive(Debug, Clone)]
+pub struct HillClimberND {
+    x: Vec<f64>,
+    dir: Vec<f64>,
+    step: Vec<f64>,
+    initial_step: f64,
+    min_step: f64,
+    grow: f64,
+    shrink: f64,
+    /// Coordinate perturbed by the next suggestion.
+    coord: usize,
+    last_reward: Option<f64>,
+    last_suggested: Option<Vec<f64>>,
+}
+
+impl HillClimberND {
+    pub fn new(x0: Vec<f64>) -> Self {
+        Self::with_params(x0, 0.5, 0.1, 1.1, 0.5)
+    }
+
+    /// Create with full control over parameters, shared by all coordinates.
+    /// - `step`: initial step size
+    /// - `min_step`: minimum step size threshold
+    /// - `grow`: factor to grow step on improvement (>1.0)
+    /// - `shrink`: factor to shrink step on failure (0..1)
+    pub fn with_params(x0: Vec<f64>, step: f64, min_step: f64, grow: f64, shrink: f64) -> Self {
+        assert!(!x0.is_empty(), "must have at least one dimension");
+        assert!(step > 0.0 && min_step > 0.0);
+        assert!(grow > 1.0 && (0.0..1.0).contains(&shrink));
+        let dims = x0.len();
+        Self {
+            x: x0,
+            dir: vec![1.0; dims],
+            step: vec![step; dims],
+            initial_step: step,
+            min_step,
+            grow,
+            shrink,
+            coord: 0,
+            last_reward: None,
+            last_suggested: None,
+        }
+    }
+}
+
+impl OptimizerND for HillClimberND {
+    fn suggest(&mut self) -> Vec<f64> {
+        // First suggestion is the current x.
+        let mut s = self.x.clone();
+        if self.last_suggested.is_some() {
+            let c = self.coord;
+            s[c] += self.dir[c] * self.step[c];
+        }
+        self.last_suggested = Some(s.clone());
+        s
+    }
+
+    fn observe(&mut self, reward: f64) {
+        let Some(s) = self.last_suggested.clone() else {
+            return; // Should not happen; safe no-op.
+        };
+        match self.last_reward {
+            None => {
+                // First observation initializes state; adopt s as current.
+                self.x = s;
+                self.last_reward = Some(reward);
+            }
+            Some(prev_r) => {
+                let c = self.coord;
+                if reward >= prev_r {
+                    // Improvement: keep this coordinate's direction; grow its step
+                    self.x = s;
+                    self.step[c] *= self.grow;
+                    self.last_reward = Some(reward);
+                } else {
+                    // No improvement: reverse this coordinate; shrink its step
+                    self.dir[c] = -self.dir[c];
+                    self.step[c] = (self.step[c] * self.shrink).max(self.min_step);
+                }
+                self.coord = (c + 1) % self.x.len();
+            }
+        }
+    }
+
+    fn params(&self) -> &[f64] {
+        &self.x
+    }
+
+    fn reset(&mut self, x0: Vec<f64>) {
+        assert!(!x0.is_empty(), "must have at least one dimension");
+        let dims = x0.len();
+        self.x = x0;
+        self.dir = vec![1.0; dims];
+        self.step = vec![self.initial_step; dims];
+        self.coord = 0;
+        self.last_reward = None;
+        self.last_suggested = None;
+    }
+}
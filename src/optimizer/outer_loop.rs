@@ -0,0 +1,94 @@
+//! Outer-loop tuning of a continuous parameter around bandit experiments.
+//!
+//! Some parameters (thresholds, feature cut-offs, ...) change which arm is
+//! best and how good it is. [`OuterLoop`] tunes such a parameter with a
+//! [`HillClimber1D`]: for every suggested value it runs a short experiment
+//! with a fresh bandit and reports the experiment's average reward back to
+//! the climber as that value's reward.
+
+use super::{HillClimber1D, Optimizer};
+use crate::bandit::Bandit;
+
+/// Hill-climbs a parameter using average bandit reward as the objective.
+#[derive(Debug, Clone)]
+pub struct OuterLoop {
+    climber: HillClimber1D,
+    /// Bandit pulls per experiment.
+    steps_per_trial: usize,
+    /// Best `(parameter, average reward)` seen so far.
+    best: Option<(f64, f64)>,
+}
+
+impl OuterLoop {
+    /// Runs experiments of `steps_per_trial` pulls at values suggested by `climber`.
+    ///
+    /// # Panics
+    /// Panics if `steps_per_trial == 0`.
+    pub fn new(climber: HillClimber1D, steps_per_trial: usize) -> Self {
+        assert!(steps_per_trial > 0, "steps_per_trial must be > 0");
+        Self {
+            climber,
+            steps_per_trial,
+            best: None,
+        }
+    }
+
+    /// Runs one experiment at the next suggested parameter value and returns
+    /// `(parameter, average reward)`.
+    ///
+    /// `make_bandit` builds a fresh bandit for the experiment; `reward(param, arm)`
+    /// returns the reward of pulling `arm` while the parameter is `param`.
+    pub fn trial<B: Bandit>(
+        &mut self,
+        make_bandit: impl FnOnce() -> B,
+        mut reward: impl FnMut(f64, usize) -> f64,
+    ) -> (f64, f64) {
+        let param = self.climber.suggest();
+        let mut bandit = make_bandit();
+        let mut total = 0.0;
+        for _ in 0..self.steps_per_trial {
+            let arm = bandit.select_arm();
+            let r = reward(param, arm);
+            bandit.update(arm, r);
+            total += r;
+        }
+        let average = total / self.steps_per_trial as f64;
+        self.climber.observe(average);
+
+        if self.best.is_none_or(|(_, best)| average > best) {
+            self.best = Some((param, average));
+        }
+        (param, average)
+    }
+
+    /// Runs `trials` experiments and returns the best parameter found.
+    ///
+    /// # Panics
+    /// Panics if `trials == 0` and no experiment has run yet.
+    pub fn run<B: Bandit>(
+        &mut self,
+        trials: usize,
+        mut make_bandit: impl FnMut() -> B,
+        mut reward: impl FnMut(f64, usize) -> f64,
+    ) -> f64 {
+        for _ in 0..trials {
+            self.trial(&mut make_bandit, &mut reward);
+        }
+        self.best_param().expect("at least one trial must run")
+    }
+
+    /// Returns the parameter with the highest average reward so far.
+    pub fn best_param(&self) -> Option<f64> {
+        self.best.map(|(param, _)| param)
+    }
+
+    /// Returns the highest average reward observed so far.
+    pub fn best_reward(&self) -> Option<f64> {
+        self.best.map(|(_, reward)| reward)
+    }
+
+    /// Returns the underlying hill climber.
+    pub fn climber(&self) -> &HillClimber1D {
+        &self.climber
+    }
+}
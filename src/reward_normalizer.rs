@@ -33,6 +33,51 @@
 //! - **Zero variance:** returns `0.5` to avoid division-by-zero.  
 //! - **Window overflow:** oldest element is removed (FIFO behavior).  
 //!
+//! ## Confidence
+//!
+//! [`confidence`] reports how much the current normalization can be trusted,
+//! as a value in `[0.0, 1.0]`. It grows with the fraction of the window that
+//! is filled and collapses toward `0.0` as the spread of stored rewards
+//! approaches zero (where the z-score is meaningless). Callers can scale
+//! their use of a normalized reward by this factor.
+//!
+//! ## Asymmetric Saturation
+//!
+//! Rewards bounded on one side (e.g. latency, which cannot go below zero but
+//! has long upper tails) are poorly served by a symmetric sigmoid.
+//! [`with_asymmetric`] scales positive and negative z-scores by separate
+//! steepness factors before the sigmoid, so for example large positive
+//! outliers can saturate gently while negative deviations stay sharp.
+//!
+//! [`with_asymmetric`]: RewardNormalizer::with_asymmetric
+//!
+//! ## Min-Max Mode
+//!
+//! [`with_mode`] with [`NormalizeMode::MinMax`] replaces the sigmoid with a
+//! linear rescale between the window's observed minimum and maximum,
+//! `(r − min) / (max − min)`, clamped to `[0, 1]`. An empty or constant
+//! window maps every reward to `0.5`.
+//!
+//! [`with_mode`]: RewardNormalizer::with_mode
+//!
+//! ## Inverse
+//!
+//! [`denormalize`] maps a normalized value back to the raw reward scale of
+//! the current window, which is handy for logging.
+//!
+//! [`denormalize`]: RewardNormalizer::denormalize
+//!
+//! ## Distribution Calibration
+//!
+//! [`calibrate_uniform`] switches to a quantile mapping: a reward is mapped
+//! through the window's empirical CDF, so outputs are approximately uniform
+//! on `[0, 1]` regardless of the input shape. [`calibrate_with`] additionally
+//! passes that quantile through an inverse-CDF callback to target any other
+//! output distribution. Calibration takes precedence over the [`NormalizeMode`].
+//!
+//! [`calibrate_uniform`]: RewardNormalizer::calibrate_uniform
+//! [`calibrate_with`]: RewardNormalizer::calibrate_with
+//!
 //! ## Example
 //! ```
 //! use rustybrain::reward_normalizer::RewardNormalizer;
@@ -47,20 +92,68 @@
 //! ```
 //!
 //! ## Complexity
-//! - **Time:** O(N) per normalization (due to mean/std computation).  
-//! - **Space:** O(N) for the rolling buffer.  
+//! - **Time:** O(1) per sigmoid normalization and O(1) amortized per update.
+//!   The quantile calibration and min-max mode are O(N) per normalization.  
+//! - **Space:** O(N) for the rolling buffer, allocated once in [`new`].
+//!   Updates never reallocate; see [`capacity`] and [`reserve`].  
+//!
+//! [`new`]: RewardNormalizer::new
+//! [`capacity`]: RewardNormalizer::capacity
+//! [`reserve`]: RewardNormalizer::reserve
 //!
-//! For larger windows, consider a streaming mean/std algorithm (Welford’s).
+//! The mean and variance are maintained incrementally (Welford's algorithm,
+//! extended to remove evicted samples). To bound floating-point drift over
+//! long streams they are recomputed exactly once every `window` updates,
+//! which keeps updates O(1) amortized. In debug builds each recompute also
+//! asserts that the streaming statistics have not drifted from the exact ones;
+//! [`validate`] performs the same check on demand.
+//!
+//! [`validate`]: RewardNormalizer::validate
+
+use crate::rolling_window::RollingWindow;
+
+/// Standard deviation, relative to `1 + |mean|`, below which the window is
+/// considered to carry little information for normalization.
+const SPREAD_TOLERANCE: f64 = 1e-3;
+
+/// How far [`RewardNormalizer::denormalize`] keeps its input from 0 and 1,
+/// where the logit is infinite.
+const DENORMALIZE_EPSILON: f64 = 1e-12;
+
+/// Maximum relative disagreement tolerated by [`RewardNormalizer::validate`].
+const CONSISTENCY_TOLERANCE: f64 = 1e-6;
+
+/// How [`RewardNormalizer::normalized`] maps a reward into `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NormalizeMode {
+    /// Sigmoid of the z-score against the window's mean and standard deviation.
+    #[default]
+    Sigmoid,
+    /// Linear rescale between the window's minimum and maximum, clamped to `[0, 1]`.
+    MinMax,
+}
 
 /// Dynamically rescales streaming reward values into a stable [0, 1] range.
 ///
 /// See [module-level documentation](index.html) for usage and examples.
 #[derive(Debug, Clone)]
 pub struct RewardNormalizer {
-    /// Number of recent values to retain in the rolling window.
-    window: usize,
-    /// Stored reward values (oldest first).
-    values: Vec<f64>,
+    /// Stored reward values (oldest first); eviction is O(1) amortized.
+    values: RollingWindow,
+    /// Inverse CDF applied to empirical quantiles; `None` uses the sigmoid mapping.
+    calibration: Option<fn(f64) -> f64>,
+    /// Mapping used when no calibration is set.
+    mode: NormalizeMode,
+    /// Running mean of the stored rewards.
+    mean: f64,
+    /// Running sum of squared deviations from the mean (Welford's M2).
+    m2: f64,
+    /// Updates since `mean` and `m2` were last recomputed from scratch.
+    since_recompute: usize,
+    /// Sigmoid steepness for positive z-scores.
+    steep_pos: f64,
+    /// Sigmoid steepness for negative z-scores.
+    steep_neg: f64,
 }
 
 impl RewardNormalizer {
@@ -71,24 +164,121 @@ impl RewardNormalizer {
     pub fn new(window: usize) -> Self {
         assert!(window > 0, "window size must be > 0");
         Self {
-            window,
-            values: Vec::with_capacity(window),
+            values: RollingWindow::new(window),
+            calibration: None,
+            mode: NormalizeMode::Sigmoid,
+            mean: 0.0,
+            m2: 0.0,
+            since_recompute: 0,
+            steep_pos: 1.0,
+            steep_neg: 1.0,
+        }
+    }
+
+    /// Creates a normalizer whose sigmoid uses steepness `steep_pos` for
+    /// rewards above the mean and `steep_neg` for rewards below it.
+    ///
+    /// `new(window)` is equivalent to `with_asymmetric(window, 1.0, 1.0)`.
+    ///
+    /// # Panics
+    /// Panics if `window == 0` or either steepness is not positive.
+    pub fn with_asymmetric(window: usize, steep_pos: f64, steep_neg: f64) -> Self {
+        assert!(
+            steep_pos > 0.0 && steep_neg > 0.0,
+            "steepness must be positive"
+        );
+        Self {
+            steep_pos,
+            steep_neg,
+            ..Self::new(window)
         }
     }
 
+    /// Creates a normalizer that maps rewards using `mode`.
+    ///
+    /// `new(window)` is equivalent to `with_mode(window, NormalizeMode::Sigmoid)`.
+    ///
+    /// # Panics
+    /// Panics if `window == 0`.
+    pub fn with_mode(window: usize, mode: NormalizeMode) -> Self {
+        Self {
+            mode,
+            ..Self::new(window)
+        }
+    }
+
+    /// Maps rewards through the window's empirical CDF so normalized outputs
+    /// are approximately uniform on `[0, 1]`.
+    pub fn calibrate_uniform(self) -> Self {
+        self.calibrate_with(|q| q)
+    }
+
+    /// Maps rewards through the window's empirical CDF, then through
+    /// `inverse_cdf` to shape the output distribution.
+    ///
+    /// # Example
+    /// ```
+    /// use rustybrain::reward_normalizer::RewardNormalizer;
+    ///
+    /// // Square the quantile to skew outputs toward 0.
+    /// let mut rn = RewardNormalizer::new(4).calibrate_with(|q| q * q);
+    /// for r in [10.0, 20.0, 30.0, 40.0] {
+    ///     rn.update(r);
+    /// }
+    /// assert_eq!(rn.normalized(30.0), 0.625 * 0.625);
+    /// ```
+    pub fn calibrate_with(mut self, inverse_cdf: fn(f64) -> f64) -> Self {
+        self.calibration = Some(inverse_cdf);
+        self
+    }
+
+    /// Returns the number of rewards the backing buffer holds without reallocating.
+    ///
+    /// The buffer is allocated in full at construction (twice the window),
+    /// so this stays constant across updates unless [`reserve`](Self::reserve) is called.
+    pub fn capacity(&self) -> usize {
+        self.values.capacity()
+    }
+
+    /// Pre-allocates `additional` extra buffer slots.
+    ///
+    /// Updates never allocate. Evicted values are reclaimed by an occasional
+    /// O(window) compaction; extra slots make compactions proportionally rarer.
+    pub fn reserve(&mut self, additional: usize) {
+        self.values.reserve(additional);
+    }
+
     /// Inserts a new raw reward into the rolling window.
     ///
     /// If the window is already full, the oldest value is removed (FIFO).
     pub fn update(&mut self, reward: f64) {
-        if self.values.len() == self.window {
-            self.values.remove(0);
+        match self.values.push(reward) {
+            // Window was full: replace `old` with `reward`, keeping n fixed.
+            Some(old) => {
+                let n = self.values.len() as f64;
+                let old_mean = self.mean;
+                self.mean += (reward - old) / n;
+                self.m2 += (reward - old) * (reward - self.mean + old - old_mean);
+            }
+            None => {
+                let n = self.values.len() as f64;
+                let delta = reward - self.mean;
+                self.mean += delta / n;
+                self.m2 += delta * (reward - self.mean);
+            }
+        }
+        self.m2 = self.m2.max(0.0);
+
+        self.since_recompute += 1;
+        if self.since_recompute >= self.values.window() {
+            self.recompute();
         }
-        self.values.push(reward);
     }
 
     /// Normalizes the provided reward based on the current mean and standard deviation.
     ///
-    /// Returns a value in `[0.0, 1.0]` using a sigmoid transformation of the z-score.
+    /// Returns a value in `[0.0, 1.0]` using a sigmoid transformation of the z-score,
+    /// or a clamped min-max rescale in [`NormalizeMode::MinMax`].
     ///
     /// # Behavior
     /// - If no rewards have been recorded → returns `0.5`.
@@ -107,32 +297,179 @@ impl RewardNormalizer {
             return 0.5;
         }
 
-        let mean = self.mean();
-        let std = self.std(mean);
+        if let Some(inverse_cdf) = self.calibration {
+            return inverse_cdf(self.quantile(reward));
+        }
+        if self.mode == NormalizeMode::MinMax {
+            return self.min_max(reward);
+        }
 
-        // Avoid division by zero when all values are equal.
-        if std == 0.0 {
+        let mean = self.mean;
+        let std = self.std();
+
+        // Avoid division by zero when all values are (numerically) equal.
+        if std <= f64::EPSILON * (1.0 + mean.abs()) {
             return 0.5;
         }
 
         // Convert to z-score and map through a sigmoid into (0, 1)
         let z = (reward - mean) / std;
-        1.0 / (1.0 + (-z).exp())
+        let steepness = if z > 0.0 { self.steep_pos } else { self.steep_neg };
+        1.0 / (1.0 + (-steepness * z).exp())
+    }
+
+    /// Maps a normalized value back to the approximate raw reward it came
+    /// from, using the current window's statistics.
+    ///
+    /// For the sigmoid mapping this is `mean + logit(norm) / steepness · std`;
+    /// `norm` is clamped just inside `(0, 1)` so the result stays finite.
+    /// In [`NormalizeMode::MinMax`] it is `min + norm · (max − min)`. Returns
+    /// the current mean when the window is empty or has no spread.
+    ///
+    /// Calibrated normalizers are inverted as if they used the sigmoid, so the
+    /// result is only a rough indication of scale for them.
+    ///
+    /// # Example
+    /// ```
+    /// let mut rn = rustybrain::reward_normalizer::RewardNormalizer::new(4);
+    /// for r in [10.0, 20.0, 30.0, 40.0] {
+    ///     rn.update(r);
+    /// }
+    /// assert!((rn.denormalize(rn.normalized(33.0)) - 33.0).abs() < 1e-9);
+    /// ```
+    pub fn denormalize(&self, norm: f64) -> f64 {
+        let mean = self.mean;
+        if self.values.is_empty() {
+            return mean;
+        }
+        let norm = norm.clamp(DENORMALIZE_EPSILON, 1.0 - DENORMALIZE_EPSILON);
+
+        if self.calibration.is_none() && self.mode == NormalizeMode::MinMax {
+            let min = self.values.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = self.values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            if max <= min {
+                return mean;
+            }
+            return min + norm * (max - min);
+        }
+
+        let std = self.std();
+        if std <= f64::EPSILON * (1.0 + mean.abs()) {
+            return mean;
+        }
+        let logit = (norm / (1.0 - norm)).ln();
+        let steepness = if logit > 0.0 { self.steep_pos } else { self.steep_neg };
+        mean + logit / steepness * std
     }
 
-    /// Computes the arithmetic mean of all stored rewards.
-    fn mean(&self) -> f64 {
-        self.values.iter().copied().sum::<f64>() / self.values.len() as f64
+    /// Returns how trustworthy the current normalization is, in `[0.0, 1.0]`.
+    ///
+    /// The score is the window fill fraction scaled by a spread factor that
+    /// is `0.0` for zero variance and approaches `1.0` once the standard
+    /// deviation is well above a small tolerance relative to the mean.
+    ///
+    /// # Example
+    /// ```
+    /// let mut rn = rustybrain::reward_normalizer::RewardNormalizer::new(4);
+    /// assert_eq!(rn.confidence(), 0.0);
+    /// for r in [1.0, 2.0, 3.0, 4.0] {
+    ///     rn.update(r);
+    /// }
+    /// assert!(rn.confidence() > 0.9);
+    /// ```
+    pub fn confidence(&self) -> f64 {
+        if self.values.is_empty() {
+            return 0.0;
+        }
+
+        let fill = self.values.len() as f64 / self.values.window() as f64;
+        let mean = self.mean;
+        let std = self.std();
+        let tolerance = SPREAD_TOLERANCE * (1.0 + mean.abs());
+        fill * std / (std + tolerance)
+    }
+
+    /// Linear position of `reward` between the window's min and max, clamped to `[0, 1]`.
+    fn min_max(&self, reward: f64) -> f64 {
+        let min = self.values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = self.values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        if max <= min {
+            return 0.5;
+        }
+        ((reward - min) / (max - min)).clamp(0.0, 1.0)
+    }
+
+    /// Mid-rank empirical CDF of `reward` against the stored rewards, in `[0, 1]`.
+    fn quantile(&self, reward: f64) -> f64 {
+        let below = self.values.iter().filter(|&&x| x < reward).count();
+        let equal = self.values.iter().filter(|&&x| x == reward).count();
+        (below as f64 + 0.5 * equal as f64) / self.values.len() as f64
+    }
+
+    /// Returns the stored rewards, oldest first.
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Returns the arithmetic mean of the stored rewards (`0.0` when empty).
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    /// Returns the population standard deviation of the stored rewards (`0.0` when empty).
+    pub fn std(&self) -> f64 {
+        if self.values.is_empty() {
+            return 0.0;
+        }
+        (self.m2 / self.values.len() as f64).sqrt()
+    }
+
+    /// Checks that the streaming mean and variance agree with a fresh batch
+    /// computation over the window.
+    ///
+    /// The mean may differ by at most `1e-6 · (1 + |mean|)` and the variance
+    /// by at most `1e-6 · (1 + mean²)`. Variance is compared rather than the
+    /// standard deviation because the square root amplifies harmless rounding
+    /// when the window is (nearly) constant.
+    ///
+    /// This is O(N); it exists to catch bugs in the incremental update, not
+    /// for routine use.
+    ///
+    /// # Errors
+    /// Returns a description of the first statistic that disagrees.
+    pub fn validate(&self) -> Result<(), String> {
+        let (mean, m2) = self.batch_stats();
+        let n = self.values.len().max(1) as f64;
+        let checks = [
+            ("mean", self.mean, mean, 1.0 + mean.abs()),
+            ("variance", self.m2 / n, m2 / n, 1.0 + mean * mean),
+        ];
+        for (name, streaming, batch, scale) in checks {
+            if (streaming - batch).abs() > CONSISTENCY_TOLERANCE * scale {
+                return Err(format!(
+                    "streaming {} {} disagrees with batch {}",
+                    name, streaming, batch
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Exact mean and M2 of the window (`(0.0, 0.0)` when empty).
+    fn batch_stats(&self) -> (f64, f64) {
+        if self.values.is_empty() {
+            return (0.0, 0.0);
+        }
+        let n = self.values.len() as f64;
+        let mean = self.values.iter().sum::<f64>() / n;
+        let m2 = self.values.iter().map(|&x| (x - mean).powi(2)).sum();
+        (mean, m2)
     }
 
-    /// Computes the population standard deviation of stored rewards.
-    fn std(&self, mean: f64) -> f64 {
-        let var = self
-            .values
-            .iter()
-            .map(|&x| (x - mean).powi(2))
-            .sum::<f64>()
-            / self.values.len() as f64;
-        var.sqrt()
+    /// Recomputes the running mean and M2 exactly from the window.
+    fn recompute(&mut self) {
+        debug_assert!(self.validate().is_ok(), "{:?}", self.validate());
+        (self.mean, self.m2) = self.batch_stats();
+        self.since_recompute = 0;
     }
 }
\ No newline at end of file
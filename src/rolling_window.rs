@@ -0,0 +1,117 @@
+//! Fixed-size rolling window with O(1) amortized eviction.
+//!
+//! Shared storage for [`RewardTracker`](crate::metrics::reward_tracker::RewardTracker)
+//! and [`RewardNormalizer`](crate::reward_normalizer::RewardNormalizer).
+//!
+//! Values live in a single buffer of `window + slack` slots with a head
+//! index (`slack` starts at `window`). Evicting the oldest value only advances
+//! the head; once the buffer's end is reached, the live values are copied back
+//! to the front. That copy costs O(window) but happens at most once every
+//! `slack` pushes, so each push is O(1) amortized. Unlike a wrapping ring
+//! buffer, the live values are always contiguous and oldest-first, so the
+//! window derefs to a plain `&[f64]`.
+//!
+//! The whole buffer is allocated by [`RollingWindow::new`] and
+//! [`RollingWindow::reserve`]; pushes never allocate.
+
+use std::ops::Deref;
+
+use serde::{Deserialize, Serialize};
+
+/// Serialized form: the window size and its live values only.
+#[derive(Serialize, Deserialize)]
+struct WindowRepr {
+    window: usize,
+    values: Vec<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "WindowRepr", try_from = "WindowRepr")]
+pub(crate) struct RollingWindow {
+    /// Backing storage; live values are `buf[head..]`.
+    buf: Vec<f64>,
+    /// Index of the oldest live value.
+    head: usize,
+    /// Maximum number of live values.
+    window: usize,
+    /// Number of buffer slots used before compacting (`window + slack`).
+    slots: usize,
+}
+
+impl RollingWindow {
+    /// Creates an empty window holding at most `window` values.
+    pub(crate) fn new(window: usize) -> Self {
+        Self {
+            buf: Vec::with_capacity(2 * window),
+            head: 0,
+            window,
+            slots: 2 * window,
+        }
+    }
+
+    /// Appends `value`, returning the evicted oldest value if the window was full.
+    pub(crate) fn push(&mut self, value: f64) -> Option<f64> {
+        let evicted = if self.len() == self.window {
+            self.head += 1;
+            Some(self.buf[self.head - 1])
+        } else {
+            None
+        };
+        if self.buf.len() == self.slots {
+            self.buf.copy_within(self.head.., 0);
+            self.buf.truncate(self.buf.len() - self.head);
+            self.head = 0;
+        }
+        self.buf.push(value);
+        evicted
+    }
+
+    /// Grows the slack by `additional` slots, allocating them now so that
+    /// compaction copies happen less often.
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        self.slots += additional;
+        self.buf.reserve_exact(self.slots - self.buf.len());
+    }
+
+    /// Returns the number of values the backing buffer can hold without reallocating.
+    pub(crate) fn capacity(&self) -> usize {
+        self.buf.capacity()
+    }
+
+    /// Returns the maximum number of values held.
+    pub(crate) fn window(&self) -> usize {
+        self.window
+    }
+}
+
+impl From<RollingWindow> for WindowRepr {
+    fn from(w: RollingWindow) -> Self {
+        Self {
+            window: w.window,
+            values: w.to_vec(),
+        }
+    }
+}
+
+impl TryFrom<WindowRepr> for RollingWindow {
+    type Error = String;
+
+    fn try_from(repr: WindowRepr) -> Result<Self, String> {
+        if repr.window == 0 || repr.values.len() > repr.window {
+            return Err("invalid rolling window".into());
+        }
+        let mut w = RollingWindow::new(repr.window);
+        for x in repr.values {
+            w.push(x);
+        }
+        Ok(w)
+    }
+}
+
+impl Deref for RollingWindow {
+    type Target = [f64];
+
+    fn deref(&self) -> &[f64] {
+        &self.buf[self.head..]
+    }
+}
@@ -1,48 +1,528 @@
 //! REST service exposing bandit algorithms via Axum.
 //!
+//! Supported strategies: `epsilon_greedy`, `ucb1`, `thompson`
+//! (Beta-Bernoulli; `param` is ignored and rewards are clamped to `[0, 1]`),
+//! `thompson_gaussian` (Normal-Inverse-Gamma for real-valued rewards; `param`
+//! is ignored) and `linucb` (contextual; `param` is alpha and `context_dim` is required).
+//!
 //! Endpoints:
 //! - POST /bandit            -> create bandit, returns { "id": "<uuid>" }
-//! - GET  /bandit/:id/select -> returns { "arm": <u32> }
-//! - POST /bandit/:id/update -> body: { "arm": u32, "reward": f64 }, returns {}
-//! - GET  /bandit/:id/stats  -> returns { mean, min, max, count }
+//! - GET  /bandit            -> returns [{ "id", "strategy", "num_arms" }] for every bandit
+//! - DELETE /bandit/:id      -> removes the bandit (404 if unknown)
+//! - GET  /bandit/:id/select -> returns { "arm": <u32>, "label"? }
+//!   (`?k=3` instead returns a slate of up to k distinct arms,
+//!   { "arms": [{ "arm", "label"? }, ...] }, in selection order)
+//! - POST /bandit/:id/select -> body: { "context"?, "k"? }, same responses as GET
+//! - POST /bandit/:id/update -> body: { "arm": u32 | label, "reward": f64, "context"? },
+//!   returns {}
+//!   (400 for an out-of-range arm or non-finite reward)
+//! - POST /bandit/:id/update_batch -> body: { "updates": [{ "arm", "reward" }, ...] },
+//!   applies all updates in order (or none, if any is invalid; the 400 names the
+//!   index of the first invalid update), returns { "applied" }
+//! - POST /bandit/:id/reset  -> clears learned statistics, keeping the configuration
+//!   (404 if unknown)
+//! - GET  /bandit/:id/stats  -> returns { mean, min, max, count, epsilon?, normalized?, arms }
+//!   (`epsilon` is the current exploration rate, epsilon_greedy only;
+//!   `?space=normalized` reports normalized rewards, see below)
+//!   (`arms` has one { arm, label?, mean, count, recent_min, recent_max, last_selected }
+//!   per arm: the strategy's estimate, the raw min/max over the arm's last
+//!   `tracker_window` rewards, and the latest selection in Unix milliseconds)
+//! - GET  /bandit/:id/arms   -> returns [{ arm, label?, mean, bonus, count }] per arm
+//!   (`bonus` is the UCB1 confidence bonus; `null` for other strategies)
+//! - POST /bandit/:id/arms   -> body: { "label"? }; appends an untried arm,
+//!   returns { "arm" } (epsilon_greedy / ucb1 only; 400 if the label is taken)
+//! - DELETE /bandit/:id/arms/:arm -> removes the arm; later arms shift down
+//!   by one index (400 if out of range or the last arm)
+//! - POST /bandit/:id/clone  -> copies the bandit under a new id, returns { "id": "<uuid>" }
+//! - GET  /bandit/:id/export -> returns { "strategy", "state", "tracker"?, "activity"?,
+//!   "budget"?, "normalization"? } for checkpointing
+//! - POST /bandit/import     -> body: an export document; registers it, returns { "id": "<uuid>" }
+//! - POST /bandit/snapshot   -> body: { "path" }; writes every bandit to `path` inside
+//!   [`BanditApiConfig::snapshot_dir`] (see [`BanditRegistry::save_to_path`]), returns {};
+//!   400 if no directory is configured or `path` is absolute or contains `..`
+//!
+//! epsilon_greedy and ucb1 bandits accept `labels` at creation, one distinct
+//! name per arm; `/select`, `/arms` and `/stats` then include each arm's
+//! `label`, and updates may name the arm by label instead of index (400 for
+//! an unknown label).
+//!
+//! epsilon_greedy bandits accept `alpha`, a constant step size in `(0, 1]`
+//! that makes the estimates track non-stationary rewards (see
+//! [`EpsilonGreedy::with_step_size`]).
+//!
+//! Context-free bandits accept `costs` (one non-negative cost per arm) and a
+//! total `budget` together at creation (see
+//! [`BudgetedBandit`](crate::bandit::budgeted::BudgetedBandit)). `/select`
+//! then only returns arms that fit in the remaining budget, and answers 400
+//! once none does; every update charges the arm's cost, `/stats` reports
+//! `budget_remaining` and `budget_overspent` (how far concurrent selections
+//! went over budget), and `/reset` restores the full budget. Budgeted
+//! bandits cannot add or remove arms.
+//!
+//! linucb bandits need a `context` of `context_dim` finite numbers on every
+//! select (via POST) and update, and answer 400 without one; other
+//! strategies ignore the field. Their `/stats` and `/arms` report each arm's
+//! mean observed reward regardless of context.
+//!
+//! A bandit created with `normalize_window` (alias `normalize`) passes every
+//! reward through a [`RewardNormalizer`] over that many recent rewards before
+//! the strategy sees it. Its `/stats` then default to raw rewards
+//! (`?space=raw`), while `?space=normalized` reports the rewards the strategy
+//! actually received; both include the latest normalized reward as `normalized`;
+//! asking for normalized stats of a bandit without a normalizer is a 400.
+//! `tracker_window` (default 50) sets how many recent rewards those stats,
+//! and epsilon_greedy's, are computed over.
+//! Exports of normalized bandits carry the rewards in the normalizer's
+//! window, which `/import` replays to rebuild it.
+//!
+//! [`routes_with_config`] can wrap every response in a `{ data, error }`
+//! envelope (see [`crate::service::envelope`]); [`routes`] returns raw bodies.
+//! It can also cache `/stats` responses for a TTL
+//! ([`BanditApiConfig::stats_ttl`]): cache hits skip the bandit lock entirely,
+//! and updates, resets and arm changes invalidate the bandit's cached entry
+//! under its lock. `/select` does not, so cached `last_selected` times may lag
+//! by up to the TTL.
+//!
+//! The registry map sits behind an `RwLock` and each bandit behind its own
+//! `Mutex`, so requests for different ids never contend beyond a brief read
+//! lock on the map; only create/import/clone/delete take the map write lock.
+//! `/select` still locks its bandit exclusively: selection mutates state
+//! (e.g. `EpsilonGreedy::select_arm` advances its RNG).
 
-use std::{collections::HashMap, sync::{Arc, Mutex}};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    io,
+    path::{Component, Path as FsPath, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    routing::{get, post},
+    middleware,
+    routing::{delete, get, post},
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use tower_http::trace::TraceLayer;
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+use crate::bandit::budgeted::Budget;
 use crate::bandit::epsilon_greedy::EpsilonGreedy;
+use crate::bandit::gaussian_thompson::GaussianThompson;
+use crate::bandit::linucb::LinUcb;
+use crate::bandit::{check_priors, check_update, BanditError, BanditPolicy, BanditSnapshot};
+use crate::bandit::thompson::ThompsonSampling;
 use crate::bandit::ucb1::Ucb1;
 use crate::metrics::reward_tracker::RewardTracker;
+use crate::reward_normalizer::RewardNormalizer;
+use crate::service::envelope;
 
+/// Rebuilds the policy named `name` from its [`BanditPolicy::to_json`] state.
+///
+/// Adding a strategy means extending this and the parsing in `create_bandit`;
+/// handlers only go through [`BanditPolicy`].
+fn import_policy(name: &str, state: &str) -> Result<Box<dyn BanditPolicy>, &'static str> {
+    let invalid = |_| "invalid bandit state";
+    Ok(match name {
+        "epsilon_greedy" => Box::new(EpsilonGreedy::from_json(state).map_err(invalid)?),
+        "ucb1" => Box::new(Ucb1::from_json(state).map_err(invalid)?),
+        "thompson" => Box::new(ThompsonSampling::from_json(state).map_err(invalid)?),
+        "thompson_gaussian" => Box::new(GaussianThompson::from_json(state).map_err(invalid)?),
+        "linucb" => Box::new(LinUcb::from_json(state).map_err(invalid)?),
+        _ => return Err("unsupported strategy"),
+    })
+}
+
+/// Rewards kept by a bandit's `/stats` trackers unless `tracker_window` is given.
+const DEFAULT_TRACKER_WINDOW: usize = 50;
+
+/// Reward normalization applied in front of a bandit, with trackers for
+/// both reward spaces.
 #[derive(Clone)]
-struct EpsilonGreedyTracked {
-    bandit: EpsilonGreedy,
-    tracker: RewardTracker,
+struct Normalization {
+    window: usize,
+    normalizer: RewardNormalizer,
+    raw: RewardTracker,
+    normalized: RewardTracker,
+}
+
+impl Normalization {
+    fn new(window: usize, tracker_window: usize) -> Self {
+        Self {
+            window,
+            normalizer: RewardNormalizer::new(window),
+            raw: RewardTracker::new(tracker_window),
+            normalized: RewardTracker::new(tracker_window),
+        }
+    }
+
+    /// Records `reward` and returns its normalized value.
+    fn apply(&mut self, reward: f64) -> f64 {
+        self.normalizer.update(reward);
+        let normalized = self.normalizer.normalized(reward);
+        self.raw.update(reward);
+        self.normalized.update(normalized);
+        normalized
+    }
+
+    /// The most recent normalized reward, if any.
+    fn last(&self) -> Option<f64> {
+        self.normalized.values().last().copied()
+    }
+
+    fn export(&self) -> NormalizationExport {
+        NormalizationExport {
+            window: self.window,
+            rewards: self.normalizer.values().to_vec(),
+            raw: state_of(&self.raw),
+            normalized: state_of(&self.normalized),
+        }
+    }
+}
+
+/// Export form of a [`Normalization`]. The normalizer is rebuilt by
+/// replaying the rewards in its window.
+#[derive(Serialize, Deserialize)]
+struct NormalizationExport {
+    window: usize,
+    rewards: Vec<f64>,
+    raw: serde_json::Value,
+    normalized: serde_json::Value,
+}
+
+impl NormalizationExport {
+    fn into_normalization(self) -> Result<Normalization, &'static str> {
+        if self.window == 0 || self.rewards.len() > self.window {
+            return Err("invalid normalize window");
+        }
+        if !self.rewards.iter().all(|r| r.is_finite()) {
+            return Err("normalized rewards must be finite");
+        }
+        let tracker = |t: serde_json::Value| {
+            RewardTracker::from_json(&t.to_string()).map_err(|_| "invalid normalization state")
+        };
+        let mut normalizer = RewardNormalizer::new(self.window);
+        for &reward in &self.rewards {
+            normalizer.update(reward);
+        }
+        Ok(Normalization {
+            window: self.window,
+            normalizer,
+            raw: tracker(self.raw)?,
+            normalized: tracker(self.normalized)?,
+        })
+    }
+}
+
+/// Recent raw rewards and latest selection time of one arm.
+#[derive(Clone, Serialize, Deserialize)]
+struct ArmActivity {
+    rewards: RewardTracker,
+    /// Milliseconds since the Unix epoch.
+    last_selected: Option<u64>,
+}
+
+impl ArmActivity {
+    fn new(window: usize) -> Self {
+        Self {
+            rewards: RewardTracker::new(window),
+            last_selected: None,
+        }
+    }
 }
 
 #[derive(Clone)]
-enum Strategy {
-    EpsilonGreedy(EpsilonGreedyTracked),
-    Ucb1(Ucb1),
+struct BanditEntry {
+    policy: Box<dyn BanditPolicy>,
+    /// Recent raw rewards backing `/stats`, for bandits that keep them.
+    tracker: Option<RewardTracker>,
+    /// Present when the bandit was created with `normalize_window`.
+    normalization: Option<Normalization>,
+    /// One entry per arm, kept in step with the strategy's arms.
+    activity: Vec<ArmActivity>,
+    /// Present when the bandit was created with `costs` and `budget`.
+    budget: Option<Budget>,
 }
 
+impl BanditEntry {
+    fn new(
+        policy: Box<dyn BanditPolicy>,
+        normalization: Option<Normalization>,
+        window: usize,
+    ) -> Self {
+        let activity = vec![ArmActivity::new(window); policy.counts().len()];
+        Self {
+            policy,
+            tracker: None,
+            normalization,
+            activity,
+            budget: None,
+        }
+    }
+
+    /// Selects an arm and records when it was chosen.
+    fn select_arm(&mut self, context: Option<&[f64]>) -> Result<usize, BanditError> {
+        // Budgets are only accepted for context-free policies.
+        let arm = match (&self.budget, self.policy.as_bandit_mut()) {
+            (Some(budget), Some(bandit)) => {
+                budget.select_arm(bandit).ok_or(BanditError::BudgetExhausted)?
+            }
+            _ => self.policy.select_arm(context)?,
+        };
+        self.record_selection(&[arm]);
+        Ok(arm)
+    }
+
+    /// Selects a slate of up to `k` arms and records when they were chosen.
+    fn select_arms(
+        &mut self,
+        k: usize,
+        context: Option<&[f64]>,
+    ) -> Result<Vec<usize>, BanditError> {
+        let arms = match (&self.budget, self.policy.as_bandit_mut()) {
+            (Some(budget), Some(bandit)) => {
+                let arms = budget.select_arms(bandit, k);
+                if arms.is_empty() {
+                    return Err(BanditError::BudgetExhausted);
+                }
+                arms
+            }
+            _ => self.policy.select_arms(k, context)?,
+        };
+        self.record_selection(&arms);
+        Ok(arms)
+    }
+
+    fn record_selection(&mut self, arms: &[usize]) {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        for &arm in arms {
+            self.activity[arm].last_selected = Some(now.as_millis() as u64);
+        }
+    }
+
+    /// Per-arm entries of `/stats`.
+    fn arm_summaries(&self) -> Vec<ArmSummary> {
+        let snapshot = self.policy.snapshot();
+        self.activity
+            .iter()
+            .enumerate()
+            .map(|(arm, activity)| {
+                let recent = (activity.rewards.count() > 0).then_some(&activity.rewards);
+                ArmSummary {
+                    arm,
+                    label: self.policy.arm_label(arm).map(String::from),
+                    mean: snapshot.values[arm],
+                    count: snapshot.counts[arm],
+                    recent_min: recent.map(RewardTracker::min),
+                    recent_max: recent.map(RewardTracker::max),
+                    last_selected: activity.last_selected,
+                }
+            })
+            .collect()
+    }
+
+    /// Resolves an arm given by index or by label.
+    fn resolve(&self, arm: &ArmRef) -> Result<usize, BanditError> {
+        match arm {
+            ArmRef::Index(i) => Ok(*i as usize),
+            ArmRef::Label(label) => self
+                .policy
+                .arm_index(label)
+                .ok_or_else(|| BanditError::UnknownArm(label.clone())),
+        }
+    }
+
+    /// Validates an update without applying it.
+    ///
+    /// The result holds only for this one update against the current state;
+    /// callers applying several updates must validate each as they go (see
+    /// `update_batch`).
+    fn check(&self, arm: usize, reward: f64, context: Option<&[f64]>) -> Result<(), BanditError> {
+        check_update(self.policy.counts().len(), arm, reward)?;
+        self.policy.check_context(context)?;
+        // Normalized rewards lie in [0, 1], so only raw rewards can overflow
+        // the policy's statistics.
+        if self.normalization.is_none() {
+            self.policy.check_reward(arm, reward)?;
+        }
+        Ok(())
+    }
+
+    /// Clears the bandit's learned statistics and reward history, keeping its
+    /// configuration.
+    fn reset(&mut self) {
+        self.policy.reset();
+        if let Some(tracker) = &mut self.tracker {
+            *tracker = RewardTracker::new(tracker.window());
+        }
+        if let Some(n) = &mut self.normalization {
+            *n = Normalization::new(n.window, n.raw.window());
+        }
+        for activity in &mut self.activity {
+            *activity = ArmActivity::new(activity.rewards.window());
+        }
+        if let Some(budget) = &mut self.budget {
+            budget.reset();
+        }
+    }
+
+    /// Records `reward` for `arm`, normalizing it first if configured, and
+    /// charges the arm's cost to the budget if there is one.
+    fn try_update(
+        &mut self,
+        arm: usize,
+        reward: f64,
+        context: Option<&[f64]>,
+    ) -> Result<(), BanditError> {
+        // Validate before the normalizer records the reward.
+        self.check(arm, reward, context)?;
+        self.activity[arm].rewards.update(reward);
+        let seen = match &mut self.normalization {
+            Some(n) => n.apply(reward),
+            None => reward,
+        };
+        self.policy.try_update(arm, seen, context)?;
+        if let Some(tracker) = &mut self.tracker {
+            tracker.update(reward);
+        }
+        if let Some(budget) = &mut self.budget {
+            budget.charge(arm);
+        }
+        Ok(())
+    }
+}
+
+/// A registered bandit; locked independently of the registry map.
+type SharedEntry = Arc<Mutex<BanditEntry>>;
+
+/// The set of registered bandits behind a bandit router.
+///
+/// Cloning is cheap and shares the bandits, so other routers (see
+/// [`crate::service::AppState`]) can observe the same registry.
 #[derive(Clone, Default)]
-struct Registry {
-    map: Arc<Mutex<HashMap<String, Strategy>>>,
+pub struct BanditRegistry {
+    map: Arc<RwLock<HashMap<String, SharedEntry>>>,
+    /// Cached `/stats` responses with the time they were computed.
+    stats_cache: Arc<Mutex<HashMap<String, (Instant, StatsResp)>>>,
+    /// How long a cached `/stats` response stays valid; `None` disables caching.
+    stats_ttl: Option<Duration>,
+    /// Directory `/snapshot` writes into; `None` disables the endpoint.
+    snapshot_dir: Option<PathBuf>,
+}
+
+impl BanditRegistry {
+    /// Looks up a bandit, holding the map read lock only for the lookup.
+    fn entry(&self, id: &str, op: &str) -> Result<SharedEntry, (StatusCode, String)> {
+        self.map
+            .read()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| unknown_id(id, op))
+    }
+
+    /// Drops the cached `/stats` of `id`. Callers that changed the bandit
+    /// must still hold its lock, or a concurrent `/stats` could cache the
+    /// state from before the change after this runs.
+    fn invalidate_stats(&self, id: &str) {
+        self.stats_cache.lock().unwrap().remove(id);
+    }
+
+    fn insert(&self, id: String, entry: BanditEntry) {
+        self.map.write().unwrap().insert(id, Arc::new(Mutex::new(entry)));
+    }
+
+    /// Per-arm statistics of every registered bandit, sorted by id.
+    pub fn snapshots(&self) -> Vec<(String, BanditSnapshot)> {
+        let map = self.map.read().unwrap();
+        let mut snapshots: Vec<(String, BanditSnapshot)> = map
+            .iter()
+            .map(|(id, entry)| (id.clone(), entry.lock().unwrap().policy.snapshot()))
+            .collect();
+        snapshots.sort_by(|a, b| a.0.cmp(&b.0));
+        snapshots
+    }
+
+    /// Writes every bandit, keyed by id, to `path` as JSON (the same per-bandit
+    /// document as `/export`).
+    ///
+    /// The snapshot is written to a temporary file next to `path` and then
+    /// renamed over it, so `path` never holds a partially written snapshot.
+    pub fn save_to_path(&self, path: &FsPath) -> io::Result<()> {
+        let map = self.map.read().unwrap();
+        let bandits: BTreeMap<String, BanditExport> = map
+            .iter()
+            .map(|(id, entry)| (id.clone(), BanditExport::of(&entry.lock().unwrap())))
+            .collect();
+        drop(map);
+        let json = serde_json::to_string(&RegistrySnapshot { bandits })?;
+        let name = path.file_name().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "snapshot path has no file name")
+        })?;
+        let mut tmp_name = name.to_os_string();
+        tmp_name.push(format!(".{}.tmp", Uuid::new_v4()));
+        let tmp = path.with_file_name(tmp_name);
+        std::fs::write(&tmp, json)
+            .and_then(|()| std::fs::rename(&tmp, path))
+            .inspect_err(|_| {
+                let _ = std::fs::remove_file(&tmp);
+            })
+    }
+
+    /// Rebuilds a registry from a file written by [`save_to_path`](Self::save_to_path),
+    /// keeping every bandit's id.
+    ///
+    /// # Errors
+    /// Fails if the file cannot be read, or with [`io::ErrorKind::InvalidData`]
+    /// if it is not a valid snapshot.
+    pub fn load_from_path(path: &FsPath) -> io::Result<BanditRegistry> {
+        let snapshot: RegistrySnapshot = serde_json::from_slice(&std::fs::read(path)?)?;
+        let registry = BanditRegistry::default();
+        for (id, export) in snapshot.bandits {
+            let entry = export
+                .into_entry()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{id}: {e}")))?;
+            registry.insert(id, entry);
+        }
+        Ok(registry)
+    }
+}
+
+/// On-disk form of a [`BanditRegistry`].
+#[derive(Serialize, Deserialize)]
+struct RegistrySnapshot {
+    bandits: BTreeMap<String, BanditExport>,
 }
 
 #[derive(Deserialize)]
 struct CreateReq {
-    strategy: String, // "epsilon_greedy"
-    param: f64,       // epsilon
+    strategy: String,        // "epsilon_greedy", "ucb1", "thompson",
+                             // "thompson_gaussian" or "linucb"
+    param: f64,              // epsilon, c or alpha (unused by thompson and thompson_gaussian)
     num_arms: usize,
+    reward_min: Option<f64>, // ucb1 only: expected reward range,
+    reward_max: Option<f64>, // scales the exploration bonus
+    seed: Option<u64>,       // epsilon_greedy / thompson(_gaussian) RNG seed (default 42)
+    #[serde(alias = "normalize")]
+    normalize_window: Option<usize>, // normalize rewards over this many recent values
+    priors: Option<Vec<PriorReq>>,   // epsilon_greedy / ucb1: one initial estimate per arm
+    labels: Option<Vec<String>>,     // epsilon_greedy / ucb1: one name per arm
+    tracker_window: Option<usize>,   // rewards kept for `/stats` (default 50)
+    context_dim: Option<usize>,      // linucb only: length of every context
+    alpha: Option<f64>,              // epsilon_greedy only: constant step size in (0, 1]
+    costs: Option<Vec<f64>>,         // context-free only: cost of one pull per arm
+    budget: Option<f64>,             // total cost allowed; requires `costs`
+}
+
+/// Historical estimate an arm starts from.
+#[derive(Deserialize)]
+struct PriorReq {
+    value: f64,
+    count: u64,
 }
 
 #[derive(Serialize)]
@@ -53,128 +533,817 @@ struct CreateResp {
 #[derive(Serialize)]
 struct SelectResp {
     arm: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AddArmReq {
+    label: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SelectReq {
+    context: Option<Vec<f64>>,
+    k: Option<usize>,
+}
+
+#[derive(Deserialize)]
+struct SelectQuery {
+    /// Slate size; a single arm is selected when absent.
+    k: Option<usize>,
+}
+
+/// Response of `/select`: one arm, or a slate when `k` was given.
+#[derive(Serialize)]
+#[serde(untagged)]
+enum SelectOut {
+    Arm(SelectResp),
+    Slate { arms: Vec<SelectResp> },
 }
 
 #[derive(Deserialize)]
 struct UpdateReq {
-    arm: u32,
+    arm: ArmRef,
     reward: f64,
+    context: Option<Vec<f64>>, // linucb only
+}
+
+/// An arm given by index or, for labeled bandits, by name.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ArmRef {
+    Index(u32),
+    Label(String),
+}
+
+#[derive(Deserialize)]
+struct BatchUpdateReq {
+    updates: Vec<UpdateReq>,
+}
+
+#[derive(Serialize)]
+struct BatchUpdateResp {
+    applied: usize,
+}
+
+/// Logs and builds the 404 returned for an unknown bandit id.
+fn unknown_id(id: &str, op: &str) -> (StatusCode, String) {
+    warn!(bandit_id = %id, op, "unknown bandit id");
+    (StatusCode::NOT_FOUND, "unknown id".into())
+}
+
+/// Logs and builds a 400 response for an invalid request.
+fn bad_request(op: &str, msg: &str) -> (StatusCode, String) {
+    warn!(op, error = msg, "rejected request");
+    (StatusCode::BAD_REQUEST, msg.into())
 }
 
 async fn create_bandit(
-    State(reg): State<Registry>,
+    State(reg): State<BanditRegistry>,
     Json(req): Json<CreateReq>,
 ) -> Result<Json<CreateResp>, (StatusCode, String)> {
-    if !(req.strategy == "epsilon_greedy" || req.strategy == "ucb1") {
-        return Err((StatusCode::BAD_REQUEST, "unsupported strategy".into()));
+    if !matches!(
+        req.strategy.as_str(),
+        "epsilon_greedy" | "ucb1" | "thompson" | "thompson_gaussian" | "linucb"
+    ) {
+        return Err(bad_request("create", "unsupported strategy"));
     }
     if req.num_arms == 0 {
-        return Err((StatusCode::BAD_REQUEST, "invalid number of arms".into()));
+        return Err(bad_request("create", "invalid number of arms"));
+    }
+    if req.normalize_window == Some(0) {
+        return Err(bad_request("create", "invalid normalize window"));
     }
+    let tracker_window = req.tracker_window.unwrap_or(DEFAULT_TRACKER_WINDOW);
+    if tracker_window == 0 {
+        return Err(bad_request("create", "invalid tracker window"));
+    }
+    let priors: Option<Vec<(f64, u64)>> = req
+        .priors
+        .as_ref()
+        .map(|p| p.iter().map(|p| (p.value, p.count)).collect());
+    if let Some(priors) = &priors {
+        if matches!(req.strategy.as_str(), "thompson" | "thompson_gaussian" | "linucb") {
+            let msg = format!("priors are not supported for {}", req.strategy);
+            return Err(bad_request("create", &msg));
+        }
+        if priors.len() != req.num_arms {
+            return Err(bad_request("create", "invalid priors"));
+        }
+        check_priors(priors).map_err(|e| bad_request("create", e))?;
+    }
+    if let Some(alpha) = req.alpha {
+        if req.strategy != "epsilon_greedy" {
+            let msg = format!("a step size is not supported for {}", req.strategy);
+            return Err(bad_request("create", &msg));
+        }
+        if !(alpha > 0.0 && alpha <= 1.0) {
+            return Err(bad_request("create", "invalid step size"));
+        }
+    }
+    if let Some(labels) = &req.labels {
+        if matches!(req.strategy.as_str(), "thompson" | "thompson_gaussian" | "linucb") {
+            let msg = format!("labels are not supported for {}", req.strategy);
+            return Err(bad_request("create", &msg));
+        }
+        if labels.len() != req.num_arms {
+            return Err(bad_request("create", "invalid labels"));
+        }
+        let mut seen = std::collections::HashSet::new();
+        if !labels.iter().all(|label| seen.insert(label)) {
+            return Err(bad_request("create", "duplicate labels"));
+        }
+    }
+
+    let budget = match (&req.costs, req.budget) {
+        (None, None) => None,
+        (Some(costs), Some(total)) => {
+            if req.strategy == "linucb" {
+                return Err(bad_request("create", "budgets are not supported for linucb"));
+            }
+            if costs.len() != req.num_arms || costs.iter().any(|&c| !(c >= 0.0 && c.is_finite())) {
+                return Err(bad_request("create", "invalid costs"));
+            }
+            if !(total >= 0.0 && total.is_finite()) {
+                return Err(bad_request("create", "invalid budget"));
+            }
+            Some(Budget::new(costs.clone(), total))
+        }
+        _ => return Err(bad_request("create", "costs and budget must be given together")),
+    };
 
     let id = Uuid::new_v4().to_string();
+    let seed = req.seed.unwrap_or(42);
 
-    let strategy = match req.strategy.as_str() {
+    let policy: Box<dyn BanditPolicy> = match req.strategy.as_str() {
         "epsilon_greedy" => {
             if !(0.0..=1.0).contains(&req.param) {
-                return Err((StatusCode::BAD_REQUEST, "invalid epsilon".into()));
+                return Err(bad_request("create", "invalid epsilon"));
             }
-            let tracked = EpsilonGreedyTracked {
-                bandit: EpsilonGreedy::new(req.num_arms, req.param),
-                tracker: RewardTracker::new(50),
+            let mut bandit = match priors {
+                Some(priors) => EpsilonGreedy::with_priors(req.param, priors).fork(seed),
+                None => EpsilonGreedy::with_seed(req.num_arms, req.param, seed),
             };
-            Strategy::EpsilonGreedy(tracked)
+            if let Some(labels) = req.labels.clone() {
+                bandit.set_labels(labels);
+            }
+            if let Some(alpha) = req.alpha {
+                bandit = bandit.with_step_size(alpha);
+            }
+            Box::new(bandit)
         }
         "ucb1" => {
             if req.param < 0.0 {
-                return Err((StatusCode::BAD_REQUEST, "invalid exploration factor".into()));
+                return Err(bad_request("create", "invalid exploration factor"));
+            }
+            let mut ucb = match (req.reward_min, req.reward_max, priors) {
+                (None, None, None) => Ucb1::new(req.num_arms, req.param),
+                (None, None, Some(priors)) => Ucb1::with_priors(req.param, priors),
+                (Some(lo), Some(hi), None) if lo < hi => {
+                    Ucb1::with_range(req.num_arms, req.param, lo, hi)
+                }
+                (Some(_), Some(_), Some(_)) => {
+                    return Err(bad_request("create", "priors cannot be combined with a reward range"))
+                }
+                _ => return Err(bad_request("create", "invalid reward range")),
+            };
+            if let Some(labels) = req.labels.clone() {
+                ucb.set_labels(labels);
+            }
+            Box::new(ucb)
+        }
+        "thompson" => Box::new(ThompsonSampling::new(req.num_arms, seed)),
+        "thompson_gaussian" => Box::new(GaussianThompson::new(req.num_arms, seed)),
+        "linucb" => {
+            if req.param < 0.0 || !req.param.is_finite() {
+                return Err(bad_request("create", "invalid alpha"));
+            }
+            match req.context_dim {
+                Some(dim) if dim > 0 => Box::new(LinUcb::new(req.num_arms, dim, req.param)),
+                _ => return Err(bad_request("create", "invalid context dimension")),
             }
-            Strategy::Ucb1(Ucb1::new(req.num_arms, req.param))
         }
         _ => unreachable!(),
     };
 
-    reg.map.lock().unwrap().insert(id.clone(), strategy);
+    let normalization = req
+        .normalize_window
+        .map(|window| Normalization::new(window, tracker_window));
+    let mut entry = BanditEntry::new(policy, normalization, tracker_window);
+    entry.budget = budget;
+    // epsilon_greedy reports `/stats` from its recent rewards.
+    if req.strategy == "epsilon_greedy" {
+        entry.tracker = Some(RewardTracker::new(tracker_window));
+    }
+    reg.insert(id.clone(), entry);
+    info!(bandit_id = %id, op = "create", strategy = %req.strategy, num_arms = req.num_arms, "bandit created");
     Ok(Json(CreateResp { id }))
 }
 
+#[derive(Serialize)]
+struct ListEntry {
+    id: String,
+    strategy: &'static str,
+    num_arms: usize,
+}
+
+async fn list_bandits(State(reg): State<BanditRegistry>) -> Json<Vec<ListEntry>> {
+    let map = reg.map.read().unwrap();
+    let mut entries: Vec<ListEntry> = map
+        .iter()
+        .map(|(id, entry)| {
+            let entry = entry.lock().unwrap();
+            ListEntry {
+                id: id.clone(),
+                strategy: entry.policy.name(),
+                num_arms: entry.policy.counts().len(),
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| a.id.cmp(&b.id));
+    Json(entries)
+}
+
+async fn delete_bandit(
+    State(reg): State<BanditRegistry>,
+    Path(id): Path<String>,
+) -> Result<(), (StatusCode, String)> {
+    let entry = reg
+        .map
+        .write()
+        .unwrap()
+        .remove(&id)
+        .ok_or_else(|| unknown_id(&id, "delete"))?;
+    let _entry = entry.lock().unwrap();
+    reg.invalidate_stats(&id);
+    info!(bandit_id = %id, op = "delete", "bandit deleted");
+    Ok(())
+}
+
 async fn select_arm(
-    State(reg): State<Registry>,
+    State(reg): State<BanditRegistry>,
     Path(id): Path<String>,
-) -> Result<Json<SelectResp>, (StatusCode, String)> {
-    let mut map = reg.map.lock().unwrap();
-    let entry = map.get_mut(&id).ok_or((StatusCode::NOT_FOUND, "unknown id".into()))?;
-    let arm = match entry {
-        Strategy::EpsilonGreedy(t) => t.bandit.select_arm() as u32,
-        Strategy::Ucb1(b) => b.select_arm() as u32,
+    Query(query): Query<SelectQuery>,
+) -> Result<Json<SelectOut>, (StatusCode, String)> {
+    select(&reg, &id, query.k, None)
+}
+
+async fn select_arm_with_context(
+    State(reg): State<BanditRegistry>,
+    Path(id): Path<String>,
+    Json(req): Json<SelectReq>,
+) -> Result<Json<SelectOut>, (StatusCode, String)> {
+    select(&reg, &id, req.k, req.context.as_deref())
+}
+
+fn select(
+    reg: &BanditRegistry,
+    id: &str,
+    k: Option<usize>,
+    context: Option<&[f64]>,
+) -> Result<Json<SelectOut>, (StatusCode, String)> {
+    if k == Some(0) {
+        return Err(bad_request("select", "invalid slate size"));
+    }
+    let entry = reg.entry(id, "select")?;
+    // Selection mutates the bandit, so this is an exclusive lock on it.
+    let mut entry = entry.lock().unwrap();
+    let Some(k) = k else {
+        let arm = entry
+            .select_arm(context)
+            .map_err(|e| bad_request("select", &e.to_string()))? as u32;
+        let label = entry.policy.arm_label(arm as usize).map(String::from);
+        drop(entry);
+        debug!(bandit_id = %id, op = "select", arm, "arm selected");
+        return Ok(Json(SelectOut::Arm(SelectResp { arm, label })));
     };
-    Ok(Json(SelectResp { arm }))
+    let arms: Vec<SelectResp> = entry
+        .select_arms(k, context)
+        .map_err(|e| bad_request("select", &e.to_string()))?
+        .into_iter()
+        .map(|arm| SelectResp {
+            arm: arm as u32,
+            label: entry.policy.arm_label(arm).map(String::from),
+        })
+        .collect();
+    drop(entry);
+    debug!(bandit_id = %id, op = "select", k, "slate selected");
+    Ok(Json(SelectOut::Slate { arms }))
+}
+
+async fn reset_bandit(
+    State(reg): State<BanditRegistry>,
+    Path(id): Path<String>,
+) -> Result<(), (StatusCode, String)> {
+    let entry = reg.entry(&id, "reset")?;
+    let mut entry = entry.lock().unwrap();
+    entry.reset();
+    reg.invalidate_stats(&id);
+    drop(entry);
+    info!(bandit_id = %id, op = "reset", "bandit reset");
+    Ok(())
 }
 
 async fn update_reward(
-    State(reg): State<Registry>,
+    State(reg): State<BanditRegistry>,
     Path(id): Path<String>,
     Json(req): Json<UpdateReq>,
 ) -> Result<(), (StatusCode, String)> {
-    let mut map = reg.map.lock().unwrap();
-    let entry = map.get_mut(&id).ok_or((StatusCode::NOT_FOUND, "unknown id".into()))?;
-    match entry {
-        Strategy::EpsilonGreedy(t) => {
-            t.bandit.update(req.arm as usize, req.reward);
-            t.tracker.update(req.reward);
+    let entry = reg.entry(&id, "update")?;
+    let mut entry = entry.lock().unwrap();
+    let arm = entry
+        .resolve(&req.arm)
+        .and_then(|arm| entry.try_update(arm, req.reward, req.context.as_deref()).map(|_| arm))
+        .map_err(|e| bad_request("update", &e.to_string()))?;
+    reg.invalidate_stats(&id);
+    drop(entry);
+    debug!(bandit_id = %id, op = "update", arm, reward = req.reward, "reward recorded");
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct ArmResp {
+    arm: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    mean: f64,
+    bonus: Option<f64>,
+    count: u64,
+}
+
+async fn arm_stats(
+    State(reg): State<BanditRegistry>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<ArmResp>>, (StatusCode, String)> {
+    let entry = reg.entry(&id, "arms")?;
+    let entry = entry.lock().unwrap();
+    let snapshot = entry.policy.snapshot();
+    let bonuses = entry.policy.bonuses();
+    let labels: Vec<Option<String>> = (0..snapshot.counts.len())
+        .map(|arm| entry.policy.arm_label(arm).map(String::from))
+        .collect();
+    drop(entry);
+
+    let arms = snapshot
+        .counts
+        .iter()
+        .zip(&snapshot.values)
+        .enumerate()
+        .map(|(arm, (&count, &mean))| ArmResp {
+            arm,
+            label: labels[arm].clone(),
+            mean,
+            bonus: bonuses.as_ref().map(|b| b[arm]),
+            count,
+        })
+        .collect();
+    Ok(Json(arms))
+}
+
+async fn add_arm(
+    State(reg): State<BanditRegistry>,
+    Path(id): Path<String>,
+    Json(req): Json<AddArmReq>,
+) -> Result<Json<SelectResp>, (StatusCode, String)> {
+    let entry = reg.entry(&id, "add_arm")?;
+    let mut entry = entry.lock().unwrap();
+    if entry.budget.is_some() {
+        return Err(bad_request("add_arm", "adding arms is not supported for budgeted bandits"));
+    }
+    let Some(added) = entry.policy.add_arm(req.label) else {
+        let msg = format!("adding arms is not supported for {}", entry.policy.name());
+        return Err(bad_request("add_arm", &msg));
+    };
+    let arm = added.map_err(|e| bad_request("add_arm", &e.to_string()))? as u32;
+    let window = entry.activity[0].rewards.window();
+    entry.activity.push(ArmActivity::new(window));
+    let label = entry.policy.arm_label(arm as usize).map(String::from);
+    reg.invalidate_stats(&id);
+    drop(entry);
+    info!(bandit_id = %id, op = "add_arm", arm, "arm added");
+    Ok(Json(SelectResp { arm, label }))
+}
+
+async fn remove_arm(
+    State(reg): State<BanditRegistry>,
+    Path((id, arm)): Path<(String, usize)>,
+) -> Result<(), (StatusCode, String)> {
+    let entry = reg.entry(&id, "remove_arm")?;
+    let mut entry = entry.lock().unwrap();
+    let num_arms = entry.policy.counts().len();
+    if arm >= num_arms {
+        return Err(bad_request("remove_arm", "arm out of range"));
+    }
+    if num_arms == 1 {
+        return Err(bad_request("remove_arm", "cannot remove the last arm"));
+    }
+    if entry.budget.is_some() {
+        let msg = "removing arms is not supported for budgeted bandits";
+        return Err(bad_request("remove_arm", msg));
+    }
+    if !entry.policy.remove_arm(arm) {
+        let msg = format!("removing arms is not supported for {}", entry.policy.name());
+        return Err(bad_request("remove_arm", &msg));
+    }
+    entry.activity.remove(arm);
+    reg.invalidate_stats(&id);
+    drop(entry);
+    info!(bandit_id = %id, op = "remove_arm", arm, "arm removed");
+    Ok(())
+}
+
+async fn update_batch(
+    State(reg): State<BanditRegistry>,
+    Path(id): Path<String>,
+    Json(req): Json<BatchUpdateReq>,
+) -> Result<Json<BatchUpdateResp>, (StatusCode, String)> {
+    let entry = reg.entry(&id, "update_batch")?;
+    let mut entry = entry.lock().unwrap();
+    // Whether an update is valid can depend on the updates before it (e.g. a
+    // posterior overflowing), so apply the batch to a copy and keep it only
+    // if every update succeeds.
+    let mut staged = entry.clone();
+    for (i, u) in req.updates.iter().enumerate() {
+        staged
+            .resolve(&u.arm)
+            .and_then(|arm| staged.try_update(arm, u.reward, u.context.as_deref()))
+            .map_err(|e| bad_request("update_batch", &format!("update {}: {}", i, e)))?;
+    }
+    *entry = staged;
+    reg.invalidate_stats(&id);
+    drop(entry);
+    let applied = req.updates.len();
+    debug!(bandit_id = %id, op = "update_batch", applied, "rewards recorded");
+    Ok(Json(BatchUpdateResp { applied }))
+}
+
+async fn clone_bandit(
+    State(reg): State<BanditRegistry>,
+    Path(id): Path<String>,
+) -> Result<Json<CreateResp>, (StatusCode, String)> {
+    let entry = reg.entry(&id, "clone")?;
+    let entry = entry.lock().unwrap();
+    let copy = BanditEntry {
+        policy: entry.policy.fork(rand::random()),
+        tracker: entry.tracker.clone(),
+        normalization: entry.normalization.clone(),
+        activity: entry.activity.clone(),
+        budget: entry.budget.clone(),
+    };
+    drop(entry);
+
+    let new_id = Uuid::new_v4().to_string();
+    reg.insert(new_id.clone(), copy);
+    info!(bandit_id = %id, op = "clone", new_id = %new_id, "bandit cloned");
+    Ok(Json(CreateResp { id: new_id }))
+}
+
+/// Checkpoint of a registered bandit, as produced by `/export` and accepted by `/import`.
+#[derive(Serialize, Deserialize)]
+struct BanditExport {
+    strategy: String,
+    state: serde_json::Value,
+    /// Recent-reward tracker backing `/stats`, for bandits that keep one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tracker: Option<serde_json::Value>,
+    /// Per-arm activity backing `/stats`; fresh if absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    activity: Option<Vec<ArmActivity>>,
+    /// Costs and remaining budget of budgeted bandits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    budget: Option<Budget>,
+    /// Reward normalizer of bandits created with `normalize_window`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    normalization: Option<NormalizationExport>,
+}
+
+fn state_of<T: Serialize>(value: &T) -> serde_json::Value {
+    serde_json::to_value(value).expect("bandit state is always serializable")
+}
+
+impl BanditExport {
+    /// Captures `entry`.
+    fn of(entry: &BanditEntry) -> Self {
+        BanditExport {
+            strategy: entry.policy.name().into(),
+            state: serde_json::from_str(&entry.policy.to_json())
+                .expect("bandit state is always valid JSON"),
+            tracker: entry.tracker.as_ref().map(state_of),
+            activity: Some(entry.activity.clone()),
+            budget: entry.budget.clone(),
+            normalization: entry.normalization.as_ref().map(Normalization::export),
         }
-        Strategy::Ucb1(b) => b.update(req.arm as usize, req.reward),
     }
+
+    /// Rebuilds the bandit this document describes.
+    fn into_entry(self) -> Result<BanditEntry, &'static str> {
+        let policy = import_policy(&self.strategy, &self.state.to_string())?;
+        let tracker = self
+            .tracker
+            .map(|t| RewardTracker::from_json(&t.to_string()).map_err(|_| "invalid bandit state"))
+            .transpose()?;
+        let normalization = self
+            .normalization
+            .map(NormalizationExport::into_normalization)
+            .transpose()?;
+        let mut entry = BanditEntry::new(policy, normalization, DEFAULT_TRACKER_WINDOW);
+        entry.tracker = tracker;
+        if let Some(activity) = self.activity {
+            if activity.len() != entry.activity.len() {
+                return Err("activity must have one entry per arm");
+            }
+            entry.activity = activity;
+        }
+        if let Some(budget) = self.budget {
+            budget.validate()?;
+            if budget.costs().len() != entry.activity.len() {
+                return Err("budget must have one cost per arm");
+            }
+            if entry.policy.as_bandit_mut().is_none() {
+                return Err("budgets are only supported for context-free bandits");
+            }
+            entry.budget = Some(budget);
+        }
+        Ok(entry)
+    }
+}
+
+async fn export_bandit(
+    State(reg): State<BanditRegistry>,
+    Path(id): Path<String>,
+) -> Result<Json<BanditExport>, (StatusCode, String)> {
+    let entry = reg.entry(&id, "export")?;
+    let export = BanditExport::of(&entry.lock().unwrap());
+    info!(bandit_id = %id, op = "export", "bandit exported");
+    Ok(Json(export))
+}
+
+#[derive(Deserialize)]
+struct SnapshotReq {
+    path: PathBuf,
+}
+
+async fn snapshot_registry(
+    State(reg): State<BanditRegistry>,
+    Json(req): Json<SnapshotReq>,
+) -> Result<(), (StatusCode, String)> {
+    let Some(dir) = &reg.snapshot_dir else {
+        return Err(bad_request("snapshot", "snapshots are disabled"));
+    };
+    // Only plain relative paths, so clients cannot write outside `dir`.
+    let relative = req.path.components().all(|c| matches!(c, Component::Normal(_)));
+    if !relative || req.path.file_name().is_none() {
+        return Err(bad_request("snapshot", "invalid path"));
+    }
+    let path = dir.join(&req.path);
+    let target = path.clone();
+    tokio::task::spawn_blocking(move || reg.save_to_path(&target))
+        .await
+        .map_err(io::Error::other)
+        .and_then(|saved| saved)
+        .map_err(|e| {
+            warn!(op = "snapshot", error = %e, "snapshot failed");
+            (StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+        })?;
+    info!(op = "snapshot", path = %path.display(), "registry saved");
     Ok(())
 }
 
-#[derive(serde::Serialize)]
+async fn import_bandit(
+    State(reg): State<BanditRegistry>,
+    Json(req): Json<BanditExport>,
+) -> Result<Json<CreateResp>, (StatusCode, String)> {
+    let strategy = req.strategy.clone();
+    let entry = req.into_entry().map_err(|e| bad_request("import", e))?;
+
+    let id = Uuid::new_v4().to_string();
+    reg.insert(id.clone(), entry);
+    info!(bandit_id = %id, op = "import", strategy = %strategy, "bandit imported");
+    Ok(Json(CreateResp { id }))
+}
+
+#[derive(Clone, serde::Serialize)]
 struct StatsResp {
     mean: f64,
     min: f64,
     max: f64,
     count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    epsilon: Option<f64>,
+    /// Most recent normalized reward, for normalized bandits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    normalized: Option<f64>,
+    /// Budget accounting, for budgeted bandits.
+    #[serde(flatten)]
+    budget: Option<BudgetStats>,
+    arms: Vec<ArmSummary>,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct BudgetStats {
+    /// Budget left to spend.
+    budget_remaining: f64,
+    /// How far reported rewards exceeded the budget (see [`Budget::overspent`]).
+    budget_overspent: f64,
+}
+
+impl BudgetStats {
+    fn of(budget: &Budget) -> Self {
+        Self {
+            budget_remaining: budget.remaining(),
+            budget_overspent: budget.overspent(),
+        }
+    }
+}
+
+#[derive(Clone, serde::Serialize)]
+struct ArmSummary {
+    arm: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    mean: f64,
+    count: u64,
+    /// `None` until the arm has a reward.
+    recent_min: Option<f64>,
+    recent_max: Option<f64>,
+    last_selected: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct StatsQuery {
+    /// `raw` (default) or `normalized`.
+    space: Option<String>,
 }
 
 async fn get_stats(
-    State(reg): State<Registry>,
+    State(reg): State<BanditRegistry>,
     Path(id): Path<String>,
+    Query(query): Query<StatsQuery>,
 ) -> Result<Json<StatsResp>, (StatusCode, String)> {
-    let map = reg.map.lock().unwrap();
-    let entry = map.get(&id).ok_or((StatusCode::NOT_FOUND, "unknown id".into()))?;
-    match entry {
-        Strategy::EpsilonGreedy(t) => Ok(Json(StatsResp {
-            mean: t.tracker.mean(),
-            min: t.tracker.min(),
-            max: t.tracker.max(),
-            count: t.tracker.count(),
-        })),
-        Strategy::Ucb1(b) => {
-            let avg = b.values().iter().sum::<f64>() / b.values().len() as f64;
-            Ok(Json(StatsResp {
-                mean: avg,
-                min: b.values().iter().fold(f64::INFINITY, |a, &x| a.min(x)),
-                max: b.values().iter().fold(f64::NEG_INFINITY, |a, &x| a.max(x)),
-                count: b.counts().iter().sum::<u64>() as usize,
-            }))
+    match query.space.as_deref() {
+        None | Some("raw") => {}
+        // Normalized-space stats are never cached.
+        Some("normalized") => return normalized_stats(&reg, &id).map(Json),
+        Some(_) => return Err(bad_request("stats", "unsupported stats space")),
+    }
+
+    if let Some(ttl) = reg.stats_ttl {
+        if let Some((at, stats)) = reg.stats_cache.lock().unwrap().get(&id) {
+            if at.elapsed() < ttl {
+                debug!(bandit_id = %id, op = "stats", "served cached stats");
+                return Ok(Json(stats.clone()));
+            }
         }
     }
+    let entry = reg.entry(&id, "stats")?;
+    let entry = entry.lock().unwrap();
+    let stats = compute_stats(&entry);
+    // Cache while still holding the bandit's lock, so a concurrent change
+    // cannot invalidate the cache before this (then stale) insert.
+    if reg.stats_ttl.is_some() {
+        reg.stats_cache
+            .lock()
+            .unwrap()
+            .insert(id, (Instant::now(), stats.clone()));
+    }
+    drop(entry);
+    Ok(Json(stats))
+}
+
+fn compute_stats(entry: &BanditEntry) -> StatsResp {
+    let epsilon = entry.policy.current_epsilon();
+    let arms = entry.arm_summaries();
+    let budget = entry.budget.as_ref().map(BudgetStats::of);
+    // The strategy only sees normalized rewards, so report the raw tracker.
+    if let Some(n) = &entry.normalization {
+        return StatsResp {
+            normalized: n.last(),
+            budget,
+            ..tracker_stats(&n.raw, epsilon, arms)
+        };
+    }
+    if let Some(tracker) = &entry.tracker {
+        return StatsResp {
+            budget,
+            ..tracker_stats(tracker, epsilon, arms)
+        };
+    }
+
+    let snapshot = entry.policy.snapshot();
+    let values = &snapshot.values;
+    StatsResp {
+        mean: values.iter().sum::<f64>() / values.len() as f64,
+        min: values.iter().fold(f64::INFINITY, |a, &x| a.min(x)),
+        max: values.iter().fold(f64::NEG_INFINITY, |a, &x| a.max(x)),
+        count: snapshot.counts.iter().sum::<u64>() as usize,
+        epsilon: None,
+        normalized: None,
+        budget,
+        arms,
+    }
+}
+
+fn normalized_stats(reg: &BanditRegistry, id: &str) -> Result<StatsResp, (StatusCode, String)> {
+    let entry = reg.entry(id, "stats")?;
+    let entry = entry.lock().unwrap();
+    let n = entry
+        .normalization
+        .as_ref()
+        .ok_or_else(|| bad_request("stats", "bandit has no normalizer"))?;
+    let epsilon = entry.policy.current_epsilon();
+    Ok(StatsResp {
+        normalized: n.last(),
+        budget: entry.budget.as_ref().map(BudgetStats::of),
+        ..tracker_stats(&n.normalized, epsilon, entry.arm_summaries())
+    })
+}
+
+fn tracker_stats(
+    tracker: &RewardTracker,
+    epsilon: Option<f64>,
+    arms: Vec<ArmSummary>,
+) -> StatsResp {
+    StatsResp {
+        mean: tracker.mean(),
+        min: tracker.min(),
+        max: tracker.max(),
+        count: tracker.count(),
+        epsilon,
+        normalized: None,
+        budget: None,
+        arms,
+    }
+}
+
+/// Construction options for the bandit router.
+#[derive(Debug, Clone, Default)]
+pub struct BanditApiConfig {
+    /// Wrap every response as `{ "data": ..., "error": ... }`.
+    pub envelope: bool,
+    /// Serve `/stats` from a per-bandit cache for up to this long; `None` disables caching.
+    pub stats_ttl: Option<Duration>,
+    /// Directory `/snapshot` resolves its relative `path` against; `None`
+    /// disables snapshots over HTTP.
+    pub snapshot_dir: Option<PathBuf>,
 }
 
 /// Build the Axum router for bandit endpoints
 pub fn routes() -> Router {
-    let reg = Registry::default();
-    Router::new()
-        .route("/", post(create_bandit))
-        .route("/:id/select", get(select_arm))
+    routes_with_config(BanditApiConfig::default())
+}
+
+/// Build the bandit router with the given options.
+pub fn routes_with_config(config: BanditApiConfig) -> Router {
+    routes_with_registry(config, BanditRegistry::default())
+}
+
+/// Build the bandit router on top of an existing (possibly shared) registry.
+pub fn routes_with_registry(config: BanditApiConfig, registry: BanditRegistry) -> Router {
+    let reg = BanditRegistry {
+        stats_ttl: config.stats_ttl,
+        snapshot_dir: config.snapshot_dir,
+        ..registry
+    };
+    let router = Router::new()
+        .route("/", post(create_bandit).get(list_bandits))
+        .route("/:id", delete(delete_bandit))
+        .route("/:id/select", get(select_arm).post(select_arm_with_context))
         .route("/:id/update", post(update_reward))
+        .route("/:id/update_batch", post(update_batch))
+        .route("/:id/reset", post(reset_bandit))
         .route("/:id/stats", get(get_stats))
-        .with_state(reg)
+        .route("/:id/arms", get(arm_stats).post(add_arm))
+        .route("/:id/arms/:arm", delete(remove_arm))
+        .route("/:id/clone", post(clone_bandit))
+        .route("/:id/export", get(export_bandit))
+        .route("/import", post(import_bandit))
+        .route("/snapshot", post(snapshot_registry))
+        .layer(TraceLayer::new_for_http())
+        .with_state(reg);
+
+    if config.envelope {
+        router.layer(middleware::map_response(envelope::wrap))
+    } else {
+        router
+    }
 }
 
 /// Run the REST server on `addr` (e.g., "127.0.0.1:8080").
 pub async fn start_server(addr: &str) -> Result<(), Box<dyn std::error::Error>> {
+    start_server_with_shutdown(addr, std::future::pending()).await
+}
+
+/// Run the REST server on `addr` until `shutdown` completes, then finish
+/// in-flight requests and return.
+pub async fn start_server_with_shutdown(
+    addr: &str,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), Box<dyn std::error::Error>> {
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, routes()).await?;
+    axum::serve(listener, routes())
+        .with_graceful_shutdown(shutdown)
+        .await?;
+    info!(op = "shutdown", "bandit server stopped");
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,181 @@
+//! REST API for dueling bandits, which learn from pairwise preferences
+//! ("A beat B") instead of scalar rewards.
+//!
+//! ## Endpoints
+//! - POST /duel               -> body: { "num_arms": usize, "alpha"?: f64, "seed"?: u64 },
+//!   returns { "id" }
+//! - GET  /duel/:id/select    -> returns { "first": usize, "second": usize }
+//! - POST /duel/:id/update    -> body: { "winner": usize, "loser": usize }, returns {}
+//! - GET  /duel/:id/stats     -> returns { "duels", "best_arm", "copeland_scores", "wins" }
+//!
+//! Agents are [`Rucb`] with exploration factor `alpha` (default 0.51, must
+//! exceed 0.5) and RNG `seed` (default 42). `wins[i][j]` counts how often arm
+//! `i` beat arm `j`. The win matrix grows with the square of `num_arms`, so
+//! creation is limited to [`MAX_ARMS`] arms.
+//!
+//! As in [`bandit_api`](super::bandit_api), the registry map sits behind an
+//! `RwLock` and each agent behind its own `Mutex`, so requests for different
+//! ids only contend on a brief read lock.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use tower_http::trace::TraceLayer;
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+use crate::bandit::dueling::Rucb;
+
+/// Exploration factor used when the request omits `alpha`.
+const DEFAULT_ALPHA: f64 = 0.51;
+
+/// Largest `num_arms` accepted at creation; the win matrix holds
+/// `MAX_ARMS²` counters.
+pub const MAX_ARMS: usize = 1024;
+
+/// A registered agent; locked independently of the registry map.
+type SharedAgent = Arc<Mutex<Rucb>>;
+
+#[derive(Clone, Default)]
+struct Registry {
+    map: Arc<RwLock<HashMap<String, SharedAgent>>>,
+}
+
+impl Registry {
+    /// Looks up an agent, holding the map read lock only for the lookup.
+    fn agent(&self, id: &str, op: &str) -> Result<SharedAgent, (StatusCode, String)> {
+        self.map
+            .read()
+            .unwrap()
+            .get(id)
+            .cloned()
+            .ok_or_else(|| unknown_id(id, op))
+    }
+}
+
+// ===== Request / Response DTOs =====
+
+#[derive(Deserialize)]
+struct CreateReq {
+    num_arms: usize,
+    alpha: Option<f64>,
+    seed: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct CreateResp {
+    id: String,
+}
+
+#[derive(Serialize)]
+struct DuelResp {
+    first: usize,
+    second: usize,
+}
+
+#[derive(Deserialize)]
+struct UpdateReq {
+    winner: usize,
+    loser: usize,
+}
+
+#[derive(Serialize)]
+struct StatsResp {
+    duels: u64,
+    best_arm: usize,
+    copeland_scores: Vec<usize>,
+    wins: Vec<Vec<u64>>,
+}
+
+/// Logs and builds the 404 returned for an unknown dueling bandit id.
+fn unknown_id(id: &str, op: &str) -> (StatusCode, String) {
+    warn!(duel_id = %id, op, "unknown dueling bandit id");
+    (StatusCode::NOT_FOUND, "unknown id".into())
+}
+
+// ===== Handlers =====
+
+async fn create_duel(
+    State(reg): State<Registry>,
+    Json(req): Json<CreateReq>,
+) -> Result<Json<CreateResp>, (StatusCode, String)> {
+    if req.num_arms == 0 || req.num_arms > MAX_ARMS {
+        return Err((StatusCode::BAD_REQUEST, "invalid number of arms".into()));
+    }
+    let alpha = req.alpha.unwrap_or(DEFAULT_ALPHA);
+    if !(alpha > 0.5 && alpha.is_finite()) {
+        return Err((StatusCode::BAD_REQUEST, "invalid alpha".into()));
+    }
+
+    let id = Uuid::new_v4().to_string();
+    let agent = Rucb::new(req.num_arms, alpha, req.seed.unwrap_or(42));
+    reg.map
+        .write()
+        .unwrap()
+        .insert(id.clone(), Arc::new(Mutex::new(agent)));
+    info!(duel_id = %id, op = "create", num_arms = req.num_arms, "dueling bandit created");
+    Ok(Json(CreateResp { id }))
+}
+
+async fn select_duel(
+    State(reg): State<Registry>,
+    Path(id): Path<String>,
+) -> Result<Json<DuelResp>, (StatusCode, String)> {
+    let agent = reg.agent(&id, "select")?;
+    let (first, second) = agent.lock().unwrap().select_duel();
+    debug!(duel_id = %id, op = "select", first, second, "duel selected");
+    Ok(Json(DuelResp { first, second }))
+}
+
+async fn update_duel(
+    State(reg): State<Registry>,
+    Path(id): Path<String>,
+    Json(req): Json<UpdateReq>,
+) -> Result<(), (StatusCode, String)> {
+    reg.agent(&id, "update")?
+        .lock()
+        .unwrap()
+        .try_update_duel(req.winner, req.loser)
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    debug!(duel_id = %id, op = "update", winner = req.winner, loser = req.loser, "duel recorded");
+    Ok(())
+}
+
+async fn duel_stats(
+    State(reg): State<Registry>,
+    Path(id): Path<String>,
+) -> Result<Json<StatsResp>, (StatusCode, String)> {
+    let agent = reg.agent(&id, "stats")?;
+    let agent = agent.lock().unwrap();
+    let n = agent.num_arms();
+    Ok(Json(StatsResp {
+        duels: agent.duels(),
+        best_arm: agent.best_arm(),
+        copeland_scores: agent.copeland_scores(),
+        wins: (0..n)
+            .map(|i| (0..n).map(|j| agent.wins(i, j)).collect())
+            .collect(),
+    }))
+}
+
+// ===== Router =====
+
+/// Build the Axum router for dueling bandit endpoints.
+pub fn routes() -> Router {
+    Router::new()
+        .route("/", post(create_duel))
+        .route("/:id/select", get(select_duel))
+        .route("/:id/update", post(update_duel))
+        .route("/:id/stats", get(duel_stats))
+        .layer(TraceLayer::new_for_http())
+        .with_state(Registry::default())
+}
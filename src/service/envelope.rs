@@ -0,0 +1,39 @@
+//! Optional response envelope for the REST services.
+//!
+//! Some client frameworks expect every response shaped as
+//! `{ "data": <payload>, "error": null }` on success and
+//! `{ "data": null, "error": "<message>" }` on failure. [`wrap`] is an
+//! Axum response mapper that rewrites handler output into that shape while
+//! keeping the original status code.
+
+use axum::{
+    body::{to_bytes, Body},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::{json, Value};
+
+/// Wraps a handler response in the `{ data, error }` envelope.
+///
+/// Successful JSON bodies go under `data` (`null` for empty bodies); error
+/// bodies are treated as plain-text messages and go under `error`.
+pub async fn wrap(response: Response) -> Response {
+    let (mut parts, body) = response.into_parts();
+    let bytes = match to_bytes(body, usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => return Response::from_parts(parts, Body::from(e.to_string())),
+    };
+
+    let envelope = if parts.status.is_success() {
+        let data = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+        json!({ "data": data, "error": null })
+    } else {
+        json!({ "data": null, "error": String::from_utf8_lossy(&bytes) })
+    };
+
+    parts.headers.remove(header::CONTENT_TYPE);
+    parts.headers.remove(header::CONTENT_LENGTH);
+    let (status, headers) = (parts.status, parts.headers);
+    (status, headers, Json(envelope)).into_response()
+}
@@ -0,0 +1,61 @@
+//! Prometheus scrape target for the bandit registry.
+//!
+//! ## Endpoints
+//! - GET /metrics/prometheus -> text exposition format (version 0.0.4)
+//!
+//! For every registered bandit and arm it reports:
+//! - `bandit_arm_count{id,arm}`: times the arm has been updated (gauge)
+//! - `bandit_arm_value{id,arm}`: the arm's estimated mean reward (gauge)
+//! - `bandit_pull_total{id}`: updates across all of the bandit's arms (counter)
+//!
+//! The router reads the registry in [`AppState`], so build the bandit router
+//! with [`bandit_api::routes_with_registry`](crate::service::bandit_api::routes_with_registry)
+//! on the same state.
+
+use std::fmt::Write;
+
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+use tower_http::trace::TraceLayer;
+
+use crate::service::AppState;
+
+/// Content type of the Prometheus text format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+async fn prometheus(State(state): State<AppState>) -> impl IntoResponse {
+    let snapshots = state.bandits.snapshots();
+    let mut out = String::new();
+
+    out.push_str("# HELP bandit_arm_count Number of updates recorded for an arm.\n");
+    out.push_str("# TYPE bandit_arm_count gauge\n");
+    for (id, snapshot) in &snapshots {
+        for (arm, count) in snapshot.counts.iter().enumerate() {
+            writeln!(out, "bandit_arm_count{{id=\"{id}\",arm=\"{arm}\"}} {count}").unwrap();
+        }
+    }
+
+    out.push_str("# HELP bandit_arm_value Estimated mean reward of an arm.\n");
+    out.push_str("# TYPE bandit_arm_value gauge\n");
+    for (id, snapshot) in &snapshots {
+        for (arm, value) in snapshot.values.iter().enumerate() {
+            writeln!(out, "bandit_arm_value{{id=\"{id}\",arm=\"{arm}\"}} {value}").unwrap();
+        }
+    }
+
+    out.push_str("# HELP bandit_pull_total Updates recorded across all arms of a bandit.\n");
+    out.push_str("# TYPE bandit_pull_total counter\n");
+    for (id, snapshot) in &snapshots {
+        let total: u64 = snapshot.counts.iter().sum();
+        writeln!(out, "bandit_pull_total{{id=\"{id}\"}} {total}").unwrap();
+    }
+
+    ([(header::CONTENT_TYPE, CONTENT_TYPE)], out)
+}
+
+/// Build the metrics router over `state`.
+pub fn routes(state: AppState) -> Router {
+    Router::new()
+        .route("/prometheus", get(prometheus))
+        .layer(TraceLayer::new_for_http())
+        .with_state(state)
+}
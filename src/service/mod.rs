@@ -1,3 +1,30 @@
+//! REST services built on Axum.
+//!
+//! Handlers emit `tracing` events (bandit/job id, operation, outcome) and
+//! every router carries a `TraceLayer`. Nothing is printed unless a
+//! subscriber is installed, e.g. via [`init_tracing`].
+
 pub mod bandit_api;
+pub mod dueling_api;
+pub mod envelope;
+pub mod metrics_api;
 pub mod optimizer_api;
-pub mod training_api;
\ No newline at end of file
+pub mod training_api;
+
+/// State shared across routers, e.g. so `/metrics` sees the bandits
+/// registered through `/bandit`.
+#[derive(Clone, Default)]
+pub struct AppState {
+    pub bandits: bandit_api::BanditRegistry,
+}
+
+/// Installs a formatted `tracing` subscriber filtered by `RUST_LOG`
+/// (defaulting to `info`).
+///
+/// Opt-in: the library never installs a subscriber on its own, so tests
+/// stay quiet. Calling this more than once is a no-op.
+pub fn init_tracing() {
+    let filter = tracing_subscriber::EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info"));
+    let _ = tracing_subscriber::fmt().with_env_filter(filter).try_init();
+}
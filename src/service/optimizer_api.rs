@@ -4,10 +4,13 @@
 //! reporting observed rewards, and inspecting current optimizer state.
 //!
 //! ## Endpoints
-//! - POST /optimizer                -> create optimizer
+//! - POST /optimizer                -> body: { "x0": f64, "step": f64, "min_step": f64 }, returns { "id" }
 //! - GET  /optimizer/:id/suggest    -> returns { "x": f64 }
 //! - POST /optimizer/:id/observe    -> body: { "reward": f64 }
 //! - GET  /optimizer/:id/state      -> returns { "x": f64 }
+//!
+//! Optimizers use the default grow (1.1) and shrink (0.5) factors of
+//! [`HillClimber1D::new`].
 
 use std::{
     collections::HashMap,
@@ -21,37 +24,29 @@ use axum::{
     Json, Router,
 };
 use serde::{Deserialize, Serialize};
+use tower_http::trace::TraceLayer;
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
-use crate::{
-    bandit::{epsilon_greedy::EpsilonGreedy, ucb1::Ucb1},
-    metrics::reward_tracker::RewardTracker,
-};
+use crate::optimizer::{HillClimber1D, Optimizer};
 
-#[derive(Clone)]
-struct EpsilonGreedyTracked {
-    bandit: EpsilonGreedy,
-    tracker: RewardTracker,
-}
-
-#[derive(Clone)]
-enum Strategy {
-    EpsilonGreedy(EpsilonGreedyTracked),
-    Ucb1(Ucb1),
-}
+/// Step growth factor applied on improvement.
+const GROW: f64 = 1.1;
+/// Step shrink factor applied on failure.
+const SHRINK: f64 = 0.5;
 
 #[derive(Clone, Default)]
 struct Registry {
-    map: Arc<Mutex<HashMap<String, Strategy>>>,
+    map: Arc<Mutex<HashMap<String, HillClimber1D>>>,
 }
 
 // ===== Request / Response DTOs =====
 
 #[derive(Deserialize)]
 struct CreateReq {
-    strategy: String, // "epsilon_greedy" or "ucb1"
-    param: f64,       // epsilon or c
-    num_arms: usize,
+    x0: f64,
+    step: f64,
+    min_step: f64,
 }
 
 #[derive(Serialize)]
@@ -60,125 +55,71 @@ struct CreateResp {
 }
 
 #[derive(Serialize)]
-struct SelectResp {
-    arm: u32,
+struct ParamResp {
+    x: f64,
 }
 
 #[derive(Deserialize)]
-struct UpdateReq {
-    arm: u32,
+struct ObserveReq {
     reward: f64,
 }
 
-#[derive(Serialize)]
-struct StatsResp {
-    mean: f64,
-    min: f64,
-    max: f64,
-    count: usize,
+/// Logs and builds the 404 returned for an unknown optimizer id.
+fn unknown_id(id: &str, op: &str) -> (StatusCode, String) {
+    warn!(optimizer_id = %id, op, "unknown optimizer id");
+    (StatusCode::NOT_FOUND, "unknown id".into())
 }
 
 // ===== Handlers =====
 
-async fn create_bandit(
+async fn create_optimizer(
     State(reg): State<Registry>,
     Json(req): Json<CreateReq>,
 ) -> Result<Json<CreateResp>, (StatusCode, String)> {
-    if !(req.strategy == "epsilon_greedy" || req.strategy == "ucb1") {
-        return Err((StatusCode::BAD_REQUEST, "unsupported strategy".into()));
+    if !req.x0.is_finite() {
+        return Err((StatusCode::BAD_REQUEST, "invalid x0".into()));
     }
-    if req.num_arms == 0 {
-        return Err((StatusCode::BAD_REQUEST, "invalid number of arms".into()));
+    if !(req.step > 0.0 && req.min_step > 0.0) {
+        return Err((StatusCode::BAD_REQUEST, "invalid step size".into()));
     }
 
     let id = Uuid::new_v4().to_string();
-
-    let strategy = match req.strategy.as_str() {
-        "epsilon_greedy" => {
-            if !(0.0..=1.0).contains(&req.param) {
-                return Err((StatusCode::BAD_REQUEST, "invalid epsilon".into()));
-            }
-            let tracked = EpsilonGreedyTracked {
-                bandit: EpsilonGreedy::new(req.num_arms, req.param),
-                tracker: RewardTracker::new(50),
-            };
-            Strategy::EpsilonGreedy(tracked)
-        }
-        "ucb1" => {
-            if req.param < 0.0 {
-                return Err((StatusCode::BAD_REQUEST, "invalid exploration factor".into()));
-            }
-            Strategy::Ucb1(Ucb1::new(req.num_arms, req.param))
-        }
-        _ => unreachable!(),
-    };
-
-    reg.map.lock().unwrap().insert(id.clone(), strategy);
+    let opt = HillClimber1D::with_params(req.x0, req.step, req.min_step, GROW, SHRINK);
+    reg.map.lock().unwrap().insert(id.clone(), opt);
+    info!(optimizer_id = %id, op = "create", x0 = req.x0, "optimizer created");
     Ok(Json(CreateResp { id }))
 }
 
-async fn select_arm(
+async fn suggest(
     State(reg): State<Registry>,
     Path(id): Path<String>,
-) -> Result<Json<SelectResp>, (StatusCode, String)> {
+) -> Result<Json<ParamResp>, (StatusCode, String)> {
     let mut map = reg.map.lock().unwrap();
-    let entry = map
-        .get_mut(&id)
-        .ok_or((StatusCode::NOT_FOUND, "unknown id".into()))?;
-
-    let arm = match entry {
-        Strategy::EpsilonGreedy(t) => t.bandit.select_arm() as u32,
-        Strategy::Ucb1(b) => b.select_arm() as u32,
-    };
-    Ok(Json(SelectResp { arm }))
+    let opt = map.get_mut(&id).ok_or_else(|| unknown_id(&id, "suggest"))?;
+    let x = opt.suggest();
+    debug!(optimizer_id = %id, op = "suggest", x, "parameter suggested");
+    Ok(Json(ParamResp { x }))
 }
 
-async fn update_reward(
+async fn observe(
     State(reg): State<Registry>,
     Path(id): Path<String>,
-    Json(req): Json<UpdateReq>,
+    Json(req): Json<ObserveReq>,
 ) -> Result<(), (StatusCode, String)> {
     let mut map = reg.map.lock().unwrap();
-    let entry = map
-        .get_mut(&id)
-        .ok_or((StatusCode::NOT_FOUND, "unknown id".into()))?;
-
-    match entry {
-        Strategy::EpsilonGreedy(t) => {
-            t.bandit.update(req.arm as usize, req.reward);
-            t.tracker.update(req.reward);
-        }
-        Strategy::Ucb1(b) => b.update(req.arm as usize, req.reward),
-    }
+    let opt = map.get_mut(&id).ok_or_else(|| unknown_id(&id, "observe"))?;
+    opt.observe(req.reward);
+    debug!(optimizer_id = %id, op = "observe", reward = req.reward, "reward observed");
     Ok(())
 }
 
-async fn get_stats(
+async fn get_state(
     State(reg): State<Registry>,
     Path(id): Path<String>,
-) -> Result<Json<StatsResp>, (StatusCode, String)> {
+) -> Result<Json<ParamResp>, (StatusCode, String)> {
     let map = reg.map.lock().unwrap();
-    let entry = map
-        .get(&id)
-        .ok_or((StatusCode::NOT_FOUND, "unknown id".into()))?;
-
-    match entry {
-        Strategy::EpsilonGreedy(t) => Ok(Json(StatsResp {
-            mean: t.tracker.mean(),
-            min: t.tracker.min(),
-            max: t.tracker.max(),
-            count: t.tracker.count(),
-        })),
-        Strategy::Ucb1(b) => {
-            let avg = b.values().iter().sum::<f64>() / b.values().len() as f64;
-            Ok(Json(StatsResp {
-                mean: avg,
-                min: b.values().iter().fold(f64::INFINITY, |a, &x| a.min(x)),
-                max: b.values().iter().fold(f64::NEG_INFINITY, |a, &x| a.max(x)),
-                count: b.counts().iter().sum::<u64>() as usize,
-            }))
-        }
-    }
+    let opt = map.get(&id).ok_or_else(|| unknown_id(&id, "state"))?;
+    Ok(Json(ParamResp { x: opt.param() }))
 }
 
 // ===== Router =====
@@ -186,17 +127,18 @@ async fn get_stats(
 pub fn routes() -> Router {
     let reg = Registry::default();
     Router::new()
-        .route("/", post(create_bandit))
-        .route("/:id/select", get(select_arm))
-        .route("/:id/update", post(update_reward))
-        .route("/:id/stats", get(get_stats))
+        .route("/", post(create_optimizer))
+        .route("/:id/suggest", get(suggest))
+        .route("/:id/observe", post(observe))
+        .route("/:id/state", get(get_state))
+        .layer(TraceLayer::new_for_http())
         .with_state(reg)
 }
 
 /// Convenience function to run the API directly.
 pub async fn start_server(addr: &str) -> Result<(), Box<dyn std::error::Error>> {
     let listener = tokio::net::TcpListener::bind(addr).await?;
-    println!("🚀 Bandit API running at http://{addr}/bandit");
+    info!("optimizer API listening on http://{addr}/optimizer");
     axum::serve(listener, routes()).await?;
     Ok(())
-}
\ No newline at end of file
+}
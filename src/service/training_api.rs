@@ -2,29 +2,128 @@
 //!
 //! Endpoints:
 //! - POST /train/start   -> launch training job
-//! - POST /train/metrics -> record metrics (loss, reward)
-//! - POST /train/stop    -> terminate job
+//! - POST /train/metrics -> record a job's metrics { id, reward }, returns { anomaly, zscore }
+//!   (`"broadcast": true` records the reward for every job instead)
+//! - POST /train/stop    -> terminate job (it stays listed as `aborted`)
+//! - GET  /train         -> returns [{ id, status, error, reward_count }] for every job
+//! - GET  /train/:id/status -> returns { id, status, error, reward_count }
+//! - GET  /train/:id/stats  -> returns { mean, min, max, count }
+//!
+//! The subprocess's stdout is captured and read line by line; invalid UTF-8
+//! is replaced rather than ending the read. Lines of the form `reward=<f64>`,
+//! or JSON objects with a numeric `"reward"` field, feed the job's reward
+//! tracker directly, ignoring non-finite values; the key is configurable
+//! through [`TrainingApiConfig::reward_key`]. Stderr is passed through unchanged.
+//!
+//! A job whose subprocess cannot be spawned, or exits unsuccessfully, is
+//! marked `failed` with a descriptive error instead of silently appearing
+//! to run.
+//!
+//! Subprocesses are killed when their job is stopped. On server shutdown,
+//! [`TrainingShutdown::drain`] aborts every remaining job so none are orphaned;
+//! [`start_server_with_shutdown`] runs it once its shutdown future completes.
 
-use axum::{extract::State, routing::post, Json, Router};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    routing::{get, post},
+    Json, Router,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
+    future::Future,
     process::Stdio,
     sync::{Arc, Mutex},
+    time::Duration,
 };
-use tokio::{process::Command, task::JoinHandle};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::Command,
+    task::JoinHandle,
+};
+use tower_http::trace::TraceLayer;
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 use crate::metrics::reward_tracker::RewardTracker;
 
+/// Lifecycle state of a training job's subprocess.
+#[derive(Debug, Clone, PartialEq)]
+enum JobStatus {
+    Running,
+    Completed,
+    Failed(String),
+    /// Stopped through `/stop` while still running.
+    Aborted,
+}
+
+impl JobStatus {
+    fn label(&self) -> &'static str {
+        match self {
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed(_) => "failed",
+            JobStatus::Aborted => "aborted",
+        }
+    }
+}
+
 struct TrainingJob {
     id: String,
     handle: JoinHandle<()>,
-    tracker: RewardTracker,
+    /// Shared with the task that parses the subprocess's stdout.
+    tracker: Arc<Mutex<RewardTracker>>,
+    /// Updated by the background task when the subprocess exits.
+    status: Arc<Mutex<JobStatus>>,
 }
 
 #[derive(Clone, Default)]
 struct TrainingRegistry {
     jobs: Arc<Mutex<HashMap<String, TrainingJob>>>,
+    config: TrainingApiConfig,
+}
+
+/// Construction options for the training router.
+#[derive(Debug, Clone)]
+pub struct TrainingApiConfig {
+    /// Absolute z-score above which a posted reward is flagged as an anomaly.
+    pub anomaly_threshold: f64,
+    /// Key recognized in stdout lines, as `<key>=<f64>` or a JSON field.
+    pub reward_key: String,
+}
+
+impl Default for TrainingApiConfig {
+    fn default() -> Self {
+        Self {
+            anomaly_threshold: 3.0,
+            reward_key: "reward".into(),
+        }
+    }
+}
+
+/// Extracts a reward from one line of training output.
+///
+/// Accepts a JSON object with a numeric `key` field, or `key=<f64>` anywhere
+/// in the line (the key must not be the tail of a longer identifier).
+/// NaN and infinite values are rejected.
+fn parse_reward(line: &str, key: &str) -> Option<f64> {
+    let line = line.trim();
+    if line.starts_with('{') {
+        let value: serde_json::Value = serde_json::from_str(line).ok()?;
+        return value.get(key)?.as_f64().filter(|r| r.is_finite());
+    }
+    let needle = format!("{key}=");
+    line.match_indices(&needle).find_map(|(start, _)| {
+        let prev = line[..start].chars().next_back();
+        if prev.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            return None;
+        }
+        let rest = &line[start + needle.len()..];
+        let end = rest
+            .find(|c: char| c.is_whitespace() || c == ',' || c == ';')
+            .unwrap_or(rest.len());
+        rest[..end].parse().ok().filter(|r: &f64| r.is_finite())
+    })
 }
 
 #[derive(Deserialize)]
@@ -40,19 +139,57 @@ struct StartResp {
 async fn start_job(
     State(reg): State<TrainingRegistry>,
     Json(req): Json<StartReq>,
-) -> Json<StartResp> {
+) -> Result<Json<StartResp>, (StatusCode, String)> {
     let id = Uuid::new_v4().to_string();
-    let tracker = RewardTracker::new(50);
+    let tracker = Arc::new(Mutex::new(RewardTracker::new(50)));
+
+    // Spawn synchronously so immediate failures are reported to the caller.
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(&req.cmd)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| {
+            warn!(op = "start", error = %e, "failed to spawn training job");
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed to spawn training job: {e}"),
+            )
+        })?;
 
-    // Launch subprocess in background
+    // Parse rewards from stdout in background, then record how the subprocess ended.
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let status = Arc::new(Mutex::new(JobStatus::Running));
+    let task_status = status.clone();
+    let task_tracker = tracker.clone();
+    let reward_key = reg.config.reward_key.clone();
+    let job_id = id.clone();
     let handle = tokio::spawn(async move {
-        let _ = Command::new("sh")
-            .arg("-c")
-            .arg(req.cmd)
-            .stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .status()
-            .await;
+        let mut stdout = BufReader::new(stdout);
+        let mut line = Vec::new();
+        while matches!(stdout.read_until(b'\n', &mut line).await, Ok(n) if n > 0) {
+            if let Some(reward) = parse_reward(&String::from_utf8_lossy(&line), &reward_key) {
+                debug!(job_id = %job_id, reward, "reward parsed from training output");
+                task_tracker.lock().unwrap().update(reward);
+            }
+            line.clear();
+        }
+
+        let outcome = match child.wait().await {
+            Ok(exit) if exit.success() => JobStatus::Completed,
+            // POSIX shells exit with 127 when the command cannot be found.
+            Ok(exit) if exit.code() == Some(127) => {
+                JobStatus::Failed(format!("command not found: {}", req.cmd))
+            }
+            Ok(exit) => JobStatus::Failed(format!("training command failed ({exit})")),
+            Err(e) => JobStatus::Failed(format!("failed to wait for training command: {e}")),
+        };
+        if let JobStatus::Failed(error) = &outcome {
+            warn!(job_id = %job_id, error = %error, "training job failed");
+        }
+        *task_status.lock().unwrap() = outcome;
     });
 
     reg.jobs.lock().unwrap().insert(
@@ -61,24 +198,69 @@ async fn start_job(
             id: id.clone(),
             handle,
             tracker,
+            status,
         },
     );
 
-    Json(StartResp { id })
+    info!(job_id = %id, op = "start", "training job started");
+    Ok(Json(StartResp { id }))
 }
 
 #[derive(Deserialize)]
 struct MetricsReq {
+    /// Job the reward belongs to; required unless `broadcast` is set.
+    id: Option<String>,
     reward: f64,
+    /// Record the reward for every job instead of a single one.
+    broadcast: Option<bool>,
+}
+
+#[derive(Serialize)]
+struct MetricsResp {
+    anomaly: bool,
+    zscore: f64,
 }
 
+/// Records the reward for the job `id` and reports its z-score against that
+/// job's history.
+///
+/// With `broadcast`, the reward is recorded for every job and the z-score
+/// with the largest magnitude is returned.
 async fn update_metrics(
     State(reg): State<TrainingRegistry>,
     Json(req): Json<MetricsReq>,
-) {
-    for job in reg.jobs.lock().unwrap().values_mut() {
-        job.tracker.update(req.reward);
+) -> Result<Json<MetricsResp>, (StatusCode, String)> {
+    let jobs = reg.jobs.lock().unwrap();
+    let targets: Vec<&TrainingJob> = match (req.broadcast, &req.id) {
+        (Some(true), _) => jobs.values().collect(),
+        (_, Some(id)) => {
+            let job = jobs.get(id).ok_or_else(|| {
+                warn!(job_id = %id, op = "metrics", "unknown job id");
+                (StatusCode::NOT_FOUND, "unknown id".to_string())
+            })?;
+            vec![job]
+        }
+        (_, None) => return Err((StatusCode::BAD_REQUEST, "missing job id".into())),
+    };
+
+    let mut zscore: f64 = 0.0;
+    for job in targets {
+        let mut tracker = job.tracker.lock().unwrap();
+        tracker.update(req.reward);
+        let z = tracker.latest_zscore();
+        if z.abs() > zscore.abs() {
+            zscore = z;
+        }
+    }
+    drop(jobs);
+
+    let anomaly = zscore.abs() > reg.config.anomaly_threshold;
+    if anomaly {
+        warn!(op = "metrics", reward = req.reward, zscore, "anomalous training metric");
+    } else {
+        debug!(op = "metrics", reward = req.reward, "training metrics recorded");
     }
+    Ok(Json(MetricsResp { anomaly, zscore }))
 }
 
 #[derive(Deserialize)]
@@ -87,17 +269,180 @@ struct StopReq {
 }
 
 async fn stop_job(State(reg): State<TrainingRegistry>, Json(req): Json<StopReq>) {
-    if let Some(job) = reg.jobs.lock().unwrap().remove(&req.id) {
+    if let Some(job) = reg.jobs.lock().unwrap().get(&req.id) {
+        // Aborting drops the subprocess (`kill_on_drop`) before the task can
+        // record an outcome, so the status has to be set here.
         job.handle.abort();
-        println!("🛑 Training job {} stopped", req.id);
+        let mut status = job.status.lock().unwrap();
+        if *status == JobStatus::Running {
+            *status = JobStatus::Aborted;
+        }
+        info!(job_id = %job.id, op = "stop", "training job stopped");
+    }
+}
+
+#[derive(Serialize)]
+struct StatusResp {
+    id: String,
+    status: &'static str,
+    error: Option<String>,
+    /// Rewards recorded for the job, from stdout and `/metrics`.
+    reward_count: u64,
+}
+
+impl StatusResp {
+    fn of(job: &TrainingJob) -> Self {
+        let status = job.status.lock().unwrap().clone();
+        let error = match &status {
+            JobStatus::Failed(e) => Some(e.clone()),
+            _ => None,
+        };
+        StatusResp {
+            id: job.id.clone(),
+            status: status.label(),
+            error,
+            reward_count: job.tracker.lock().unwrap().total_updates(),
+        }
+    }
+}
+
+async fn job_status(
+    State(reg): State<TrainingRegistry>,
+    Path(id): Path<String>,
+) -> Result<Json<StatusResp>, (StatusCode, String)> {
+    let jobs = reg.jobs.lock().unwrap();
+    let job = jobs
+        .get(&id)
+        .ok_or((StatusCode::NOT_FOUND, "unknown id".into()))?;
+    Ok(Json(StatusResp::of(job)))
+}
+
+async fn list_jobs(State(reg): State<TrainingRegistry>) -> Json<Vec<StatusResp>> {
+    let jobs = reg.jobs.lock().unwrap();
+    let mut statuses: Vec<StatusResp> = jobs.values().map(StatusResp::of).collect();
+    statuses.sort_by(|a, b| a.id.cmp(&b.id));
+    Json(statuses)
+}
+
+#[derive(Serialize)]
+struct StatsResp {
+    mean: f64,
+    min: f64,
+    max: f64,
+    count: usize,
+}
+
+async fn job_stats(
+    State(reg): State<TrainingRegistry>,
+    Path(id): Path<String>,
+) -> Result<Json<StatsResp>, (StatusCode, String)> {
+    let jobs = reg.jobs.lock().unwrap();
+    let job = jobs
+        .get(&id)
+        .ok_or((StatusCode::NOT_FOUND, "unknown id".into()))?;
+    let t = job.tracker.lock().unwrap();
+    Ok(Json(StatsResp {
+        mean: t.mean(),
+        min: t.min(),
+        max: t.max(),
+        count: t.count(),
+    }))
+}
+
+/// How long [`TrainingShutdown::drain`] waits, in total, for aborted jobs to finish.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Handle for stopping every job in a training router's registry.
+#[derive(Clone)]
+pub struct TrainingShutdown {
+    reg: TrainingRegistry,
+}
+
+impl TrainingShutdown {
+    /// Aborts all running jobs, empties the registry, and waits briefly for
+    /// their tasks (and subprocesses) to terminate. Every job is aborted
+    /// before any is awaited, and all of them share one deadline.
+    ///
+    /// Returns the number of jobs that terminated within the wait.
+    pub async fn drain(&self) -> usize {
+        let jobs: Vec<TrainingJob> = self
+            .reg
+            .jobs
+            .lock()
+            .unwrap()
+            .drain()
+            .map(|(_, j)| j)
+            .collect();
+        for job in &jobs {
+            job.handle.abort();
+        }
+        let deadline = tokio::time::Instant::now() + DRAIN_TIMEOUT;
+        let mut terminated = 0;
+        for job in jobs {
+            if tokio::time::timeout_at(deadline, job.handle).await.is_ok() {
+                terminated += 1;
+            } else {
+                warn!(
+                    job_id = %job.id,
+                    op = "shutdown",
+                    "training job did not terminate in time"
+                );
+            }
+        }
+        info!(op = "shutdown", terminated, "training jobs drained");
+        terminated
     }
 }
 
 pub fn routes() -> Router {
-    let reg = TrainingRegistry::default();
-    Router::new()
+    routes_with_config(TrainingApiConfig::default())
+}
+
+/// Build the training router with the given options.
+pub fn routes_with_config(config: TrainingApiConfig) -> Router {
+    routes_with_shutdown(config).0
+}
+
+/// Build the training router together with a handle that drains its jobs,
+/// typically awaited from the server's graceful-shutdown future.
+pub fn routes_with_shutdown(config: TrainingApiConfig) -> (Router, TrainingShutdown) {
+    let reg = TrainingRegistry {
+        config,
+        ..Default::default()
+    };
+    let shutdown = TrainingShutdown { reg: reg.clone() };
+    let router = Router::new()
+        .route("/", get(list_jobs))
         .route("/start", post(start_job))
         .route("/metrics", post(update_metrics))
         .route("/stop", post(stop_job))
-        .with_state(reg)
-}
\ No newline at end of file
+        .route("/:id/status", get(job_status))
+        .route("/:id/stats", get(job_stats))
+        .layer(TraceLayer::new_for_http())
+        .with_state(reg);
+    (router, shutdown)
+}
+
+/// Run the training server on `addr` (e.g., "127.0.0.1:8081").
+pub async fn start_server(addr: &str) -> Result<(), Box<dyn std::error::Error>> {
+    start_server_with_shutdown(addr, std::future::pending()).await
+}
+
+/// Run the training server on `addr` until `shutdown` completes. Running
+/// jobs are then aborted and the registry cleared (see
+/// [`TrainingShutdown::drain`]) before in-flight requests finish.
+pub async fn start_server_with_shutdown(
+    addr: &str,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (router, jobs) = routes_with_shutdown(TrainingApiConfig::default());
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async move {
+            shutdown.await;
+            jobs.drain().await;
+        })
+        .await?;
+    info!(op = "shutdown", "training server stopped");
+    Ok(())
+}
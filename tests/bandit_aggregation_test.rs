@@ -0,0 +1,39 @@
+use rustybrain::bandit::aggregation::{aggregate, AggregationPolicy};
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::bandit::ucb1::Ucb1;
+use approx::assert_relative_eq;
+
+#[test]
+fn test_per_sample_vs_mean_batch() {
+    let batch = [(0, 1.0), (0, 1.0), (0, 1.0)];
+
+    let mut per_sample = EpsilonGreedy::new(2, 0.0);
+    per_sample.update(0, 0.0);
+    per_sample.update_batch(&batch, AggregationPolicy::PerSample);
+
+    let mut mean = EpsilonGreedy::new(2, 0.0);
+    mean.update(0, 0.0);
+    mean.update_batch(&batch, AggregationPolicy::Mean);
+
+    // PerSample: (0 + 1 + 1 + 1) / 4; Mean: (0 + 1) / 2
+    assert_relative_eq!(per_sample.values()[0], 0.75, epsilon = 1e-12);
+    assert_relative_eq!(mean.values()[0], 0.5, epsilon = 1e-12);
+    assert_eq!(per_sample.counts()[0], 4);
+    assert_eq!(mean.counts()[0], 2);
+}
+
+#[test]
+fn test_max_batch_on_ucb1() {
+    let mut agent = Ucb1::new(2, 2.0);
+    agent.update_batch(&[(1, 0.2), (0, 0.4), (1, 0.9)], AggregationPolicy::Max);
+    assert_relative_eq!(agent.values()[1], 0.9, epsilon = 1e-12);
+    assert_relative_eq!(agent.values()[0], 0.4, epsilon = 1e-12);
+    assert_eq!(agent.counts(), &[1, 1]);
+}
+
+#[test]
+fn test_aggregate_preserves_first_seen_order() {
+    let batch = [(2, 1.0), (0, 4.0), (2, 3.0)];
+    assert_eq!(aggregate(&batch, AggregationPolicy::Mean), vec![(2, 2.0), (0, 4.0)]);
+    assert_eq!(aggregate(&batch, AggregationPolicy::PerSample), batch.to_vec());
+}
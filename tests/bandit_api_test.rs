@@ -6,6 +6,9 @@ use tower::ServiceExt; // for `oneshot`
 use rustybrain::service::bandit_api::routes;
 use serde_json::{json, Value};
 
+mod common;
+use common::{get_json, post_json};
+
 #[tokio::test]
 async fn rest_bandit_happy_path() {
     // Build the /bandit router
@@ -59,4 +62,1080 @@ async fn rest_bandit_not_found() {
 
     let resp = app.oneshot(req).await.unwrap();
     assert_eq!(resp.status(), StatusCode::NOT_FOUND);
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn rest_bandit_clone_diverges_from_original() {
+    let app = routes();
+
+    let (_, v) = post_json(&app, "/", json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2})).await;
+    let id = v["id"].as_str().unwrap().to_string();
+    for r in [1.0, 0.5, 0.75] {
+        let (status, _) = post_json(&app, &format!("/{}/update", id), json!({"arm": 0, "reward": r})).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, v) = post_json(&app, &format!("/{}/clone", id), json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    let clone_id = v["id"].as_str().unwrap().to_string();
+    assert_ne!(clone_id, id);
+
+    let (_, original_before) = get_json(&app, &format!("/{}/stats", id)).await;
+    let (_, clone_before) = get_json(&app, &format!("/{}/stats", clone_id)).await;
+    assert_eq!(original_before, clone_before, "clone should start from the same state");
+
+    let (status, _) = post_json(&app, &format!("/{}/update", clone_id), json!({"arm": 1, "reward": 10.0})).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (_, original_after) = get_json(&app, &format!("/{}/stats", id)).await;
+    let (_, clone_after) = get_json(&app, &format!("/{}/stats", clone_id)).await;
+    assert_eq!(original_after, original_before);
+    assert_eq!(clone_after["count"], 4);
+    assert_eq!(clone_after["max"], 10.0);
+}
+
+#[tokio::test]
+async fn rest_bandit_clone_unknown_id() {
+    let app = routes();
+    let (status, _) = post_json(&app, "/does-not-exist/clone", json!({})).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+/// Runs `steps` select/update rounds with fixed per-arm rewards and returns pull counts.
+async fn pull_counts(app: &axum::Router, id: &str, rewards: &[f64], steps: usize) -> Vec<usize> {
+    let mut counts = vec![0; rewards.len()];
+    for _ in 0..steps {
+        let (_, v) = get_json(app, &format!("/{}/select", id)).await;
+        let arm = v["arm"].as_u64().unwrap() as usize;
+        counts[arm] += 1;
+        post_json(app, &format!("/{}/update", id), json!({"arm": arm, "reward": rewards[arm]})).await;
+    }
+    counts
+}
+
+#[tokio::test]
+async fn rest_ucb1_reward_range_scales_exploration() {
+    let app = routes();
+    let rewards = [90.0, 80.0, 70.0];
+
+    let (status, v) = post_json(
+        &app,
+        "/",
+        json!({"strategy":"ucb1","param":1.0,"num_arms":3,"reward_min":0.0,"reward_max":100.0}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let ranged = pull_counts(&app, v["id"].as_str().unwrap(), &rewards, 300).await;
+
+    let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":3})).await;
+    let unranged = pull_counts(&app, v["id"].as_str().unwrap(), &rewards, 300).await;
+
+    // Without the range the bonus is negligible next to the reward gaps.
+    assert_eq!(&unranged[1..], &[1, 1]);
+    assert!(ranged[1] > 5 && ranged[2] > 5, "ranged counts = {:?}", ranged);
+    assert!(ranged[0] > ranged[1] && ranged[1] > ranged[2]);
+}
+
+#[tokio::test]
+async fn rest_ucb1_rejects_invalid_reward_range() {
+    let app = routes();
+    for body in [
+        json!({"strategy":"ucb1","param":1.0,"num_arms":2,"reward_min":5.0,"reward_max":5.0}),
+        json!({"strategy":"ucb1","param":1.0,"num_arms":2,"reward_min":0.0}),
+    ] {
+        let (status, _) = post_json(&app, "/", body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn rest_bandit_envelope_mode_wraps_responses() {
+    use rustybrain::service::bandit_api::{routes_with_config, BanditApiConfig};
+
+    let app = routes_with_config(BanditApiConfig {
+        envelope: true,
+        ..Default::default()
+    });
+
+    let (status, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":2})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(v["error"].is_null());
+    let id = v["data"]["id"].as_str().unwrap().to_string();
+
+    let (status, v) = get_json(&app, &format!("/{}/select", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["data"]["arm"], 0);
+    assert!(v["error"].is_null());
+
+    // Handlers with no body still produce an envelope.
+    let (status, v) = post_json(&app, &format!("/{}/update", id), json!({"arm": 0, "reward": 1.0})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v, json!({"data": null, "error": null}));
+
+    let (status, v) = get_json(&app, "/missing/select").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert!(v["data"].is_null());
+    assert_eq!(v["error"], "unknown id");
+}
+
+#[tokio::test]
+async fn rest_bandit_raw_mode_is_default() {
+    let app = routes();
+    let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":2})).await;
+    assert!(v.get("data").is_none());
+    assert!(v["id"].is_string());
+}
+
+#[tokio::test]
+async fn rest_thompson_strategy_prefers_best_arm() {
+    let app = routes();
+    let (status, v) = post_json(&app, "/", json!({"strategy":"thompson","param":0.0,"num_arms":3})).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let counts = pull_counts(&app, &id, &[0.0, 0.2, 1.0], 200).await;
+    assert!(counts[2] > 150, "counts = {:?}", counts);
+
+    let (status, v) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["count"], 200);
+    assert_eq!(v["max"], 1.0);
+}
+
+#[tokio::test]
+async fn rest_gaussian_thompson_handles_continuous_rewards() {
+    let app = routes();
+    let body = json!({"strategy":"thompson_gaussian","param":0.0,"num_arms":3});
+    let (status, v) = post_json(&app, "/", body).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let counts = pull_counts(&app, &id, &[5.0, 20.0, 10.0], 200).await;
+    assert!(counts[1] > 150, "counts = {:?}", counts);
+
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert_eq!(arms[1]["mean"], 20.0);
+
+    let (status, export) = get_json(&app, &format!("/{}/export", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(export["strategy"], "thompson_gaussian");
+    let (status, _) = post_json(&app, "/import", export).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // A reward that would overflow the posterior is rejected and leaves the
+    // bandit usable.
+    let update = format!("/{}/update", id);
+    let (status, _) = post_json(&app, &update, json!({"arm": 0, "reward": 1e160})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get_json(&app, &format!("/{}/select", id)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn rest_budgeted_bandit() {
+    let app = routes();
+    let body = json!({
+        "strategy":"ucb1","param":1.0,"num_arms":2,"costs":[3.0,1.0],"budget":4.0
+    });
+    let (status, v) = post_json(&app, "/", body).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let update = format!("/{}/update", id);
+    post_json(&app, &update, json!({"arm": 0, "reward": 1.0})).await;
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(stats["budget_remaining"], 1.0);
+    let (_, v) = get_json(&app, &format!("/{}/select", id)).await;
+    assert_eq!(v["arm"], 1);
+    post_json(&app, &update, json!({"arm": 1, "reward": 0.0})).await;
+    let (status, _) = get_json(&app, &format!("/{}/select", id)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    post_json(&app, &format!("/{}/reset", id), json!({})).await;
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(stats["budget_remaining"], 4.0);
+    assert_eq!(stats["budget_overspent"], 0.0);
+    let (status, _) = post_json(&app, &format!("/{}/arms", id), json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Two outstanding selections of arm 0 cost more than the budget.
+    post_json(&app, &update, json!({"arm": 0, "reward": 1.0})).await;
+    post_json(&app, &update, json!({"arm": 0, "reward": 1.0})).await;
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(stats["budget_remaining"], 0.0);
+    assert_eq!(stats["budget_overspent"], 2.0);
+
+    let (_, mut export) = get_json(&app, &format!("/{}/export", id)).await;
+    export["budget"]["total"] = json!(-1.0);
+    let (status, _) = post_json(&app, "/import", export).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    for body in [
+        json!({"strategy":"ucb1","param":1.0,"num_arms":2,"budget":4.0}),
+        json!({"strategy":"ucb1","param":1.0,"num_arms":2,"costs":[1.0],"budget":4.0}),
+        json!({"strategy":"ucb1","param":1.0,"num_arms":1,"costs":[-1.0],"budget":4.0}),
+    ] {
+        let (status, _) = post_json(&app, "/", body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn rest_seed_gives_reproducible_but_distinct_agents() {
+    let app = routes();
+    let mut sequences = Vec::new();
+    for seed in [1, 2, 1] {
+        let (status, v) = post_json(
+            &app,
+            "/",
+            json!({"strategy":"epsilon_greedy","param":1.0,"num_arms":5,"seed":seed}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let id = v["id"].as_str().unwrap().to_string();
+        let mut arms = Vec::new();
+        for _ in 0..30 {
+            let (_, v) = get_json(&app, &format!("/{}/select", id)).await;
+            arms.push(v["arm"].as_u64().unwrap());
+        }
+        sequences.push(arms);
+    }
+    assert_ne!(sequences[0], sequences[1]);
+    assert_eq!(sequences[0], sequences[2]);
+}
+
+#[tokio::test]
+async fn rest_stats_report_current_epsilon() {
+    let app = routes();
+    let (_, v) = post_json(&app, "/", json!({"strategy":"epsilon_greedy","param":0.2,"num_arms":2})).await;
+    let (_, stats) = get_json(&app, &format!("/{}/stats", v["id"].as_str().unwrap())).await;
+    assert_eq!(stats["epsilon"], 0.2);
+
+    let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":2})).await;
+    let (_, stats) = get_json(&app, &format!("/{}/stats", v["id"].as_str().unwrap())).await;
+    assert!(stats.get("epsilon").is_none());
+}
+
+#[tokio::test]
+async fn rest_stats_cache_serves_snapshot_until_update() {
+    use rustybrain::service::bandit_api::{routes_with_config, BanditApiConfig};
+    use std::time::Duration;
+
+    let app = routes_with_config(BanditApiConfig {
+        stats_ttl: Some(Duration::from_secs(60)),
+        ..Default::default()
+    });
+    let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":2})).await;
+    let id = v["id"].as_str().unwrap().to_string();
+    post_json(&app, &format!("/{}/update", id), json!({"arm": 0, "reward": 1.0})).await;
+
+    let (status, first) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(first["count"], 1);
+    for _ in 0..100 {
+        let (status, again) = get_json(&app, &format!("/{}/stats", id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(again, first);
+    }
+
+    post_json(&app, &format!("/{}/update", id), json!({"arm": 1, "reward": 3.0})).await;
+    let (_, refreshed) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(refreshed["count"], 2);
+    assert_eq!(refreshed["max"], 3.0);
+
+    let (status, _) = get_json(&app, "/missing/stats").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn rest_export_import_round_trip() {
+    let app = routes();
+    let (_, v) = post_json(&app, "/", json!({"strategy":"epsilon_greedy","param":0.3,"num_arms":3})).await;
+    let id = v["id"].as_str().unwrap().to_string();
+    pull_counts(&app, &id, &[0.2, 0.9, 0.5], 40).await;
+
+    let (status, exported) = get_json(&app, &format!("/{}/export", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(exported["strategy"], "epsilon_greedy");
+
+    let (status, v) = post_json(&app, "/import", exported.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+    let copy = v["id"].as_str().unwrap().to_string();
+    assert_ne!(copy, id);
+
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    let (_, copy_stats) = get_json(&app, &format!("/{}/stats", copy)).await;
+    assert_eq!(stats, copy_stats);
+    let (_, copy_export) = get_json(&app, &format!("/{}/export", copy)).await;
+    assert_eq!(copy_export["state"]["counts"], exported["state"]["counts"]);
+    assert_eq!(copy_export["state"]["values"], exported["state"]["values"]);
+
+    for _ in 0..20 {
+        let (_, a) = get_json(&app, &format!("/{}/select", id)).await;
+        let (_, b) = get_json(&app, &format!("/{}/select", copy)).await;
+        assert_eq!(a, b);
+    }
+
+    let (status, _) = post_json(&app, "/import", json!({"strategy":"ucb1","state":{"c":1.0}})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get_json(&app, "/missing/export").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn rest_stats_in_normalized_space() {
+    let app = routes();
+    let (status, v) = post_json(
+        &app,
+        "/",
+        json!({"strategy":"ucb1","param":1.0,"num_arms":2,"normalize_window":20}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    // Rewards in the thousands; arm 1 pays more.
+    for step in 0..40 {
+        let arm = step % 2;
+        let reward = 1_000.0 * (arm + 1) as f64 + (step % 7) as f64;
+        post_json(&app, &format!("/{}/update", id), json!({"arm": arm, "reward": reward})).await;
+    }
+
+    let (_, raw) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert!(raw["min"].as_f64().unwrap() >= 1_000.0);
+    assert!(raw["max"].as_f64().unwrap() > 2_000.0);
+    let (_, explicit_raw) = get_json(&app, &format!("/{}/stats?space=raw", id)).await;
+    assert_eq!(raw, explicit_raw);
+
+    let (status, norm) = get_json(&app, &format!("/{}/stats?space=normalized", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    for key in ["mean", "min", "max"] {
+        let x = norm[key].as_f64().unwrap();
+        assert!((0.0..=1.0).contains(&x), "{} = {}", key, x);
+    }
+    assert_eq!(norm["count"], raw["count"]);
+
+    // A bandit without a normalizer has no normalized space.
+    let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":2})).await;
+    let plain = v["id"].as_str().unwrap();
+    let (status, _) = get_json(&app, &format!("/{}/stats?space=normalized", plain)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get_json(&app, &format!("/{}/stats?space=bogus", id)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn rest_list_and_delete_bandits() {
+    let app = routes();
+    let (_, list) = get_json(&app, "/").await;
+    assert_eq!(list, json!([]));
+
+    let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":3})).await;
+    let ucb = v["id"].as_str().unwrap().to_string();
+    let (_, v) = post_json(&app, "/", json!({"strategy":"thompson","param":0.0,"num_arms":2})).await;
+    let thompson = v["id"].as_str().unwrap().to_string();
+
+    let (status, list) = get_json(&app, "/").await;
+    assert_eq!(status, StatusCode::OK);
+    let list = list.as_array().unwrap();
+    assert_eq!(list.len(), 2);
+    let ucb_entry = list.iter().find(|e| e["id"] == ucb.as_str()).unwrap();
+    assert_eq!(ucb_entry["strategy"], "ucb1");
+    assert_eq!(ucb_entry["num_arms"], 3);
+    let thompson_entry = list.iter().find(|e| e["id"] == thompson.as_str()).unwrap();
+    assert_eq!(thompson_entry["strategy"], "thompson");
+    assert_eq!(thompson_entry["num_arms"], 2);
+
+    for id in [&ucb, &thompson] {
+        let req = Request::delete(format!("/{}", id)).body(Body::empty()).unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+    let (_, list) = get_json(&app, "/").await;
+    assert_eq!(list, json!([]));
+    let (status, _) = get_json(&app, &format!("/{}/select", ucb)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn rest_delete_unknown_bandit() {
+    let app = routes();
+    let req = Request::delete("/no-such-id").body(Body::empty()).unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn rest_arms_report_confidence_bonus() {
+    let app = routes();
+    let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":2})).await;
+    let id = v["id"].as_str().unwrap().to_string();
+    for _ in 0..30 {
+        post_json(&app, &format!("/{}/update", id), json!({"arm": 0, "reward": 0.6})).await;
+    }
+    post_json(&app, &format!("/{}/update", id), json!({"arm": 1, "reward": 0.4})).await;
+
+    let (status, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(arms[0]["count"], 30);
+    assert_eq!(arms[1]["count"], 1);
+    assert_eq!(arms[1]["mean"], 0.4);
+    assert!(arms[1]["bonus"].as_f64().unwrap() > arms[0]["bonus"].as_f64().unwrap());
+
+    let (_, v) = post_json(&app, "/", json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2})).await;
+    let id = v["id"].as_str().unwrap().to_string();
+    post_json(&app, &format!("/{}/update", id), json!({"arm": 1, "reward": 2.0})).await;
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert_eq!(arms[1]["mean"], 2.0);
+    assert_eq!(arms[1]["count"], 1);
+    assert!(arms[1]["bonus"].is_null());
+}
+
+#[tokio::test]
+async fn rest_create_with_priors() {
+    let app = routes();
+    let priors = json!([{"value": 0.1, "count": 20}, {"value": 0.8, "count": 50}]);
+    let (status, v) = post_json(
+        &app,
+        "/",
+        json!({"strategy":"epsilon_greedy","param":0.0,"num_arms":2,"priors":priors}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap();
+    let (_, v) = get_json(&app, &format!("/{}/select", id)).await;
+    assert_eq!(v["arm"], 1);
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert_eq!(arms[1]["count"], 50);
+
+    let (status, _) = post_json(
+        &app,
+        "/",
+        json!({"strategy":"ucb1","param":1.0,"num_arms":3,"priors":priors}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = post_json(
+        &app,
+        "/",
+        json!({"strategy":"thompson","param":0.0,"num_arms":2,"priors":priors}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // A count this large would overflow the first update.
+    let huge = json!([{"value": 0.5, "count": 18446744073709551615u64}]);
+    for strategy in ["epsilon_greedy", "ucb1"] {
+        let (status, _) = post_json(
+            &app,
+            "/",
+            json!({"strategy":strategy,"param":0.1,"num_arms":1,"priors":huge}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn rest_update_batch_applies_all_in_order() {
+    let app = routes();
+    let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":3})).await;
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let updates = json!({"updates": [
+        {"arm": 0, "reward": 1.0},
+        {"arm": 2, "reward": 0.5},
+        {"arm": 0, "reward": 0.0},
+    ]});
+    let (status, v) = post_json(&app, &format!("/{}/update_batch", id), updates).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["applied"], 3);
+
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert_eq!(arms[0]["count"], 2);
+    assert_eq!(arms[0]["mean"], 0.5);
+    assert_eq!(arms[1]["count"], 0);
+    assert_eq!(arms[2]["count"], 1);
+}
+
+#[tokio::test]
+async fn rest_update_batch_is_all_or_nothing() {
+    let app = routes();
+    let (_, v) = post_json(&app, "/", json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2})).await;
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let updates = json!({"updates": [
+        {"arm": 0, "reward": 1.0},
+        {"arm": 5, "reward": 1.0},
+    ]});
+    let (status, _) = post_json(&app, &format!("/{}/update_batch", id), updates).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert_eq!(arms[0]["count"], 0);
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(stats["count"], 0);
+}
+
+#[tokio::test]
+async fn rest_update_batch_rejects_updates_that_overflow_together() {
+    let app = routes();
+    let body = json!({"strategy":"thompson_gaussian","param":0.0,"num_arms":2});
+    let (_, v) = post_json(&app, "/", body).await;
+    let id = v["id"].as_str().unwrap().to_string();
+
+    // Each update is fine on its own, but the second overflows the posterior
+    // left by the first.
+    let updates = json!({"updates": [
+        {"arm": 0, "reward": 1e154},
+        {"arm": 0, "reward": -1e154},
+    ]});
+    let (status, _) = post_json(&app, &format!("/{}/update_batch", id), updates).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert_eq!(arms[0]["count"], 0);
+    let (status, _) = get_json(&app, &format!("/{}/select", id)).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn rest_update_rejects_out_of_range_arm() {
+    let app = routes();
+    for strategy in ["epsilon_greedy", "ucb1", "thompson"] {
+        let (_, v) = post_json(&app, "/", json!({"strategy":strategy,"param":0.1,"num_arms":2})).await;
+        let id = v["id"].as_str().unwrap().to_string();
+        let (status, _) =
+            post_json(&app, &format!("/{}/update", id), json!({"arm": 9, "reward": 1.0})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "{}", strategy);
+
+        // The handler survived and the bandit is still usable.
+        let (status, _) =
+            post_json(&app, &format!("/{}/update", id), json!({"arm": 1, "reward": 1.0})).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn rest_concurrent_traffic_across_bandits() {
+    let app = routes();
+    let mut ids = Vec::new();
+    for _ in 0..8 {
+        let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":2})).await;
+        ids.push(v["id"].as_str().unwrap().to_string());
+    }
+
+    let mut tasks = Vec::new();
+    for id in &ids {
+        for _ in 0..4 {
+            let (app, id) = (app.clone(), id.clone());
+            tasks.push(tokio::spawn(async move {
+                for _ in 0..25 {
+                    let (status, v) = get_json(&app, &format!("/{}/select", id)).await;
+                    assert_eq!(status, StatusCode::OK);
+                    let arm = v["arm"].as_u64().unwrap();
+                    let (status, _) = post_json(
+                        &app,
+                        &format!("/{}/update", id),
+                        json!({"arm": arm, "reward": 1.0}),
+                    )
+                    .await;
+                    assert_eq!(status, StatusCode::OK);
+                    let (status, _) = get_json(&app, &format!("/{}/stats", id)).await;
+                    assert_eq!(status, StatusCode::OK);
+                }
+            }));
+        }
+    }
+    let all = join_all(tasks);
+    tokio::time::timeout(std::time::Duration::from_secs(10), all)
+        .await
+        .expect("requests deadlocked");
+
+    for id in &ids {
+        let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+        assert_eq!(stats["count"], 100);
+    }
+}
+
+async fn join_all(tasks: Vec<tokio::task::JoinHandle<()>>) {
+    for task in tasks {
+        task.await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn server_stops_on_shutdown_signal() {
+    use rustybrain::service::bandit_api::start_server_with_shutdown;
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    let server = start_server_with_shutdown("127.0.0.1:0", async {
+        let _ = rx.await;
+    });
+    let signal = async {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        tx.send(()).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        tokio::join!(server, signal)
+    })
+    .await
+    .expect("server did not shut down");
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn rest_labeled_arms() {
+    let app = routes();
+    let labels = ["control", "variant_a", "variant_b"];
+    let (status, v) = post_json(
+        &app,
+        "/",
+        json!({"strategy":"ucb1","param":1.0,"num_arms":3,"labels":labels}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let (_, v) = get_json(&app, &format!("/{}/select", id)).await;
+    let arm = v["arm"].as_u64().unwrap() as usize;
+    assert_eq!(v["label"], labels[arm]);
+
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    let got: Vec<&str> = arms
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|a| a["label"].as_str().unwrap())
+        .collect();
+    assert_eq!(got, labels);
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(stats["arms"][2]["label"], "variant_b");
+
+    // Unlabeled bandits keep the plain index-only response.
+    let (_, v) = post_json(&app, "/", json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2})).await;
+    let (_, v) = get_json(&app, &format!("/{}/select", v["id"].as_str().unwrap())).await;
+    assert!(v.get("label").is_none());
+
+    let (status, _) = post_json(
+        &app,
+        "/",
+        json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2,"labels":["only_one"]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = post_json(
+        &app,
+        "/",
+        json!({"strategy":"ucb1","param":1.0,"num_arms":2,"labels":["same","same"]}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn rest_update_by_label() {
+    let app = routes();
+    let body = json!({"strategy":"ucb1","param":1.0,"num_arms":2,"labels":["a","b"]});
+    let (_, v) = post_json(&app, "/", body).await;
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let uri = format!("/{}/update", id);
+    let (status, _) = post_json(&app, &uri, json!({"arm": "b", "reward": 1.0})).await;
+    assert_eq!(status, StatusCode::OK);
+    let batch = json!({"updates": [{"arm": "a", "reward": 0.5}, {"arm": 1, "reward": 0.0}]});
+    let (status, _) = post_json(&app, &format!("/{}/update_batch", id), batch).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert_eq!(arms[0]["count"], 1);
+    assert_eq!(arms[1]["count"], 2);
+
+    let (status, _) = post_json(&app, &uri, json!({"arm": "c", "reward": 1.0})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn rest_reset_clears_statistics() {
+    let app = routes();
+    let (_, v) = post_json(&app, "/", json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2})).await;
+    let id = v["id"].as_str().unwrap().to_string();
+    for _ in 0..3 {
+        post_json(&app, &format!("/{}/update", id), json!({"arm": 1, "reward": 1.0})).await;
+    }
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(stats["count"], 3);
+
+    let (status, _) = post_json(&app, &format!("/{}/reset", id), json!({})).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(stats["count"], 0);
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert!(arms.as_array().unwrap().iter().all(|a| a["count"] == 0));
+
+    let (status, _) = post_json(&app, "/does-not-exist/reset", json!({})).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+fn temp_snapshot_path() -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("rustybrain-snapshot-{}.json", uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn registry_snapshot_round_trip() {
+    use rustybrain::service::bandit_api::{routes_with_registry, BanditApiConfig, BanditRegistry};
+
+    let registry = BanditRegistry::default();
+    let dir = std::env::temp_dir();
+    let config = BanditApiConfig {
+        snapshot_dir: Some(dir.clone()),
+        ..BanditApiConfig::default()
+    };
+    let app = routes_with_registry(config, registry.clone());
+    let mut ids = Vec::new();
+    for body in [
+        json!({"strategy": "epsilon_greedy", "param": 0.2, "num_arms": 3}),
+        json!({"strategy": "ucb1", "param": 1.0, "num_arms": 3}),
+        json!({"strategy": "thompson", "param": 0.0, "num_arms": 3}),
+        json!({"strategy": "ucb1", "param": 1.0, "num_arms": 3, "normalize_window": 8}),
+    ] {
+        let (_, v) = post_json(&app, "/", body).await;
+        let id = v["id"].as_str().unwrap().to_string();
+        for step in 0..20 {
+            let (_, v) = get_json(&app, &format!("/{}/select", id)).await;
+            let update = json!({"arm": v["arm"], "reward": (step % 4) as f64 / 4.0});
+            post_json(&app, &format!("/{}/update", id), update).await;
+        }
+        ids.push(id);
+    }
+
+    let path = temp_snapshot_path();
+    let name = path.strip_prefix(&dir).unwrap();
+    let (status, _) = post_json(&app, "/snapshot", json!({"path": name})).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let restored = BanditRegistry::load_from_path(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(restored.snapshots(), registry.snapshots());
+
+    let restored_app = routes_with_registry(BanditApiConfig::default(), restored);
+    for id in &ids {
+        let (_, before) = get_json(&app, &format!("/{}/stats", id)).await;
+        let (status, after) = get_json(&restored_app, &format!("/{}/stats", id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(before, after);
+        let (_, before) = get_json(&app, &format!("/{}/stats?space=normalized", id)).await;
+        let (_, after) = get_json(&restored_app, &format!("/{}/stats?space=normalized", id)).await;
+        assert_eq!(before, after);
+        // RNG state is restored too, so both copies keep selecting in lockstep.
+        let (_, a) = get_json(&app, &format!("/{}/select", id)).await;
+        let (_, b) = get_json(&restored_app, &format!("/{}/select", id)).await;
+        assert_eq!(a, b);
+        // So does the normalizer, so both copies learn the same from new rewards.
+        let update = json!({"arm": a["arm"], "reward": 0.9});
+        post_json(&app, &format!("/{}/update", id), update.clone()).await;
+        post_json(&restored_app, &format!("/{}/update", id), update).await;
+        let (_, before) = get_json(&app, &format!("/{}/arms", id)).await;
+        let (_, after) = get_json(&restored_app, &format!("/{}/arms", id)).await;
+        assert_eq!(before, after);
+    }
+}
+
+#[tokio::test]
+async fn rest_snapshot_stays_inside_snapshot_dir() {
+    use rustybrain::service::bandit_api::{routes_with_config, BanditApiConfig};
+
+    let (status, _) = post_json(&routes(), "/snapshot", json!({"path": "bandits.json"})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let dir = temp_snapshot_path();
+    std::fs::create_dir(&dir).unwrap();
+    let config = BanditApiConfig {
+        snapshot_dir: Some(dir.clone()),
+        ..BanditApiConfig::default()
+    };
+    let app = routes_with_config(config);
+    let escape = std::env::temp_dir().join("rustybrain-escape.json");
+    for path in [json!(escape), json!("../escape.json"), json!("a/../../b.json"), json!("")] {
+        let (status, _) = post_json(&app, "/snapshot", json!({"path": path})).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST, "path = {}", path);
+    }
+    assert!(!escape.exists());
+
+    let (status, _) = post_json(&app, "/snapshot", json!({"path": "bandits.json"})).await;
+    assert_eq!(status, StatusCode::OK);
+    // The temporary file was renamed over the target, leaving nothing else behind.
+    let files: Vec<_> = std::fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name()).collect();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(files, ["bandits.json"]);
+}
+
+#[tokio::test]
+async fn registry_load_rejects_missing_or_invalid_files() {
+    use rustybrain::service::bandit_api::BanditRegistry;
+
+    let path = temp_snapshot_path();
+    assert!(BanditRegistry::load_from_path(&path).is_err());
+    std::fs::write(&path, r#"{"bandits":{"x":{"strategy":"ucb1","state":{}}}}"#).unwrap();
+    let err = BanditRegistry::load_from_path(&path).err().unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[tokio::test]
+async fn rest_normalize_puts_reward_scales_on_common_footing() {
+    let app = routes();
+    let (status, v) = post_json(
+        &app,
+        "/",
+        json!({"strategy":"ucb1","param":1.0,"num_arms":2,"normalize":20}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    // Arm 0 reports latency in milliseconds, arm 1 an accuracy fraction.
+    for i in 0..10 {
+        let latency = 200.0 + 10.0 * i as f64;
+        let accuracy = 0.8 + 0.01 * i as f64;
+        for (arm, reward) in [(0, latency), (1, accuracy)] {
+            let body = json!({"arm": arm, "reward": reward});
+            let (status, _) = post_json(&app, &format!("/{}/update", id), body).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+    }
+
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    for arm in arms.as_array().unwrap() {
+        let mean = arm["mean"].as_f64().unwrap();
+        assert!((0.0..=1.0).contains(&mean), "{arm}");
+    }
+
+    let (_, raw) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(raw["max"], 290.0);
+    let last = raw["normalized"].as_f64().unwrap();
+    assert!(last > 0.0 && last < 0.5, "accuracy sits below the latency-dominated mean");
+
+    let (_, normalized) = get_json(&app, &format!("/{}/stats?space=normalized", id)).await;
+    assert_eq!(normalized["normalized"], raw["normalized"]);
+    assert!(normalized["max"].as_f64().unwrap() <= 1.0);
+
+    // Bandits without a normalizer don't report the field.
+    let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":2})).await;
+    let (_, stats) = get_json(&app, &format!("/{}/stats", v["id"].as_str().unwrap())).await;
+    assert!(stats.get("normalized").is_none());
+}
+
+#[tokio::test]
+async fn rest_tracker_window_limits_stats() {
+    let app = routes();
+    let body = json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2,"tracker_window":5});
+    let (status, v) = post_json(&app, "/", body).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    for reward in 1..=10 {
+        let body = json!({"arm": 0, "reward": reward as f64});
+        let (status, _) = post_json(&app, &format!("/{}/update", id), body).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // Only rewards 6..=10 remain in the window.
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(stats["mean"], 8.0);
+    assert_eq!(stats["min"], 6.0);
+
+    let body = json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2,"tracker_window":0});
+    let (status, _) = post_json(&app, "/", body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn rest_linucb_uses_context() {
+    let app = routes();
+    let body = json!({"strategy":"linucb","param":0.5,"num_arms":2,"context_dim":2});
+    let (status, v) = post_json(&app, "/", body).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    // Arm 0 pays for context [1, 0], arm 1 for [0, 1].
+    for i in 0..200 {
+        let context = if i % 2 == 0 { [1.0, 0.0] } else { [0.0, 1.0] };
+        let body = json!({"context": context});
+        let (status, v) = post_json(&app, &format!("/{}/select", id), body).await;
+        assert_eq!(status, StatusCode::OK);
+        let arm = v["arm"].as_u64().unwrap() as usize;
+        let reward = if context[arm] == 1.0 { 1.0 } else { 0.0 };
+        let body = json!({"arm": arm, "reward": reward, "context": context});
+        let (status, _) = post_json(&app, &format!("/{}/update", id), body).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+    for (context, best) in [([1.0, 0.0], 0), ([0.0, 1.0], 1)] {
+        let (_, v) = post_json(&app, &format!("/{}/select", id), json!({"context": context})).await;
+        assert_eq!(v["arm"], best);
+    }
+
+    // A context is required on select and update, and must match the dimension.
+    let (status, _) = get_json(&app, &format!("/{}/select", id)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let body = json!({"arm": 0, "reward": 1.0});
+    let (status, _) = post_json(&app, &format!("/{}/update", id), body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let body = json!({"arm": 0, "reward": 1.0, "context": [1.0]});
+    let (status, _) = post_json(&app, &format!("/{}/update", id), body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    let pulls: u64 = arms.as_array().unwrap().iter().map(|a| a["count"].as_u64().unwrap()).sum();
+    assert_eq!(pulls, 200);
+
+    let (status, export) = get_json(&app, &format!("/{}/export", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = post_json(&app, "/import", export).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Context-free strategies ignore the field.
+    let (_, v) = post_json(&app, "/", json!({"strategy":"ucb1","param":1.0,"num_arms":2})).await;
+    let body = json!({"context": [1.0, 2.0, 3.0]});
+    let (status, _) = post_json(&app, &format!("/{}/select", v["id"].as_str().unwrap()), body).await;
+    assert_eq!(status, StatusCode::OK);
+
+    for body in [
+        json!({"strategy":"linucb","param":0.5,"num_arms":2}),
+        json!({"strategy":"linucb","param":0.5,"num_arms":2,"context_dim":0}),
+        json!({"strategy":"linucb","param":-1.0,"num_arms":2,"context_dim":2}),
+        json!({"strategy":"linucb","param":0.5,"num_arms":2,"context_dim":2,"labels":["a","b"]}),
+    ] {
+        let (status, _) = post_json(&app, "/", body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
+
+#[tokio::test]
+async fn rest_add_and_remove_arms() {
+    let app = routes();
+    let body = json!({"strategy":"ucb1","param":1.0,"num_arms":2,"labels":["a","b"]});
+    let (_, v) = post_json(&app, "/", body).await;
+    let id = v["id"].as_str().unwrap().to_string();
+    post_json(&app, &format!("/{}/update", id), json!({"arm": 0, "reward": 0.4})).await;
+    post_json(&app, &format!("/{}/update", id), json!({"arm": 1, "reward": 0.6})).await;
+
+    let (status, v) = post_json(&app, &format!("/{}/arms", id), json!({"label": "c"})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v, json!({"arm": 2, "label": "c"}));
+    let (_, v) = get_json(&app, &format!("/{}/select", id)).await;
+    assert_eq!(v["arm"], 2);
+    let (status, _) = post_json(&app, &format!("/{}/arms", id), json!({"label": "a"})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let delete = |uri: String| {
+        let app = app.clone();
+        async move {
+            let req = Request::delete(uri).body(Body::empty()).unwrap();
+            app.oneshot(req).await.unwrap().status()
+        }
+    };
+    assert_eq!(delete(format!("/{}/arms/0", id)).await, StatusCode::OK);
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    let labels: Vec<&Value> = arms.as_array().unwrap().iter().map(|a| &a["label"]).collect();
+    assert_eq!(labels, [&json!("b"), &json!("c")]);
+    assert_eq!(arms[0]["mean"], 0.6);
+
+    assert_eq!(delete(format!("/{}/arms/5", id)).await, StatusCode::BAD_REQUEST);
+    assert_eq!(delete(format!("/{}/arms/0", id)).await, StatusCode::OK);
+    assert_eq!(delete(format!("/{}/arms/0", id)).await, StatusCode::BAD_REQUEST);
+    assert_eq!(delete("/missing/arms/0".into()).await, StatusCode::NOT_FOUND);
+
+    let (_, v) = post_json(&app, "/", json!({"strategy":"thompson","param":0.0,"num_arms":2})).await;
+    let uri = format!("/{}/arms", v["id"].as_str().unwrap());
+    let (status, _) = post_json(&app, &uri, json!({})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn rest_stats_report_per_arm_activity() {
+    let app = routes();
+    let body = json!({"strategy":"ucb1","param":1.0,"num_arms":3,"tracker_window":2});
+    let (_, v) = post_json(&app, "/", body).await;
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let (_, v) = get_json(&app, &format!("/{}/select", id)).await;
+    assert_eq!(v["arm"], 0);
+    for reward in [0.9, 0.1, 0.4] {
+        let body = json!({"arm": 0, "reward": reward});
+        post_json(&app, &format!("/{}/update", id), body).await;
+    }
+
+    let (status, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    let arms = stats["arms"].as_array().unwrap();
+    assert_eq!(arms.len(), 3);
+    assert_eq!(arms[0]["count"], 3);
+    assert!((arms[0]["mean"].as_f64().unwrap() - 1.4 / 3.0).abs() < 1e-12);
+    // Only the last two rewards are recent.
+    assert_eq!(arms[0]["recent_min"], 0.1);
+    assert_eq!(arms[0]["recent_max"], 0.4);
+    assert!(arms[0]["last_selected"].as_u64().unwrap() > 0);
+    assert_eq!(arms[1]["recent_min"], Value::Null);
+    assert_eq!(arms[1]["last_selected"], Value::Null);
+
+    post_json(&app, &format!("/{}/reset", id), json!({})).await;
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(stats["arms"][0]["last_selected"], Value::Null);
+}
+
+#[tokio::test]
+async fn rest_select_slate() {
+    let app = routes();
+    let body = json!({"strategy":"ucb1","param":0.0,"num_arms":4,"labels":["a","b","c","d"]});
+    let (_, v) = post_json(&app, "/", body).await;
+    let id = v["id"].as_str().unwrap().to_string();
+    for (arm, reward) in [(0, 0.1), (1, 0.9), (2, 0.5), (3, 0.7)] {
+        post_json(&app, &format!("/{}/update", id), json!({"arm": arm, "reward": reward})).await;
+    }
+
+    let (status, v) = get_json(&app, &format!("/{}/select?k=2", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v, json!({"arms": [{"arm": 1, "label": "b"}, {"arm": 3, "label": "d"}]}));
+    let (_, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert!(stats["arms"][3]["last_selected"].is_u64());
+    assert!(stats["arms"][0]["last_selected"].is_null());
+
+    let (_, v) = get_json(&app, &format!("/{}/select?k=9", id)).await;
+    assert_eq!(v["arms"].as_array().unwrap().len(), 4);
+    let (status, _) = get_json(&app, &format!("/{}/select?k=0", id)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    // Without `k` the response is still a single arm.
+    let (_, v) = get_json(&app, &format!("/{}/select", id)).await;
+    assert_eq!(v["arm"], 1);
+
+    let body = json!({"strategy":"linucb","param":0.0,"num_arms":3,"context_dim":1});
+    let (_, v) = post_json(&app, "/", body).await;
+    let uri = format!("/{}/select", v["id"].as_str().unwrap());
+    let (status, v) = post_json(&app, &uri, json!({"context": [1.0], "k": 2})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(v["arms"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn rest_epsilon_greedy_step_size() {
+    let app = routes();
+    let body = json!({"strategy":"epsilon_greedy","param":0.0,"num_arms":2,"alpha":0.5});
+    let (status, v) = post_json(&app, "/", body).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+    for reward in [4.0, 8.0] {
+        post_json(&app, &format!("/{}/update", id), json!({"arm": 0, "reward": reward})).await;
+    }
+    let (_, arms) = get_json(&app, &format!("/{}/arms", id)).await;
+    assert_eq!(arms[0]["mean"], 5.0);
+
+    for body in [
+        json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2,"alpha":0.0}),
+        json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2,"alpha":1.5}),
+        json!({"strategy":"ucb1","param":1.0,"num_arms":2,"alpha":0.5}),
+    ] {
+        let (status, _) = post_json(&app, "/", body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,48 @@
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::bandit::ucb1::Ucb1;
+use approx::assert_relative_eq;
+
+#[test]
+fn test_arm_histories_are_independent() {
+    let mut agent = EpsilonGreedy::new(2, 0.1).with_arm_history(3);
+    agent.update(0, 1.0);
+    agent.update(1, 10.0);
+    agent.update(0, 3.0);
+    agent.update(1, 20.0);
+
+    let (mean0, min0, max0, count0) = agent.arm_stats(0).unwrap();
+    assert_relative_eq!(mean0, 2.0, epsilon = 1e-12);
+    assert_relative_eq!(min0, 1.0, epsilon = 1e-12);
+    assert_relative_eq!(max0, 3.0, epsilon = 1e-12);
+    assert_eq!(count0, 2);
+
+    let (mean1, min1, max1, count1) = agent.arm_stats(1).unwrap();
+    assert_relative_eq!(mean1, 15.0, epsilon = 1e-12);
+    assert_relative_eq!(min1, 10.0, epsilon = 1e-12);
+    assert_relative_eq!(max1, 20.0, epsilon = 1e-12);
+    assert_eq!(count1, 2);
+}
+
+#[test]
+fn test_arm_history_is_bounded_by_window() {
+    let mut agent = Ucb1::new(1, 2.0).with_arm_history(2);
+    for r in [1.0, 2.0, 3.0, 4.0] {
+        agent.update(0, r);
+    }
+    let (mean, min, _, count) = agent.arm_stats(0).unwrap();
+    assert_relative_eq!(mean, 3.5, epsilon = 1e-12);
+    assert_relative_eq!(min, 3.0, epsilon = 1e-12);
+    assert_eq!(count, 2);
+    // The lifetime mean is unaffected by the history window.
+    assert_relative_eq!(agent.values()[0], 2.5, epsilon = 1e-12);
+}
+
+#[test]
+fn test_arm_history_disabled_by_default() {
+    let mut agent = EpsilonGreedy::new(2, 0.1);
+    agent.update(0, 1.0);
+    assert!(agent.arm_stats(0).is_none());
+
+    let ucb = Ucb1::new(2, 2.0);
+    assert!(ucb.arm_stats(1).is_none());
+}
@@ -0,0 +1,47 @@
+use approx::assert_relative_eq;
+use rustybrain::bandit::bayes_ucb::BayesUcb;
+use rustybrain::bandit::sim::{gaussian_arm, run_simulation, SimArm};
+
+#[test]
+fn test_posterior_matches_conjugate_update() {
+    let mut agent = BayesUcb::new(2, 0.9).with_prior(1.0, 2.0).with_noise(0.5);
+    assert_eq!(agent.posterior(0), (1.0, 2.0));
+    for reward in [3.0, 5.0] {
+        agent.update(0, reward);
+    }
+    // precision = 1/4 + 2/0.25 = 8.25; mean = (1/4 + 8/0.25) / 8.25
+    let (mean, std) = agent.posterior(0);
+    assert_relative_eq!(mean, 32.25 / 8.25, epsilon = 1e-12);
+    assert_relative_eq!(std, 8.25f64.sqrt().recip(), epsilon = 1e-12);
+    assert_eq!(agent.values(), &[4.0, 0.0]);
+    assert_eq!(agent.posterior(1), (1.0, 2.0));
+}
+
+#[test]
+fn test_scores_use_the_posterior_quantile() {
+    let median = BayesUcb::new(1, 0.5);
+    assert_relative_eq!(median.scores()[0], 0.0, epsilon = 1e-9);
+    let upper = BayesUcb::new(1, 0.975).with_prior(2.0, 3.0);
+    assert_relative_eq!(upper.scores()[0], 2.0 + 1.959964 * 3.0, epsilon = 1e-5);
+    let lower = BayesUcb::new(1, 0.01);
+    assert_relative_eq!(lower.scores()[0], -2.326348, epsilon = 1e-5);
+}
+
+#[test]
+fn test_finds_best_gaussian_arm() {
+    let arms: Vec<SimArm> = vec![
+        gaussian_arm(1.0, 1.0),
+        gaussian_arm(1.5, 1.0),
+        gaussian_arm(0.5, 1.0),
+    ];
+    let mut agent = BayesUcb::new(3, 0.95).with_prior(0.0, 10.0);
+    let report = run_simulation(&mut agent, &arms, 3_000, 5);
+    assert!(report.pulls[1] > 2_400, "{:?}", report.pulls);
+    assert!(report.pulls.iter().all(|&n| n > 0));
+}
+
+#[test]
+#[should_panic(expected = "quantile must be between 0.0 and 1.0")]
+fn test_rejects_degenerate_quantile() {
+    BayesUcb::new(2, 1.0);
+}
@@ -0,0 +1,68 @@
+use rustybrain::bandit::budgeted::{Budget, BudgetedBandit};
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::bandit::ucb1::Ucb1;
+
+#[test]
+fn test_skips_unaffordable_arms_until_exhausted() {
+    // Arm 0 pays best but costs 5; the budget affords it once.
+    let mut agent = BudgetedBandit::new(EpsilonGreedy::new(2, 0.0), vec![5.0, 1.0], 7.0);
+    agent.update(0, 1.0);
+    assert_eq!(agent.budget().remaining(), 2.0);
+    assert_eq!(agent.budget().affordable(), vec![1]);
+
+    assert_eq!(agent.select_arm(), Some(1));
+    agent.update(1, 0.0);
+    agent.update(1, 0.0);
+    assert!(agent.budget().is_exhausted());
+    assert_eq!(agent.select_arm(), None);
+    assert_eq!(agent.budget().spent(), 7.0);
+    assert_eq!(agent.inner().counts(), &[1, 2]);
+}
+
+#[test]
+fn test_exhausted_budget_falls_back_to_default_arm() {
+    let mut agent = BudgetedBandit::new(Ucb1::new(2, 1.0), vec![2.0, 3.0], 2.0);
+    assert_eq!(agent.default_arm(), None);
+    agent.set_default_arm(1);
+    assert_eq!(agent.default_arm(), Some(1));
+
+    // Only arm 0 is affordable while budget remains.
+    assert_eq!(agent.select_arm(), Some(0));
+    agent.update(0, 1.0);
+    assert!(agent.budget().is_exhausted());
+    assert_eq!(agent.select_arm(), Some(1));
+}
+
+#[test]
+#[should_panic(expected = "default arm out of range")]
+fn test_default_arm_must_exist() {
+    BudgetedBandit::new(Ucb1::new(2, 1.0), vec![1.0, 1.0], 5.0).set_default_arm(2);
+}
+
+#[test]
+fn test_slate_fits_in_budget() {
+    let mut agent = BudgetedBandit::new(Ucb1::new(3, 1.0), vec![2.0, 2.0, 1.0], 3.0);
+    let slate = agent.select_arms(3);
+    let cost: f64 = slate.iter().map(|&arm| agent.budget().costs()[arm]).sum();
+    assert_eq!(slate.len(), 2);
+    assert!(cost <= 3.0, "slate {:?} costs {}", slate, cost);
+}
+
+#[test]
+fn test_budget_charge_reports_overspend_and_resets() {
+    let mut budget = Budget::new(vec![4.0], 3.0);
+    budget.charge(0);
+    assert_eq!(budget.remaining(), 0.0);
+    assert_eq!(budget.spent(), 4.0);
+    assert_eq!(budget.overspent(), 1.0);
+    budget.reset();
+    assert_eq!(budget.remaining(), 3.0);
+    assert_eq!(budget.overspent(), 0.0);
+    assert_eq!(budget.total(), 3.0);
+}
+
+#[test]
+#[should_panic(expected = "one entry per arm")]
+fn test_costs_must_match_arms() {
+    BudgetedBandit::new(Ucb1::new(3, 1.0), vec![1.0], 10.0);
+}
@@ -0,0 +1,105 @@
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::bandit::thompson::ThompsonSampling;
+use rustybrain::bandit::ucb1::Ucb1;
+use rustybrain::metrics::reward_tracker::RewardTracker;
+
+#[test]
+fn test_epsilon_greedy_round_trip_continues_identically() {
+    let mut agent = EpsilonGreedy::with_seed(4, 0.3, 11).with_arm_history(5);
+    agent.set_cooldown(1);
+    for i in 0..37 {
+        let arm = agent.select_arm();
+        agent.update(arm, (i % 5) as f64 / 4.0);
+    }
+
+    let mut restored = EpsilonGreedy::from_json(&agent.to_json()).unwrap();
+    assert_eq!(restored.counts(), agent.counts());
+    assert_eq!(restored.values(), agent.values());
+    assert_eq!(restored.arm_stats(2), agent.arm_stats(2));
+    for _ in 0..100 {
+        let (a, b) = (agent.select_arm(), restored.select_arm());
+        assert_eq!(a, b);
+        agent.update(a, 0.5);
+        restored.update(b, 0.5);
+    }
+}
+
+#[test]
+fn test_ucb1_and_thompson_round_trip() {
+    let mut ucb = Ucb1::with_range(3, 1.5, 0.0, 10.0);
+    let mut ts = ThompsonSampling::new(3, 5);
+    for i in 0..20 {
+        let arm = ucb.select_arm();
+        ucb.update(arm, i as f64 / 2.0);
+        let arm = ts.select_arm();
+        ts.update(arm, (i % 2) as f64);
+    }
+
+    let ucb2 = Ucb1::from_json(&ucb.to_json()).unwrap();
+    let mut ts2 = ThompsonSampling::from_json(&ts.to_json()).unwrap();
+    assert_eq!(ucb2.snapshot(), ucb.snapshot());
+    assert_eq!(ts2.alpha(), ts.alpha());
+    for _ in 0..20 {
+        assert_eq!(ucb2.select_arm(), ucb.select_arm());
+        assert_eq!(ts2.select_arm(), ts.select_arm());
+    }
+}
+
+#[test]
+fn test_reward_tracker_round_trip_and_rejects_bad_state() {
+    let mut tracker = RewardTracker::new(3);
+    for r in [1.0, 4.0, 2.0, 8.0] {
+        tracker.update(r);
+    }
+    let mut restored = RewardTracker::from_json(&tracker.to_json()).unwrap();
+    assert_eq!(restored.values(), tracker.values());
+    assert_eq!(restored.total_updates(), 4);
+    assert_eq!(restored.steps_to_reach(4.0), tracker.steps_to_reach(4.0));
+    restored.update(16.0);
+    assert_eq!(restored.values(), &[2.0, 8.0, 16.0]);
+
+    assert!(RewardTracker::from_json("{}").is_err());
+    let bad = Ucb1::new(2, 1.0).to_json().replacen("\"counts\":[0,0]", "\"counts\":[0]", 1);
+    assert!(Ucb1::from_json(&bad).is_err());
+}
+
+/// Returns `json` with the top-level `field` overwritten by `value`.
+fn patched(json: &str, field: &str, value: serde_json::Value) -> String {
+    let mut state: serde_json::Value = serde_json::from_str(json).unwrap();
+    assert!(state.get(field).is_some(), "no field {}", field);
+    state[field] = value;
+    state.to_string()
+}
+
+#[test]
+fn test_restore_rejects_configuration_constructors_reject() {
+    use rustybrain::bandit::epsilon_greedy::{DecaySchedule, SelectionPolicy};
+    use serde_json::json;
+
+    let schedule = DecaySchedule::Linear { min: 0.1, steps: 10 };
+    let greedy = EpsilonGreedy::with_decay(2, 0.5, schedule)
+        .with_policy(SelectionPolicy::Softmax { temperature: 0.5 })
+        .with_step_size(0.5)
+        .to_json();
+    assert!(EpsilonGreedy::from_json(&greedy).is_ok());
+    for (field, value) in [
+        ("epsilon", json!(1.5)),
+        ("decay", json!({"Linear": {"min": 0.9, "steps": 10}})),
+        ("policy", json!({"Softmax": {"temperature": 0.0}})),
+        ("step_size", json!(0.0)),
+    ] {
+        let bad = patched(&greedy, field, value);
+        assert!(EpsilonGreedy::from_json(&bad).is_err(), "accepted {}", bad);
+    }
+
+    let ucb = Ucb1::with_discount(2, 1.0, 0.9).to_json();
+    assert!(Ucb1::from_json(&ucb).is_ok());
+    for (field, value) in [
+        ("c", json!(-1.0)),
+        ("reward_scale", json!(0.0)),
+        ("discount", json!({"gamma": 1.5, "counts": [0.0, 0.0], "sums": [0.0, 0.0]})),
+    ] {
+        let bad = patched(&ucb, field, value);
+        assert!(Ucb1::from_json(&bad).is_err(), "accepted {}", bad);
+    }
+}
@@ -0,0 +1,40 @@
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::bandit::ucb1::Ucb1;
+use approx::assert_relative_eq;
+
+#[test]
+fn test_interval_brackets_true_mean_and_narrows() {
+    let mut agent = Ucb1::new(1, 2.0);
+    // Alternating 0/1 rewards: true mean 0.5.
+    let mut widths = Vec::new();
+    for i in 0..200 {
+        agent.update(0, (i % 2) as f64);
+        if i + 1 == 10 || i + 1 == 50 || i + 1 == 200 {
+            let (lo, hi) = agent.arm_confidence_interval(0, 1.96);
+            assert!(lo < 0.5 && 0.5 < hi, "[{}, {}] misses 0.5", lo, hi);
+            widths.push(hi - lo);
+        }
+    }
+    assert!(widths[0] > widths[1] && widths[1] > widths[2], "widths = {:?}", widths);
+}
+
+#[test]
+fn test_interval_unbounded_below_two_samples() {
+    let mut agent = EpsilonGreedy::new(2, 0.1);
+    assert_eq!(agent.arm_confidence_interval(0, 1.96), (f64::NEG_INFINITY, f64::INFINITY));
+    agent.update(0, 3.0);
+    assert_eq!(agent.arm_confidence_interval(0, 1.96), (f64::NEG_INFINITY, f64::INFINITY));
+    assert!(agent.arm_variance(0).is_none());
+}
+
+#[test]
+fn test_variance_is_unbiased_sample_variance() {
+    let mut agent = EpsilonGreedy::new(1, 0.1);
+    for r in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+        agent.update(0, r);
+    }
+    // Sum of squared deviations = 32 over 8 samples -> 32 / 7.
+    assert_relative_eq!(agent.arm_variance(0).unwrap(), 32.0 / 7.0, epsilon = 1e-12);
+    let (lo, hi) = agent.arm_confidence_interval(0, 2.0);
+    assert_relative_eq!(hi - lo, 4.0 * (32.0 / 7.0 / 8.0_f64).sqrt(), epsilon = 1e-12);
+}
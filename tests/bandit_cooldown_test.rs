@@ -0,0 +1,91 @@
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::bandit::ucb1::Ucb1;
+
+// Fixed per-arm rewards; arm 2 is the best.
+const REWARDS: [f64; 4] = [0.2, 0.4, 0.9, 0.5];
+
+fn assert_spaced(history: &[usize], cooldown: usize) {
+    for (t, &arm) in history.iter().enumerate() {
+        for back in 1..=cooldown.min(t) {
+            assert_ne!(
+                arm,
+                history[t - back],
+                "arm {} re-selected {} step(s) after its previous selection",
+                arm,
+                back
+            );
+        }
+    }
+}
+
+#[test]
+fn test_epsilon_greedy_cooldown_spaces_selections() {
+    let mut agent = EpsilonGreedy::new(4, 0.2);
+    agent.set_cooldown(2);
+    let mut history = Vec::new();
+    for _ in 0..500 {
+        let arm = agent.select_arm();
+        agent.update(arm, REWARDS[arm]);
+        history.push(arm);
+    }
+    assert_spaced(&history, 2);
+}
+
+#[test]
+fn test_ucb1_cooldown_best_arm_still_dominates() {
+    let mut agent = Ucb1::new(4, 1.0);
+    agent.set_cooldown(2);
+    let mut history = Vec::new();
+    for _ in 0..1000 {
+        let arm = agent.select_arm_mut();
+        agent.update(arm, REWARDS[arm]);
+        history.push(arm);
+    }
+    assert_spaced(&history, 2);
+
+    let counts = agent.counts();
+    for (arm, &n) in counts.iter().enumerate() {
+        if arm != 2 {
+            assert!(counts[2] > n, "best arm should dominate, counts = {:?}", counts);
+        }
+    }
+}
+
+#[test]
+fn test_ucb1_select_arm_is_pure_and_agent_is_sync() {
+    fn assert_sync<T: Sync>() {}
+    assert_sync::<Ucb1>();
+
+    let mut agent = Ucb1::new(2, 1.0);
+    agent.set_cooldown(1);
+    agent.update(0, 1.0);
+    agent.update(1, 0.0);
+    // Read-only selection leaves the cool-down untouched.
+    assert_eq!(agent.select_arm(), 0);
+    assert_eq!(agent.select_arm(), 0);
+    // Recording it excludes arm 0 from the next selection.
+    assert_eq!(agent.select_arm_mut(), 0);
+    assert_eq!(agent.select_arm(), 1);
+}
+
+#[test]
+fn test_all_arms_cooling_down_picks_least_recent() {
+    let mut agent = EpsilonGreedy::new(2, 0.0);
+    agent.set_cooldown(5);
+    let first = agent.select_arm();
+    let second = agent.select_arm();
+    assert_ne!(first, second);
+    // Both arms are now cooling down; the oldest selection comes back first.
+    assert_eq!(agent.select_arm(), first);
+    assert_eq!(agent.select_arm(), second);
+}
+
+#[test]
+fn test_zero_cooldown_matches_default_behavior() {
+    let mut plain = EpsilonGreedy::new(3, 0.5);
+    let mut cooled = EpsilonGreedy::new(3, 0.5);
+    cooled.set_cooldown(0);
+    for _ in 0..50 {
+        assert_eq!(plain.select_arm(), cooled.select_arm());
+    }
+}
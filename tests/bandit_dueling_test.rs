@@ -0,0 +1,64 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rustybrain::bandit::dueling::Rucb;
+
+/// `PREFS[i][j]`: probability that arm `i` beats arm `j`; arm 2 is the Condorcet winner.
+const PREFS: [[f64; 3]; 3] = [[0.5, 0.6, 0.2], [0.4, 0.5, 0.3], [0.8, 0.7, 0.5]];
+
+#[test]
+fn test_converges_to_condorcet_winner() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let mut agent = Rucb::new(3, 0.51, 2);
+    let mut self_duels_of_winner = 0;
+    for step in 0..2000 {
+        let (a, b) = agent.select_duel();
+        if step >= 1500 && a == 2 && b == 2 {
+            self_duels_of_winner += 1;
+        }
+        if rng.gen::<f64>() < PREFS[a][b] {
+            agent.update_duel(a, b);
+        } else {
+            agent.update_duel(b, a);
+        }
+    }
+    assert_eq!(agent.best_arm(), 2);
+    assert_eq!(agent.copeland_scores(), vec![1, 0, 2]);
+    assert!(self_duels_of_winner > 400, "winner exploited {} times", self_duels_of_winner);
+    assert_eq!(agent.duels(), 2000);
+}
+
+#[test]
+fn test_unmet_pairs_are_explored_first() {
+    let mut agent = Rucb::new(2, 0.6, 3);
+    let (a, b) = agent.select_duel();
+    assert_ne!(a, b);
+    assert_eq!(agent.upper_bounds()[0][1], 1.0);
+    assert_eq!(agent.preference(0, 1), None);
+
+    agent.update_duel(1, 0);
+    assert_eq!(agent.preference(1, 0), Some(1.0));
+    assert_eq!(agent.wins(1, 0), 1);
+    agent.update_duel(1, 1);
+    assert_eq!((agent.wins(1, 1), agent.duels()), (0, 2));
+}
+
+#[test]
+fn test_json_round_trip_reset_and_errors() {
+    let mut agent = Rucb::new(3, 0.51, 4);
+    agent.update_duel(0, 2);
+    let mut restored = Rucb::from_json(&agent.to_json()).unwrap();
+    assert_eq!(restored.wins(0, 2), 1);
+    assert_eq!(restored.select_duel(), agent.select_duel());
+    let mut bad: serde_json::Value = serde_json::from_str(&agent.to_json()).unwrap();
+    bad["wins"] = serde_json::json!([[0, 0]]);
+    assert!(Rucb::from_json(&bad.to_string()).is_err());
+
+    assert!(agent.try_update_duel(0, 3).is_err());
+    agent.reset();
+    assert_eq!((agent.wins(0, 2), agent.duels()), (0, 0));
+}
+
+#[test]
+#[should_panic(expected = "greater than 0.5")]
+fn test_alpha_must_exceed_half() {
+    Rucb::new(2, 0.5, 0);
+}
@@ -25,7 +25,7 @@ fn test_exploitation_when_epsilon_zero() {
 #[test]
 fn test_exploration_when_epsilon_high() {
     let mut agent = EpsilonGreedy::new(3, 1.0);
-    let mut seen = vec![false; 3];
+    let mut seen = [false; 3];
     for _ in 0..100 {
         let arm = agent.select_arm();
         seen[arm] = true;
@@ -50,4 +50,429 @@ fn test_deterministic_behavior_with_seed() {
     let sequence1: Vec<_> = (0..10).map(|_| agent1.select_arm()).collect();
     let sequence2: Vec<_> = (0..10).map(|_| agent2.select_arm()).collect();
     assert_eq!(sequence1, sequence2, "Deterministic RNG ensures reproducibility");
-}
\ No newline at end of file
+}
+
+
+#[test]
+fn test_fork_copies_state_with_new_rng() {
+    let mut agent = EpsilonGreedy::new(3, 1.0);
+    agent.update(1, 2.0);
+    let mut forked = agent.fork(7);
+    assert_eq!(forked.counts(), agent.counts());
+    assert_eq!(forked.values(), agent.values());
+
+    let original: Vec<_> = (0..20).map(|_| agent.select_arm()).collect();
+    let branched: Vec<_> = (0..20).map(|_| forked.select_arm()).collect();
+    assert_ne!(original, branched, "fork should explore along its own sequence");
+}
+
+#[test]
+fn test_try_select_arm_without_warmup_matches_select() {
+    let mut a = EpsilonGreedy::new(3, 0.2);
+    let mut b = EpsilonGreedy::new(3, 0.2);
+    for _ in 0..20 {
+        assert_eq!(a.try_select_arm(), Some(b.select_arm()));
+    }
+
+    a.set_min_pulls(1);
+    assert_eq!(a.try_select_arm(), None);
+    for arm in 0..3 {
+        a.update(arm, 1.0);
+    }
+    assert!(a.try_select_arm().is_some());
+}
+
+#[test]
+fn test_with_seed_controls_exploration_sequence() {
+    let picks = |agent: &mut EpsilonGreedy| {
+        (0..50).map(|_| agent.select_arm()).collect::<Vec<_>>()
+    };
+
+    let seq_a = picks(&mut EpsilonGreedy::with_seed(5, 1.0, 1));
+    let seq_b = picks(&mut EpsilonGreedy::with_seed(5, 1.0, 2));
+    let seq_a2 = picks(&mut EpsilonGreedy::with_seed(5, 1.0, 1));
+    assert_ne!(seq_a, seq_b);
+    assert_eq!(seq_a, seq_a2);
+    assert_eq!(
+        picks(&mut EpsilonGreedy::new(5, 1.0)),
+        picks(&mut EpsilonGreedy::with_seed(5, 1.0, 42))
+    );
+}
+
+#[test]
+fn test_reset_replays_from_scratch() {
+    let mut agent = EpsilonGreedy::with_seed(3, 0.5, 9);
+    let run = |agent: &mut EpsilonGreedy| {
+        (0..30)
+            .map(|_| {
+                let arm = agent.select_arm();
+                agent.update(arm, arm as f64);
+                arm
+            })
+            .collect::<Vec<_>>()
+    };
+    let first = run(&mut agent);
+    agent.reset();
+    assert_eq!(agent.counts(), &[0, 0, 0]);
+    assert_eq!(agent.values(), &[0.0, 0.0, 0.0]);
+    assert_eq!(run(&mut agent), first);
+    assert_eq!(agent.seed(), 9);
+}
+
+#[test]
+fn test_linear_decay_reaches_min_and_stays() {
+    use rustybrain::bandit::epsilon_greedy::DecaySchedule;
+
+    let schedule = DecaySchedule::Linear { min: 0.05, steps: 200 };
+    let mut agent = EpsilonGreedy::with_decay(3, 0.5, schedule);
+    assert_eq!(agent.current_epsilon(), 0.5);
+
+    let mut previous = agent.current_epsilon();
+    for t in 1..=500u64 {
+        let arm = agent.select_arm();
+        agent.update(arm, 1.0);
+        let eps = agent.current_epsilon();
+        assert!(eps >= 0.05 && eps <= previous, "t = {}, eps = {}", t, eps);
+        if t == 100 {
+            assert_relative_eq!(eps, 0.275, epsilon = 1e-12);
+        }
+        if t >= 200 {
+            assert_eq!(eps, 0.05);
+        }
+        previous = eps;
+    }
+
+    assert_eq!(EpsilonGreedy::new(3, 0.3).current_epsilon(), 0.3);
+}
+
+#[test]
+fn test_exponential_decay_respects_floor() {
+    use rustybrain::bandit::epsilon_greedy::DecaySchedule;
+
+    let schedule = DecaySchedule::Exponential { rate: 0.01, min: 0.02 };
+    assert_relative_eq!(schedule.epsilon_at(0.4, 100), 0.4 * (-1.0f64).exp());
+    assert_eq!(schedule.epsilon_at(0.4, 10_000), 0.02);
+}
+
+#[test]
+fn test_with_priors_exploits_seeded_arm_and_blends_updates() {
+    let mut agent = EpsilonGreedy::with_priors(0.0, vec![(0.1, 10), (0.9, 100), (0.2, 10)]);
+    assert_eq!(agent.counts(), &[10, 100, 10]);
+    assert_eq!(agent.select_arm(), 1);
+
+    agent.update(1, 0.0);
+    assert_eq!(agent.counts()[1], 101);
+    assert_relative_eq!(agent.values()[1], 0.9 * 100.0 / 101.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_with_priors_variance_ignores_seeded_counts() {
+    let mut agent = EpsilonGreedy::with_priors(0.1, vec![(0.7, 1000)]);
+    assert_eq!(agent.arm_variance(0), None);
+    assert_eq!(agent.arm_confidence_interval(0, 1.96), (f64::NEG_INFINITY, f64::INFINITY));
+
+    agent.update(0, 0.0);
+    assert_eq!(agent.arm_variance(0), None);
+    agent.update(0, 1.0);
+    assert_relative_eq!(agent.arm_variance(0).unwrap(), 0.5, epsilon = 1e-12);
+    let (lo, hi) = agent.arm_confidence_interval(0, 1.0);
+    assert_relative_eq!(lo, 0.0, epsilon = 1e-12);
+    assert_relative_eq!(hi, 1.0, epsilon = 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "prior counts")]
+fn test_with_priors_rejects_huge_count() {
+    EpsilonGreedy::with_priors(0.1, vec![(0.5, u64::MAX)]);
+}
+
+#[test]
+#[should_panic(expected = "prior values")]
+fn test_with_priors_rejects_non_finite_value() {
+    EpsilonGreedy::with_priors(0.1, vec![(f64::NAN, 1)]);
+}
+
+#[test]
+fn test_try_with_prior_stats_validates_lengths() {
+    use rustybrain::bandit::BanditError;
+
+    let agent = EpsilonGreedy::try_with_prior_stats(0.0, &[10, 100], &[0.1, 0.9]).unwrap();
+    assert_eq!(agent.counts(), &[10, 100]);
+    assert_eq!(agent.values(), &[0.1, 0.9]);
+    assert!(matches!(
+        EpsilonGreedy::try_with_prior_stats(0.0, &[10], &[0.1, 0.9]),
+        Err(BanditError::InvalidPriors(_))
+    ));
+    assert!(matches!(
+        EpsilonGreedy::try_with_prior_stats(0.0, &[u64::MAX], &[0.1]),
+        Err(BanditError::InvalidPriors(_))
+    ));
+}
+
+#[test]
+fn test_try_update_rejects_bad_input() {
+    use rustybrain::bandit::BanditError;
+
+    let mut agent = EpsilonGreedy::new(2, 0.1);
+    assert_eq!(
+        agent.try_update(2, 1.0),
+        Err(BanditError::ArmOutOfRange { arm: 2, num_arms: 2 })
+    );
+    assert!(matches!(agent.try_update(0, f64::NAN), Err(BanditError::NonFiniteReward(_))));
+    assert_eq!(agent.counts(), &[0, 0]);
+    assert_eq!(agent.try_update(1, 0.5), Ok(()));
+    assert_eq!(agent.values()[1], 0.5);
+}
+
+#[test]
+fn test_labels() {
+    let labels = vec!["control".to_string(), "variant_a".into(), "variant_b".into()];
+    let agent = EpsilonGreedy::with_labels(0.1, labels);
+    assert_eq!(agent.counts().len(), 3);
+    assert_eq!(agent.label(0), Some("control"));
+    assert_eq!(agent.label(2), Some("variant_b"));
+    assert_eq!(agent.label(3), None);
+
+    let restored = EpsilonGreedy::from_json(&agent.to_json()).unwrap();
+    assert_eq!(restored.label(1), Some("variant_a"));
+
+    let unlabeled = EpsilonGreedy::new(3, 0.1);
+    assert_eq!(unlabeled.label(0), None);
+}
+
+#[test]
+fn test_update_by_label() {
+    use rustybrain::bandit::BanditError;
+
+    let mut agent = EpsilonGreedy::with_labels(0.0, vec!["control".into(), "variant".into()]);
+    assert_eq!(agent.arm_index("variant"), Some(1));
+    agent.update_labeled("variant", 1.0).unwrap();
+    assert_eq!(agent.counts(), &[0, 1]);
+    assert_eq!(agent.select_labeled(), (1, Some("variant")));
+    assert!(matches!(
+        agent.update_labeled("missing", 1.0),
+        Err(BanditError::UnknownArm(label)) if label == "missing"
+    ));
+    assert_eq!(EpsilonGreedy::new(2, 0.1).arm_index("0"), None);
+}
+
+#[test]
+fn test_tie_break() {
+    use rustybrain::bandit::TieBreak;
+
+    // epsilon = 0 always exploits; all values are equal at 0.0.
+    let mut agent = EpsilonGreedy::new(4, 0.0);
+    assert_eq!(agent.tie_break(), TieBreak::Lowest);
+    assert!((0..100).all(|_| agent.select_arm() == 0));
+
+    agent.set_tie_break(TieBreak::Highest);
+    assert!((0..100).all(|_| agent.select_arm() == 3));
+
+    agent.set_tie_break(TieBreak::Random);
+    let mut seen = [0usize; 4];
+    for _ in 0..400 {
+        seen[agent.select_arm()] += 1;
+    }
+    assert!(seen.iter().all(|&n| n > 50), "{:?}", seen);
+
+    // Random tie-breaking replays exactly from the same seed.
+    let mut a = EpsilonGreedy::with_seed(4, 0.0, 7);
+    let mut b = EpsilonGreedy::with_seed(4, 0.0, 7);
+    a.set_tie_break(TieBreak::Random);
+    b.set_tie_break(TieBreak::Random);
+    let picks_a: Vec<usize> = (0..50).map(|_| a.select_arm()).collect();
+    let picks_b: Vec<usize> = (0..50).map(|_| b.select_arm()).collect();
+    assert_eq!(picks_a, picks_b);
+
+    // A strictly better arm is never subject to tie-breaking.
+    a.update(2, 1.0);
+    assert!((0..50).all(|_| a.select_arm() == 2));
+}
+
+#[test]
+fn test_softmax_policy_temperature() {
+    use rustybrain::bandit::epsilon_greedy::SelectionPolicy;
+
+    let share_of_best = |temperature: f64| {
+        let mut agent = EpsilonGreedy::with_seed(3, 0.1, 5)
+            .with_policy(SelectionPolicy::Softmax { temperature });
+        for (arm, reward) in [(0, 0.2), (1, 0.5), (2, 1.0)] {
+            agent.update(arm, reward);
+        }
+        let p = agent.selection_probabilities();
+        assert!(p.iter().all(|x| x.is_finite() && *x >= 0.0), "{:?}", p);
+        assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+
+        // Selection must not change the estimates, so sample without updating.
+        let picks = (0..2000).filter(|_| agent.select_arm() == 2).count();
+        (picks as f64 / 2000.0, p[2])
+    };
+
+    let (cold, cold_p) = share_of_best(0.05);
+    let (hot, hot_p) = share_of_best(10.0);
+    assert!(cold > 0.95, "cold softmax should exploit: {}", cold);
+    assert!(hot < 0.45, "hot softmax should spread out: {}", hot);
+    assert!((cold - cold_p).abs() < 0.03 && (hot - hot_p).abs() < 0.03);
+}
+
+#[test]
+fn test_softmax_handles_large_values() {
+    use rustybrain::bandit::epsilon_greedy::SelectionPolicy;
+
+    let mut agent = EpsilonGreedy::new(2, 0.0)
+        .with_policy(SelectionPolicy::Softmax { temperature: 0.01 });
+    agent.update(0, 1e6);
+    agent.update(1, 1e6 - 1.0);
+    let p = agent.selection_probabilities();
+    assert!(p.iter().all(|x| x.is_finite()), "{:?}", p);
+    assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert_eq!(agent.select_arm(), 0);
+}
+
+#[test]
+fn test_default_policy_probabilities() {
+    use rustybrain::bandit::epsilon_greedy::SelectionPolicy;
+
+    let mut agent = EpsilonGreedy::new(4, 0.2);
+    assert_eq!(agent.policy(), SelectionPolicy::EpsilonGreedy);
+    agent.update(1, 1.0);
+    let p = agent.selection_probabilities();
+    for (got, want) in p.iter().zip([0.05, 0.85, 0.05, 0.05]) {
+        assert_relative_eq!(*got, want, epsilon = 1e-12);
+    }
+}
+
+#[test]
+#[should_panic(expected = "temperature")]
+fn test_softmax_rejects_zero_temperature() {
+    use rustybrain::bandit::epsilon_greedy::SelectionPolicy;
+
+    let _ = EpsilonGreedy::new(2, 0.1).with_policy(SelectionPolicy::Softmax { temperature: 0.0 });
+}
+
+#[test]
+fn test_add_and_remove_arms_keep_statistics() {
+    use rustybrain::bandit::BanditError;
+
+    let mut agent = EpsilonGreedy::new(2, 0.0);
+    agent.update(0, 1.0);
+    agent.update(1, 3.0);
+
+    assert_eq!(agent.add_arm(), 2);
+    assert_eq!(agent.counts(), &[1, 1, 0]);
+    assert_eq!(agent.values(), &[1.0, 3.0, 0.0]);
+    agent.update(2, 5.0);
+    assert_eq!(agent.select_arm(), 2);
+
+    agent.remove_arm(0);
+    assert_eq!(agent.counts(), &[1, 1]);
+    assert_eq!(agent.values(), &[3.0, 5.0]);
+    assert_eq!(agent.select_arm(), 1);
+
+    // Naming an arm on an unlabeled agent labels the others by index.
+    assert_eq!(
+        agent.try_add_labeled_arm("1".into()),
+        Err(BanditError::DuplicateLabel("1".into()))
+    );
+    assert_eq!(agent.add_labeled_arm("new".into()), 2);
+    assert_eq!(agent.label(0), Some("0"));
+    assert_eq!(agent.label(2), Some("new"));
+    assert!(agent.try_add_labeled_arm("new".into()).is_err());
+    assert_eq!(agent.counts().len(), 3);
+}
+
+#[test]
+fn test_index_labels_never_duplicate() {
+    let mut agent = EpsilonGreedy::with_labels(0.1, vec!["2".into(), "a".into()]);
+    assert_eq!(agent.add_arm(), 2);
+    assert_eq!(agent.label(2), Some("2_1"));
+}
+
+#[test]
+#[should_panic(expected = "labels must be unique")]
+fn test_duplicate_labels_panic() {
+    EpsilonGreedy::with_labels(0.1, vec!["a".into(), "a".into()]);
+}
+
+#[test]
+#[should_panic(expected = "must have at least one arm")]
+fn test_remove_only_arm_panics() {
+    EpsilonGreedy::new(1, 0.1).remove_arm(0);
+}
+
+#[test]
+fn test_step_size_tracks_drifting_rewards() {
+    let mut averaging = EpsilonGreedy::new(1, 0.0);
+    let mut forgetting = EpsilonGreedy::new(1, 0.0).with_step_size(0.2);
+    assert_eq!(forgetting.step_size(), Some(0.2));
+    assert_eq!(averaging.step_size(), None);
+
+    for step in 0..200 {
+        let reward = if step < 100 { 1.0 } else { 0.0 };
+        averaging.update(0, reward);
+        forgetting.update(0, reward);
+    }
+    assert_relative_eq!(averaging.values()[0], 0.5);
+    // 0.8^100 of the old level remains.
+    assert!(forgetting.values()[0] < 1e-9, "{}", forgetting.values()[0]);
+    assert_eq!(forgetting.counts()[0], 200);
+
+    let mut agent = EpsilonGreedy::new(1, 0.0).with_step_size(0.5);
+    agent.update(0, 4.0);
+    agent.update(0, 8.0);
+    assert_eq!(agent.values()[0], 5.0);
+}
+
+#[test]
+fn test_step_size_has_no_sample_variance() {
+    let mut agent = EpsilonGreedy::new(1, 0.0).with_step_size(1.0);
+    for reward in [1.0, 5.0, -3.0, 10.0] {
+        agent.update(0, reward);
+    }
+    assert_eq!(agent.values()[0], 10.0);
+    assert_eq!(agent.arm_variance(0), None);
+    assert_eq!(
+        agent.arm_confidence_interval(0, 1.96),
+        (f64::NEG_INFINITY, f64::INFINITY)
+    );
+
+    let json = agent.to_json().replace("\"step_size\":1.0", "\"step_size\":1.5");
+    assert!(EpsilonGreedy::from_json(&json).is_err());
+}
+
+#[test]
+#[should_panic(expected = "step size must be in (0.0, 1.0]")]
+fn test_zero_step_size_panics() {
+    EpsilonGreedy::new(2, 0.1).with_step_size(0.0);
+}
+
+#[test]
+fn test_inverse_time_decay_and_set_schedule() {
+    use rustybrain::bandit::epsilon_greedy::DecaySchedule;
+
+    let schedule = DecaySchedule::InverseTime { scale: 50.0, min: 0.01 };
+    assert_eq!(schedule.epsilon_at(0.4, 0), 0.4);
+    assert_relative_eq!(schedule.epsilon_at(0.4, 50), 0.2);
+    assert_relative_eq!(schedule.epsilon_at(0.4, 150), 0.1);
+    assert_eq!(schedule.epsilon_at(0.4, 1_000_000), 0.01);
+
+    let mut agent = EpsilonGreedy::new(2, 0.4);
+    for _ in 0..150 {
+        agent.update(0, 1.0);
+    }
+    assert_eq!(agent.current_epsilon(), 0.4);
+    agent.set_schedule(schedule);
+    assert_eq!(agent.schedule(), schedule);
+    assert_relative_eq!(agent.current_epsilon(), 0.1);
+
+    agent.set_schedule(DecaySchedule::Linear { min: 0.0, steps: 300 });
+    assert_relative_eq!(agent.current_epsilon(), 0.2);
+}
+
+#[test]
+#[should_panic(expected = "decay scale must be positive")]
+fn test_set_schedule_rejects_invalid_scale() {
+    use rustybrain::bandit::epsilon_greedy::DecaySchedule;
+
+    EpsilonGreedy::new(2, 0.4).set_schedule(DecaySchedule::InverseTime { scale: 0.0, min: 0.0 });
+}
@@ -0,0 +1,29 @@
+use rustybrain::bandit::export::to_vw;
+
+/// Parses a `action:cost:probability | ...` line back into its label fields.
+fn parse_label(line: &str) -> (usize, f64, f64) {
+    let (label, features) = line.split_once(" |").expect("missing feature separator");
+    assert!(features.trim().is_empty());
+    let fields: Vec<&str> = label.split(':').collect();
+    assert_eq!(fields.len(), 3, "bad label {:?}", label);
+    (
+        fields[0].parse().unwrap(),
+        fields[1].parse().unwrap(),
+        fields[2].parse().unwrap(),
+    )
+}
+
+#[test]
+fn test_vw_lines_round_trip_action_cost_probability() {
+    let events = [(0, 1.0, 0.8), (1, 0.0, 0.1), (2, 0.3, 0.05)];
+    let log = to_vw(&events);
+
+    let parsed: Vec<_> = log.lines().map(parse_label).collect();
+    assert_eq!(parsed.len(), 3);
+    for (&(arm, reward, p), &(action, cost, prob)) in events.iter().zip(&parsed) {
+        assert_eq!(action, arm + 1);
+        assert!((cost - (1.0 - reward)).abs() < 1e-12);
+        assert_eq!(prob, p);
+    }
+    assert!(to_vw(&[]).is_empty());
+}
@@ -0,0 +1,112 @@
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::bandit::gated::{GateError, GatedBandit};
+use rustybrain::bandit::thompson::ThompsonSampling;
+use rustybrain::bandit::ucb1::Ucb1;
+use rustybrain::bandit::Bandit;
+
+/// Pulls a gated bandit where arm `i` pays `i / 10`, returning pull counts.
+fn run_gated<B: Bandit>(gated: &mut GatedBandit<B>, eligible: &[bool], steps: usize) -> Vec<u64> {
+    let mut counts = vec![0; eligible.len()];
+    for _ in 0..steps {
+        let arm = gated.select_arm_eligible(eligible).unwrap();
+        assert!(eligible[arm], "selected ineligible arm {}", arm);
+        counts[arm] += 1;
+        gated.update(arm, arm as f64 / 10.0);
+    }
+    counts
+}
+
+#[test]
+fn test_gated_never_selects_ineligible_arms() {
+    // The best arm (4) is ineligible; arm 3 is the best eligible one.
+    let eligible = [true, false, true, true, false];
+
+    let mut explorer = GatedBandit::new(EpsilonGreedy::new(5, 0.5));
+    let counts = run_gated(&mut explorer, &eligible, 500);
+    assert_eq!((counts[1], counts[4]), (0, 0));
+    assert!(counts[3] > counts[0] && counts[3] > counts[2], "counts = {:?}", counts);
+
+    let mut ucb = GatedBandit::new(Ucb1::new(5, 0.5));
+    let counts = run_gated(&mut ucb, &eligible, 500);
+    assert_eq!((counts[1], counts[4]), (0, 0));
+    assert_eq!(ucb.inner().most_pulled(), 3);
+
+    let mut thompson = GatedBandit::new(ThompsonSampling::new(5, 3));
+    let counts = run_gated(&mut thompson, &eligible, 500);
+    assert_eq!((counts[1], counts[4]), (0, 0));
+}
+
+#[test]
+fn test_gated_rejects_empty_or_mismatched_mask() {
+    let mut gated = GatedBandit::new(Ucb1::new(3, 1.0));
+    assert_eq!(
+        gated.select_arm_eligible(&[false, false, false]),
+        Err(GateError::NoEligibleArms)
+    );
+    assert_eq!(
+        gated.select_arm_eligible(&[true, true]),
+        Err(GateError::MaskLength {
+            expected: 3,
+            actual: 2
+        })
+    );
+}
+
+#[test]
+fn test_gated_falls_back_to_default_arm() {
+    let mut gated = GatedBandit::new(EpsilonGreedy::new(4, 0.1));
+    for arm in 0..4 {
+        gated.update(arm, arm as f64);
+    }
+    gated.set_default_arm(1);
+    assert_eq!(gated.default_arm(), Some(1));
+
+    for _ in 0..10 {
+        assert_eq!(gated.select_arm_eligible(&[false; 4]), Ok(1));
+    }
+    // Normal selection is unaffected while some arm is eligible.
+    assert_eq!(gated.select_arm_eligible(&[true, false, true, false]), Ok(2));
+    // A malformed mask is still an error.
+    assert!(gated.select_arm_eligible(&[false; 3]).is_err());
+}
+
+/// Minimal strategy relying on the default `select_arm_among`: always picks arm 0.
+struct AlwaysFirst {
+    counts: Vec<u64>,
+    values: Vec<f64>,
+}
+
+impl Bandit for AlwaysFirst {
+    fn select_arm(&mut self) -> usize {
+        0
+    }
+
+    fn update(&mut self, arm: usize, reward: f64) {
+        self.counts[arm] += 1;
+        self.values[arm] += (reward - self.values[arm]) / self.counts[arm] as f64;
+    }
+
+    fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    fn values(&self) -> &[f64] {
+        &self.values
+    }
+}
+
+#[test]
+fn test_gated_uses_default_select_arm_among() {
+    let mut gated = GatedBandit::new(AlwaysFirst {
+        counts: vec![0; 3],
+        values: vec![0.0; 3],
+    });
+    gated.update(1, 0.2);
+    gated.update(2, 0.7);
+
+    // The inner choice is kept while eligible...
+    assert_eq!(gated.select_arm_eligible(&[true, true, true]), Ok(0));
+    // ...and replaced by the best eligible value otherwise.
+    assert_eq!(gated.select_arm_eligible(&[false, true, true]), Ok(2));
+    assert_eq!(gated.select_arm_eligible(&[false, true, false]), Ok(1));
+}
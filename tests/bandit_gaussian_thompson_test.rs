@@ -0,0 +1,83 @@
+use approx::assert_relative_eq;
+use rustybrain::bandit::gaussian_thompson::{GaussianThompson, NormalInverseGamma};
+
+#[test]
+fn test_conjugate_update() {
+    let mut agent = GaussianThompson::new(1, 1);
+    agent.update(0, 2.0);
+    agent.update(0, 4.0);
+    let p = agent.posterior(0);
+    // κ = 1 + 2, μ₀ = (0 + 2 + 4) / 3, α = 1 + 2·½, β = 1 + ½(Σx² − 3μ₀²) = 1 + ½(20 − 12)
+    assert_relative_eq!(p.kappa, 3.0);
+    assert_relative_eq!(p.mean, 2.0);
+    assert_relative_eq!(p.alpha, 2.0);
+    assert_relative_eq!(p.beta, 5.0, epsilon = 1e-12);
+    assert_eq!(agent.counts(), &[2]);
+    assert_relative_eq!(agent.values()[0], 3.0);
+}
+
+#[test]
+fn test_prefers_higher_mean_with_continuous_rewards() {
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, Normal};
+
+    let arms = [Normal::new(10.0, 3.0).unwrap(), Normal::new(12.0, 3.0).unwrap()];
+    let mut rng = StdRng::seed_from_u64(5);
+    let mut agent = GaussianThompson::new(2, 11);
+    for _ in 0..1000 {
+        let arm = agent.select_arm();
+        agent.update(arm, arms[arm].sample(&mut rng));
+    }
+    assert!(agent.counts()[1] > 800, "counts = {:?}", agent.counts());
+    assert!((agent.means()[1] - 12.0).abs() < 0.5);
+}
+
+#[test]
+fn test_seeded_and_resumable() {
+    let mut a = GaussianThompson::new(3, 9);
+    let mut b = GaussianThompson::new(3, 9);
+    let picks_a: Vec<usize> = (0..20).map(|_| a.select_arm()).collect();
+    let picks_b: Vec<usize> = (0..20).map(|_| b.select_arm()).collect();
+    assert_eq!(picks_a, picks_b);
+
+    a.update(1, 5.0);
+    let mut restored = GaussianThompson::from_json(&a.to_json()).unwrap();
+    assert_eq!(restored.posterior(1), a.posterior(1));
+    assert_eq!(restored.select_arm(), a.select_arm());
+    assert!(GaussianThompson::from_json(r#"{"prior":1}"#).is_err());
+}
+
+#[test]
+fn test_with_prior_and_reset() {
+    let prior = NormalInverseGamma { mean: 100.0, kappa: 2.0, alpha: 3.0, beta: 4.0 };
+    let mut agent = GaussianThompson::new(2, 3).with_prior(prior);
+    assert_eq!(agent.posterior(1), prior);
+    agent.update(0, 90.0);
+    agent.reset();
+    assert_eq!(agent.posterior(0), prior);
+    assert_eq!(agent.counts(), &[0, 0]);
+}
+
+#[test]
+#[should_panic(expected = "finite and positive")]
+fn test_with_prior_rejects_non_positive_kappa() {
+    let prior = NormalInverseGamma { kappa: 0.0, ..Default::default() };
+    GaussianThompson::new(2, 3).with_prior(prior);
+}
+
+#[test]
+fn test_overflowing_reward_is_rejected_and_never_panics() {
+    use rustybrain::bandit::BanditError;
+
+    let mut agent = GaussianThompson::new(2, 3);
+    assert_eq!(agent.try_update(0, 1e160), Err(BanditError::RewardOverflow(1e160)));
+    assert_eq!(agent.posterior(0), NormalInverseGamma::default());
+    assert_eq!(agent.counts(), &[0, 0]);
+
+    // The infallible update overflows β; selection falls back to the mean.
+    agent.update(1, 1e160);
+    assert!(agent.posterior(1).beta.is_infinite());
+    for _ in 0..10 {
+        assert_eq!(agent.select_arm(), 1);
+    }
+}
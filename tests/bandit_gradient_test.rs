@@ -0,0 +1,42 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use rustybrain::bandit::gradient::GradientBandit;
+
+#[test]
+fn test_probability_mass_shifts_to_better_arm() {
+    // Unbounded, noisy rewards: arm 2 has the highest mean.
+    let means = [10.0, 12.0, 15.0];
+    let mut agent = GradientBandit::new(3, 0.1, 11);
+    let mut noise = ChaCha12Rng::seed_from_u64(5);
+
+    for _ in 0..2000 {
+        let arm = agent.select_arm();
+        let reward = means[arm] + noise.gen_range(-2.0..2.0);
+        agent.update(arm, reward);
+    }
+
+    let p = agent.probabilities();
+    assert!(p[2] > 0.9, "probabilities = {:?}", p);
+    assert!(agent.counts()[2] > agent.counts()[0] + agent.counts()[1]);
+    assert!((agent.baseline() - 15.0).abs() < 2.0);
+}
+
+#[test]
+fn test_probabilities_always_sum_to_one() {
+    let mut agent = GradientBandit::new(5, 0.5, 3);
+    let mut noise = ChaCha12Rng::seed_from_u64(8);
+
+    let sum: f64 = agent.probabilities().iter().sum();
+    assert!((sum - 1.0).abs() < 1e-12);
+    for step in 0..1000 {
+        let arm = agent.select_arm();
+        // Large, sign-flipping rewards push preferences far apart.
+        let reward = (arm as f64 - 2.0) * 1e3 + noise.gen_range(-50.0..50.0) * step as f64;
+        agent.update(arm, reward);
+
+        let p = agent.probabilities();
+        assert!(p.iter().all(|x| (0.0..=1.0).contains(x)));
+        let sum: f64 = p.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-12, "sum = {} at step {}", sum, step);
+    }
+}
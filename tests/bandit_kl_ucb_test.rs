@@ -0,0 +1,50 @@
+use rustybrain::bandit::kl_ucb::KlUcb;
+use rustybrain::bandit::sim::{simulate, BernoulliEnv};
+use rustybrain::bandit::ucb1::Ucb1;
+
+#[test]
+fn test_lower_regret_than_ucb1_on_bernoulli_arms() {
+    let env = BernoulliEnv::new(vec![0.05, 0.1, 0.15]);
+    for seed in [1, 2, 3] {
+        let kl = simulate(&mut KlUcb::new(3), &env, 5_000, seed);
+        let ucb = simulate(&mut Ucb1::new(3, 1.0), &env, 5_000, seed);
+        assert!(
+            kl.regret < ucb.regret / 2.0,
+            "seed {}: kl-ucb {} vs ucb1 {}",
+            seed,
+            kl.regret,
+            ucb.regret
+        );
+    }
+}
+
+#[test]
+fn test_index_bounds_the_mean_and_shrinks_with_pulls() {
+    let mut agent = KlUcb::new(2);
+    assert_eq!(agent.indices(), vec![1.0, 1.0]);
+    for _ in 0..10 {
+        agent.update(0, 0.5);
+        agent.update(1, 0.5);
+    }
+    let few = agent.indices()[0];
+    for _ in 0..990 {
+        agent.update(0, 0.5);
+    }
+    let indices = agent.indices();
+    assert!(indices[0] > 0.5 && indices[0] < few, "{} vs {}", indices[0], few);
+    assert!(indices[1] > few);
+    assert_eq!(agent.select_arm(), 1);
+}
+
+#[test]
+fn test_clamps_rewards_and_tries_every_arm_first() {
+    let mut agent = KlUcb::new(3);
+    for expected in 0..3 {
+        let arm = agent.select_arm();
+        assert_eq!(arm, expected);
+        agent.update(arm, 5.0);
+    }
+    assert_eq!(agent.values(), &[1.0, 1.0, 1.0]);
+    assert_eq!(agent.counts(), &[1, 1, 1]);
+    assert_eq!(agent.select_arm_among(&[2]), 2);
+}
@@ -0,0 +1,76 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rustybrain::bandit::lints::LinTs;
+use rustybrain::bandit::linucb::LinUcb;
+use rustybrain::bandit::ContextualBandit;
+
+/// Plays `steps` rounds where arm 0 pays for context `[1, 0]` and arm 1 for
+/// `[0, 1]`, returning the fraction of rounds that picked the paying arm.
+fn hit_rate(agent: &mut dyn ContextualBandit, steps: usize, seed: u64) -> f64 {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut hits = 0;
+    for _ in 0..steps {
+        let user = rng.gen_range(0..2);
+        let context = if user == 0 { [1.0, 0.0] } else { [0.0, 1.0] };
+        let arm = agent.select_arm(&context);
+        let reward = if arm == user { 1.0 } else { 0.0 };
+        hits += (arm == user) as usize;
+        agent.update(arm, &context, reward);
+    }
+    hits as f64 / steps as f64
+}
+
+#[test]
+fn test_learns_context_and_swaps_with_linucb() {
+    let mut agents: Vec<Box<dyn ContextualBandit>> = vec![
+        Box::new(LinTs::new(2, 2, 0.5, 1)),
+        Box::new(LinUcb::new(2, 2, 0.5)),
+    ];
+    for agent in &mut agents {
+        let rate = hit_rate(agent.as_mut(), 1000, 3);
+        assert!(rate > 0.85, "hit rate {}", rate);
+        assert_eq!(agent.counts().iter().sum::<u64>(), 1000);
+    }
+}
+
+#[test]
+fn test_shared_model_pools_context_weights() {
+    // Reward is 2·x₀ for every arm, plus 1 for arm 2.
+    let mut agent = LinTs::shared(3, 2, 0.0, 5);
+    assert!(agent.is_shared());
+    for i in 0..60 {
+        let x = [(i % 4) as f64, 1.0];
+        let arm = i % 3;
+        let reward = 2.0 * x[0] + if arm == 2 { 1.0 } else { 0.0 };
+        agent.update(arm, &x, reward);
+    }
+    // Arm 0 only ever saw some contexts, yet predicts from the shared weights.
+    assert!((agent.expected_reward(0, &[5.0, 1.0]) - 10.0).abs() < 0.5);
+    assert!((agent.expected_reward(2, &[1.0, 1.0]) - 3.0).abs() < 0.5);
+    assert_eq!(agent.select_arm(&[1.0, 1.0]), 2);
+}
+
+#[test]
+fn test_seeded_json_round_trip_and_reset() {
+    let mut a = LinTs::new(3, 2, 1.0, 9);
+    let mut b = LinTs::new(3, 2, 1.0, 9);
+    a.update(1, &[1.0, 0.5], 1.0);
+    b.update(1, &[1.0, 0.5], 1.0);
+    let picks_a: Vec<usize> = (0..20).map(|_| a.select_arm(&[0.5, 0.5])).collect();
+    let picks_b: Vec<usize> = (0..20).map(|_| b.select_arm(&[0.5, 0.5])).collect();
+    assert_eq!(picks_a, picks_b);
+
+    let mut restored = LinTs::from_json(&a.to_json()).unwrap();
+    assert_eq!(restored.select_arm(&[0.2, 0.9]), a.select_arm(&[0.2, 0.9]));
+    // A shared model over 2 arms needs (2 + 2)² entries in A⁻¹, not 2².
+    let json = LinTs::shared(2, 2, 1.0, 0).to_json();
+    assert!(LinTs::from_json(&json).is_ok());
+    let mut bad: serde_json::Value = serde_json::from_str(&json).unwrap();
+    bad["a_inv"] = serde_json::json!([[1.0, 0.0, 0.0, 1.0]]);
+    assert!(LinTs::from_json(&bad.to_string()).is_err());
+
+    a.reset();
+    assert_eq!(a.counts(), &[0, 0, 0]);
+    assert_eq!(a.expected_reward(1, &[1.0, 0.5]), 0.0);
+    assert!(a.try_update(0, &[1.0], 1.0).is_err());
+    assert!(a.try_update(3, &[1.0, 0.0], 1.0).is_err());
+}
@@ -0,0 +1,96 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use rustybrain::bandit::linucb::LinUcb;
+
+/// Arm 0 pays off for "mobile" contexts `[1, 0]`, arm 1 for "desktop" `[0, 1]`.
+fn expected_reward(arm: usize, context: &[f64; 2]) -> f64 {
+    match arm {
+        0 => 0.8 * context[0] + 0.2 * context[1],
+        _ => 0.2 * context[0] + 0.8 * context[1],
+    }
+}
+
+#[test]
+fn test_learns_context_dependent_arm() {
+    let mut rng = ChaCha12Rng::seed_from_u64(11);
+    let mut agent = LinUcb::new(2, 2, 0.5);
+    let contexts = [[1.0, 0.0], [0.0, 1.0]];
+
+    for _ in 0..500 {
+        let x = contexts[rng.gen_range(0..2)];
+        let arm = agent.select_arm(&x);
+        let noise: f64 = rng.gen_range(-0.1..0.1);
+        agent.update(arm, &x, expected_reward(arm, &x) + noise);
+    }
+
+    assert_eq!(agent.select_arm(&[1.0, 0.0]), 0);
+    assert_eq!(agent.select_arm(&[0.0, 1.0]), 1);
+    assert!(agent.counts().iter().all(|&n| n > 100), "{:?}", agent.counts());
+    let theta = agent.theta(0);
+    assert!((theta[0] - 0.8).abs() < 0.1, "{:?}", theta);
+}
+
+#[test]
+fn test_update_matches_closed_form() {
+    // One update with x = [1, 2]: A = I + x xᵀ, b = r x, θ = A⁻¹ b = r x / (1 + |x|²).
+    let mut agent = LinUcb::new(1, 2, 0.0);
+    agent.update(0, &[1.0, 2.0], 3.0);
+    let theta = agent.theta(0);
+    assert!((theta[0] - 0.5).abs() < 1e-12 && (theta[1] - 1.0).abs() < 1e-12, "{:?}", theta);
+
+    // With alpha = 0 the score is just θ·x.
+    assert!((agent.scores(&[1.0, 1.0])[0] - 1.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_untried_arms_share_exploration_bonus() {
+    let agent = LinUcb::new(3, 2, 1.0);
+    let scores = agent.scores(&[3.0, 4.0]);
+    assert!(scores.iter().all(|&s| (s - 5.0).abs() < 1e-12), "{:?}", scores);
+}
+
+#[test]
+#[should_panic(expected = "one entry per dimension")]
+fn test_rejects_wrong_context_length() {
+    LinUcb::new(2, 3, 1.0).select_arm(&[1.0]);
+}
+
+#[test]
+fn test_try_update_rejects_invalid_context_and_json_round_trips() {
+    use rustybrain::bandit::BanditError;
+
+    let mut agent = LinUcb::new(2, 2, 1.0);
+    assert_eq!(
+        agent.try_update(0, &[1.0], 1.0),
+        Err(BanditError::InvalidContext { dim: 2 })
+    );
+    assert_eq!(
+        agent.try_update(0, &[1.0, f64::NAN], 1.0),
+        Err(BanditError::InvalidContext { dim: 2 })
+    );
+    assert!(agent.try_update(2, &[1.0, 0.0], 1.0).is_err());
+    agent.try_update(1, &[0.0, 1.0], 0.5).unwrap();
+    agent.try_update(1, &[1.0, 1.0], 1.5).unwrap();
+    assert_eq!(agent.counts(), &[0, 2]);
+    assert_eq!(agent.values(), &[0.0, 1.0]);
+
+    let restored = LinUcb::from_json(&agent.to_json()).unwrap();
+    assert_eq!(restored.theta(1), agent.theta(1));
+    let bad = r#"{"dim":2,"alpha":1.0,"a_inv":[[1.0]],"b":[[0.0,0.0]],"counts":[0],"values":[0.0]}"#;
+    assert!(LinUcb::from_json(bad).is_err());
+
+    agent.reset();
+    assert_eq!(agent.counts(), &[0, 0]);
+    assert_eq!(agent.theta(1), vec![0.0, 0.0]);
+    assert_eq!(agent.scores(&[1.0, 0.0]), vec![1.0, 1.0]);
+}
+
+#[test]
+fn test_select_arms_ranks_by_score() {
+    let mut agent = LinUcb::new(3, 1, 0.0);
+    agent.update(0, &[1.0], 0.2);
+    agent.update(1, &[1.0], 0.8);
+    agent.update(2, &[1.0], 0.5);
+    assert_eq!(agent.select_arms(&[1.0], 2), vec![1, 2]);
+    assert_eq!(agent.select_arms(&[1.0], 5), vec![1, 2, 0]);
+}
@@ -0,0 +1,45 @@
+use rustybrain::bandit::pareto::ParetoBandit;
+
+#[test]
+fn test_trade_off_arms_are_both_on_front() {
+    let mut agent = ParetoBandit::new(2, 2);
+    // Arm 0 is more accurate, arm 1 is faster; neither dominates.
+    agent.update(0, &[0.9, 0.3]);
+    agent.update(1, &[0.7, 0.8]);
+    assert_eq!(agent.pareto_front(), vec![0, 1]);
+}
+
+#[test]
+fn test_dominated_arm_is_excluded_and_never_selected() {
+    let mut agent = ParetoBandit::new(3, 2);
+    agent.update(0, &[0.9, 0.3]);
+    agent.update(1, &[0.7, 0.8]);
+    agent.update(2, &[0.5, 0.2]); // worse than arm 0 on both objectives
+    assert_eq!(agent.pareto_front(), vec![0, 1]);
+
+    let mut seen = [0usize; 3];
+    for _ in 0..200 {
+        seen[agent.select_arm()] += 1;
+    }
+    assert_eq!(seen[2], 0);
+    assert!(seen[0] > 0 && seen[1] > 0, "both front arms should be selected");
+}
+
+#[test]
+fn test_untried_arms_selected_first() {
+    let mut agent = ParetoBandit::new(3, 2);
+    for expected in 0..3 {
+        let arm = agent.select_arm();
+        assert_eq!(arm, expected);
+        agent.update(arm, &[1.0, 1.0]);
+    }
+}
+
+#[test]
+fn test_update_keeps_running_mean_vector() {
+    let mut agent = ParetoBandit::new(1, 2);
+    agent.update(0, &[1.0, 4.0]);
+    agent.update(0, &[3.0, 2.0]);
+    assert_eq!(agent.means()[0], vec![2.0, 3.0]);
+    assert_eq!(agent.counts()[0], 2);
+}
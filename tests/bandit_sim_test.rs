@@ -0,0 +1,132 @@
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use rustybrain::bandit::sim::{lai_robbins_bound, simulate, simulate_cancellable, BernoulliEnv};
+use rustybrain::bandit::ucb1::Ucb1;
+
+#[test]
+fn test_ucb1_regret_is_logarithmic_and_above_lower_bound() {
+    let env = BernoulliEnv::new(vec![0.3, 0.5, 0.7]);
+    let mut agent = Ucb1::new(3, 1.0);
+    let result = simulate(&mut agent, &env, 10_000, 11);
+
+    let at_1k = result.regret_curve[999];
+    let at_10k = result.regret;
+    // Linear growth would multiply regret by 10; logarithmic growth stays far below that.
+    assert!(at_10k < 3.0 * at_1k, "regret grew too fast: {} -> {}", at_1k, at_10k);
+
+    assert!(at_1k > lai_robbins_bound(env.means(), 1_000));
+    assert!(at_10k > result.lower_bound);
+    assert!(result.lower_bound > 0.0);
+}
+
+#[test]
+fn test_lower_bound_zero_for_single_arm_or_tiny_horizon() {
+    assert_eq!(lai_robbins_bound(&[0.5], 1_000), 0.0);
+    assert_eq!(lai_robbins_bound(&[0.2, 0.8], 1), 0.0);
+}
+
+#[test]
+fn test_simulation_is_deterministic() {
+    let env = BernoulliEnv::new(vec![0.4, 0.6]);
+    let a = simulate(&mut EpsilonGreedy::new(2, 0.1), &env, 500, 3);
+    let b = simulate(&mut EpsilonGreedy::new(2, 0.1), &env, 500, 3);
+    assert_eq!(a.total_reward, b.total_reward);
+    assert_eq!(a.regret_curve, b.regret_curve);
+}
+
+#[test]
+fn test_ucb1_regret_grows_with_delay_but_finds_best_arm() {
+    use rustybrain::bandit::sim::{delay_impact, simulate_delayed};
+
+    let env = BernoulliEnv::new(vec![0.3, 0.5, 0.7]);
+    let short = delay_impact(|| Ucb1::new(3, 1.0), &env, 5_000, 23, 10);
+    let long = delay_impact(|| Ucb1::new(3, 1.0), &env, 5_000, 23, 50);
+
+    assert!(short.extra_regret() > 0.0);
+    assert!(long.delayed.regret > short.delayed.regret);
+    assert_eq!(short.baseline.regret, long.baseline.regret);
+
+    let mut agent = Ucb1::new(3, 1.0);
+    simulate_delayed(&mut agent, &env, 5_000, 23, 50);
+    let counts = agent.counts();
+    assert!(counts[2] > counts[0] && counts[2] > counts[1], "counts = {:?}", counts);
+}
+
+#[test]
+fn test_zero_delay_matches_simulate() {
+    use rustybrain::bandit::sim::simulate_delayed;
+
+    let env = BernoulliEnv::new(vec![0.4, 0.6]);
+    let a = simulate(&mut Ucb1::new(2, 1.0), &env, 300, 9);
+    let b = simulate_delayed(&mut Ucb1::new(2, 1.0), &env, 300, 9, 0);
+    assert_eq!(a.regret_curve, b.regret_curve);
+}
+
+#[test]
+fn test_cancelled_simulation_returns_partial_result() {
+    let env = BernoulliEnv::new(vec![0.2, 0.8]);
+    let mut agent = EpsilonGreedy::new(2, 0.1);
+    let stop = AtomicBool::new(false);
+
+    let started = Instant::now();
+    let result = std::thread::scope(|scope| {
+        scope.spawn(|| {
+            std::thread::sleep(Duration::from_millis(50));
+            stop.store(true, Ordering::Relaxed);
+        });
+        // Would take far longer than the test timeout without cancellation.
+        simulate_cancellable(&mut agent, &env, usize::MAX, 3, &stop)
+    });
+
+    assert!(started.elapsed() < Duration::from_secs(5));
+    assert!(result.steps > 0 && result.steps < usize::MAX);
+    assert_eq!(result.regret_curve.len(), result.steps);
+    assert_eq!(agent.counts().iter().sum::<u64>(), result.steps as u64);
+}
+
+#[test]
+fn test_preset_stop_flag_runs_no_steps() {
+    let env = BernoulliEnv::new(vec![0.5]);
+    let mut agent = Ucb1::new(1, 1.0);
+    let result = simulate_cancellable(&mut agent, &env, 1_000, 1, &AtomicBool::new(true));
+    assert_eq!(result.steps, 0);
+    assert_eq!(result.regret, 0.0);
+}
+
+#[test]
+fn test_run_simulation_greedy_beats_pure_exploration() {
+    use rustybrain::bandit::sim::{bernoulli_arm, gaussian_arm, run_simulation, SimArm};
+
+    let arms = || -> Vec<SimArm> {
+        vec![bernoulli_arm(0.2), gaussian_arm(0.5, 0.1), bernoulli_arm(0.8)]
+    };
+    let mut greedy = EpsilonGreedy::new(3, 0.05);
+    let mut explorer = EpsilonGreedy::new(3, 1.0);
+    let greedy_report = run_simulation(&mut greedy, &arms(), 10_000, 3);
+    let explore_report = run_simulation(&mut explorer, &arms(), 10_000, 3);
+
+    for report in [&greedy_report, &explore_report] {
+        assert_eq!(report.steps, 10_000);
+        assert_eq!(report.pulls.iter().sum::<u64>(), 10_000);
+    }
+    assert!(greedy_report.pulls[2] > 9_000, "{:?}", greedy_report.pulls);
+    assert!(
+        greedy_report.regret(0.8) < explore_report.regret(0.8) / 5.0,
+        "greedy {} vs explore {}",
+        greedy_report.regret(0.8),
+        explore_report.regret(0.8)
+    );
+}
+
+#[test]
+fn test_run_simulation_is_reproducible() {
+    use rustybrain::bandit::sim::{gaussian_arm, run_simulation, SimArm};
+
+    let arms: Vec<SimArm> = vec![gaussian_arm(1.0, 0.5), gaussian_arm(2.0, 0.5)];
+    let a = run_simulation(&mut Ucb1::new(2, 1.0), &arms, 500, 9);
+    let b = run_simulation(&mut Ucb1::new(2, 1.0), &arms, 500, 9);
+    assert_eq!(a.pulls, b.pulls);
+    assert_eq!(a.cumulative_reward, b.cumulative_reward);
+}
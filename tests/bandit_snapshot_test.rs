@@ -0,0 +1,57 @@
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::bandit::gaussian_thompson::GaussianThompson;
+use rustybrain::bandit::linucb::LinUcb;
+use rustybrain::bandit::thompson::ThompsonSampling;
+use rustybrain::bandit::ucb1::Ucb1;
+
+#[test]
+fn test_snapshot_is_detached_copy() {
+    let mut agent = Ucb1::new(2, 2.0);
+    agent.update(0, 1.0);
+    agent.update(1, 0.5);
+
+    let snap = agent.snapshot();
+    assert_eq!(snap.counts, agent.counts());
+    assert_eq!(snap.values, agent.values());
+
+    agent.update(0, 3.0);
+    assert_eq!(snap.counts, vec![1, 1]);
+    assert_eq!(snap.values, vec![1.0, 0.5]);
+    assert_ne!(snap.values, agent.values());
+}
+
+#[test]
+fn test_epsilon_greedy_snapshot_matches_accessors() {
+    let mut agent = EpsilonGreedy::new(3, 0.1);
+    agent.update(2, 4.0);
+    let snap = agent.snapshot();
+    assert_eq!(snap.counts, vec![0, 0, 1]);
+    assert_eq!(snap.values, vec![0.0, 0.0, 4.0]);
+}
+
+#[test]
+fn test_every_service_strategy_snapshot_is_detached_copy() {
+    let mut thompson = ThompsonSampling::new(2, 7);
+    let mut gaussian = GaussianThompson::new(2, 7);
+    let mut linucb = LinUcb::new(2, 1, 1.0);
+    thompson.update(1, 1.0);
+    gaussian.update(1, 3.0);
+    linucb.update(1, &[1.0], 2.0);
+
+    let snaps = [thompson.snapshot(), gaussian.snapshot(), linucb.snapshot()];
+    assert_eq!(snaps[0].counts, thompson.counts());
+    assert_eq!(snaps[0].values, thompson.values());
+    assert_eq!(snaps[1].counts, gaussian.counts());
+    assert_eq!(snaps[1].values, gaussian.values());
+    assert_eq!(snaps[2].counts, linucb.counts());
+    assert_eq!(snaps[2].values, linucb.values());
+
+    thompson.update(1, 0.0);
+    gaussian.update(1, 5.0);
+    linucb.update(1, &[1.0], 4.0);
+    for snap in &snaps {
+        assert_eq!(snap.counts, vec![0, 1]);
+    }
+    assert_ne!(snaps[1].values, gaussian.values());
+    assert_ne!(snaps[2].values, linucb.values());
+}
@@ -0,0 +1,110 @@
+use rustybrain::bandit::sw_ucb::SlidingWindowUcb;
+use rustybrain::bandit::ucb1::Ucb1;
+use rustybrain::bandit::{Bandit, DiscountedUcb, Ucb1Windowed};
+
+/// Arm 0 pays 1.0 until `collapse`, then 0.0; arm 1 always pays 0.5.
+/// Returns how often arm 0 was pulled in the `after` steps following the
+/// first `window` steps after the collapse.
+fn pulls_after_collapse<B: Bandit>(
+    agent: &mut B,
+    collapse: usize,
+    window: usize,
+    after: usize,
+) -> usize {
+    let mut late_pulls = 0;
+    for step in 0..collapse + window + after {
+        let arm = agent.select_arm();
+        let reward = match arm {
+            0 if step < collapse => 1.0,
+            0 => 0.0,
+            _ => 0.5,
+        };
+        agent.update(arm, reward);
+        if arm == 0 && step >= collapse + window {
+            late_pulls += 1;
+        }
+    }
+    late_pulls
+}
+
+#[test]
+fn test_abandons_collapsed_arm_within_window() {
+    let window = 100;
+    let mut agent = SlidingWindowUcb::new(2, 1.0, window);
+    let windowed = pulls_after_collapse(&mut agent, 1000, window, 400);
+    let vanilla = pulls_after_collapse(&mut Ucb1::new(2, 1.0), 1000, window, 400);
+    assert!(windowed < 100, "sliding window kept pulling the collapsed arm: {}", windowed);
+    assert!(vanilla > 200, "vanilla UCB1 unexpectedly adapted: {}", vanilla);
+}
+
+#[test]
+fn test_window_evicts_oldest_pulls_across_arms() {
+    let mut agent = SlidingWindowUcb::new(2, 1.0, 3);
+    agent.update(0, 1.0);
+    agent.update(0, 3.0);
+    agent.update(1, 5.0);
+    assert_eq!(agent.counts(), &[2, 1]);
+    assert_eq!(agent.values(), &[2.0, 5.0]);
+
+    // The fourth pull pushes out arm 0's first reward.
+    agent.update(1, 7.0);
+    assert_eq!(agent.counts(), &[1, 2]);
+    assert_eq!(agent.values(), &[3.0, 6.0]);
+
+    // Arm 0 leaves the window entirely and becomes untried again.
+    agent.update(1, 7.0);
+    assert_eq!(agent.counts(), &[0, 3]);
+    assert_eq!(agent.values()[0], 0.0);
+    assert_eq!(agent.select_arm(), 0);
+}
+
+#[test]
+fn test_per_arm_window_recovers_after_collapse() {
+    let window = 50;
+    let mut agent = SlidingWindowUcb::per_arm(2, 1.0, window);
+    assert!(agent.is_per_arm());
+    let windowed = pulls_after_collapse(&mut agent, 1000, 2 * window, 400);
+    let vanilla = pulls_after_collapse(&mut Ucb1::new(2, 1.0), 1000, 2 * window, 400);
+    assert!(windowed < 100, "per-arm window kept pulling the collapsed arm: {}", windowed);
+    assert!(windowed * 2 < vanilla, "per-arm {} vs vanilla {}", windowed, vanilla);
+}
+
+#[test]
+fn test_per_arm_window_keeps_each_arms_recent_rewards() {
+    let mut agent = SlidingWindowUcb::per_arm(2, 1.0, 2);
+    agent.update(0, 1.0);
+    for _ in 0..5 {
+        agent.update(1, 2.0);
+    }
+    agent.update(1, 4.0);
+    // Arm 0 is never evicted by arm 1's pulls; arm 1 keeps only its last two.
+    assert_eq!(agent.counts(), &[1, 2]);
+    assert_eq!(agent.values(), &[1.0, 3.0]);
+}
+
+#[test]
+fn test_ucb1_windowed_recovers_after_collapse() {
+    let mut agent = Ucb1Windowed::new(2, 1.0, 50);
+    assert_eq!(agent.window(), 50);
+    assert!(agent.inner().is_per_arm());
+    let vanilla = pulls_after_collapse(&mut Ucb1::new(2, 1.0), 1000, 100, 400);
+    let windowed = pulls_after_collapse(&mut agent, 1000, 100, 400);
+    assert!(windowed < 100, "windowed agent kept pulling the collapsed arm: {}", windowed);
+    assert!(windowed * 2 < vanilla, "windowed {} vs vanilla {}", windowed, vanilla);
+}
+
+#[test]
+fn test_discounted_ucb_recovers_after_collapse() {
+    let mut agent = DiscountedUcb::new(2, 1.0, 0.98);
+    assert_eq!(agent.gamma(), 0.98);
+    let vanilla = pulls_after_collapse(&mut Ucb1::new(2, 1.0), 1000, 100, 400);
+    let discounted = pulls_after_collapse(&mut agent, 1000, 100, 400);
+    assert!(discounted < 100, "discounted agent kept pulling the collapsed arm: {}", discounted);
+    assert!(discounted * 2 < vanilla, "discounted {} vs vanilla {}", discounted, vanilla);
+}
+
+#[test]
+#[should_panic(expected = "gamma")]
+fn test_discounted_ucb_requires_valid_gamma() {
+    DiscountedUcb::new(2, 1.0, 1.5);
+}
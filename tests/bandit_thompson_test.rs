@@ -0,0 +1,66 @@
+use rustybrain::bandit::thompson::ThompsonSampling;
+
+#[test]
+fn test_probability_best_favors_well_supported_arm() {
+    let mut agent = ThompsonSampling::new(2, 42);
+    for _ in 0..50 {
+        agent.update(0, 1.0);
+    }
+    for _ in 0..5 {
+        agent.update(1, 1.0);
+        agent.update(1, 0.0);
+    }
+
+    let p = agent.probability_best(2000);
+    assert!(p[0] > 0.99, "expected arm 0 to be near-certainly best, got {:?}", p);
+    assert!((p.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_probability_best_is_reproducible_with_seed() {
+    let mut a = ThompsonSampling::new(3, 9);
+    let mut b = ThompsonSampling::new(3, 9);
+    a.update(1, 1.0);
+    b.update(1, 1.0);
+    assert_eq!(a.probability_best(500), b.probability_best(500));
+}
+
+#[test]
+fn test_probability_best_uniform_prior_is_balanced() {
+    let mut agent = ThompsonSampling::new(2, 3);
+    let p = agent.probability_best(4000);
+    assert!((p[0] - 0.5).abs() < 0.05, "got {:?}", p);
+    assert_eq!(agent.probability_best(0), vec![0.0, 0.0]);
+}
+
+#[test]
+fn test_better_arm_selected_more_often_over_1000_pulls() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    let means = [0.2, 0.5, 0.8];
+    let mut agent = ThompsonSampling::new(3, 42);
+    let mut rng = StdRng::seed_from_u64(1);
+    for _ in 0..1000 {
+        let arm = agent.select_arm();
+        let reward = if rng.gen::<f64>() < means[arm] { 1.0 } else { 0.0 };
+        agent.update(arm, reward);
+    }
+
+    let counts = agent.counts();
+    assert_eq!(counts.iter().sum::<u64>(), 1000);
+    assert!(counts[2] > 700, "counts = {:?}", counts);
+    assert!((agent.values()[2] - 0.8).abs() < 0.1, "values = {:?}", agent.values());
+}
+
+#[test]
+fn test_reset_restores_uniform_prior() {
+    let mut agent = ThompsonSampling::new(2, 3);
+    for _ in 0..20 {
+        agent.update(0, 1.0);
+    }
+    agent.reset();
+    assert_eq!(agent.counts(), &[0, 0]);
+    assert_eq!(agent.values(), &[0.0, 0.0]);
+    let p = agent.probability_best(2000);
+    assert!((p[0] - 0.5).abs() < 0.05, "{:?}", p);
+}
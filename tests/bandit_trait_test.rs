@@ -0,0 +1,95 @@
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::bandit::ucb1::Ucb1;
+use rustybrain::bandit::Bandit;
+
+fn feed(bandit: &mut dyn Bandit) {
+    bandit.update(0, 1.0);
+    bandit.update(1, 4.0);
+    bandit.update(1, 2.0);
+    bandit.update(2, -1.0);
+    bandit.update(1, 3.0);
+}
+
+#[test]
+fn test_default_methods_match_manual_computation() {
+    let mut agents: Vec<Box<dyn Bandit>> =
+        vec![Box::new(EpsilonGreedy::new(3, 0.1)), Box::new(Ucb1::new(3, 2.0))];
+
+    for agent in agents.iter_mut() {
+        feed(agent.as_mut());
+        let bandit: &dyn Bandit = agent.as_ref();
+
+        let manual_best = bandit.values().iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let manual_worst = bandit.values().iter().cloned().fold(f64::INFINITY, f64::min);
+        assert_eq!(bandit.best_value(), manual_best);
+        assert_eq!(bandit.worst_value(), manual_worst);
+        assert_eq!(bandit.best_value(), 3.0);
+        assert_eq!(bandit.worst_value(), -1.0);
+        assert_eq!(bandit.most_pulled(), 1);
+    }
+}
+
+#[test]
+fn test_most_pulled_prefers_lowest_index_on_tie() {
+    let agent = Ucb1::new(3, 2.0);
+    let bandit: &dyn Bandit = &agent;
+    assert_eq!(bandit.most_pulled(), 0);
+    assert_eq!(bandit.best_value(), 0.0);
+}
+
+#[test]
+fn bandit_policy_objects_share_one_interface() {
+    use rustybrain::bandit::linucb::LinUcb;
+    use rustybrain::bandit::thompson::ThompsonSampling;
+    use rustybrain::bandit::{BanditError, BanditPolicy};
+
+    let mut policies: Vec<Box<dyn BanditPolicy>> = vec![
+        Box::new(EpsilonGreedy::new(3, 0.0)),
+        Box::new(Ucb1::new(3, 1.0)),
+        Box::new(ThompsonSampling::new(3, 5)),
+        Box::new(LinUcb::new(3, 2, 1.0)),
+    ];
+    let context = [1.0, 0.5];
+    for policy in &mut policies {
+        let arm = policy.select_arm(Some(&context)).unwrap();
+        policy.try_update(arm, 1.0, Some(&context)).unwrap();
+        let snapshot = policy.snapshot();
+        assert_eq!(snapshot.counts.iter().sum::<u64>(), 1);
+        assert_eq!(snapshot.counts, policy.counts());
+        assert_eq!(snapshot.values, policy.values());
+        // Copies carry the learned state.
+        assert_eq!(policy.clone().snapshot(), snapshot);
+    }
+
+    // Only the contextual policy needs a context, and it reports a missing
+    // one as an error rather than panicking.
+    let linucb = &mut policies[3];
+    assert!(linucb.as_bandit_mut().is_none());
+    assert_eq!(linucb.select_arm(None), Err(BanditError::InvalidContext { dim: 2 }));
+    assert_eq!(linucb.try_update(0, 1.0, None), Err(BanditError::InvalidContext { dim: 2 }));
+    assert!(policies[0].select_arm(None).is_ok());
+
+    // Optional features default to unsupported.
+    assert_eq!(policies[0].current_epsilon(), Some(0.0));
+    assert_eq!(policies[2].current_epsilon(), None);
+    assert!(policies[1].bonuses().is_some());
+    assert!(policies[2].add_arm(None).is_none());
+    assert!(!policies[3].remove_arm(0));
+}
+
+#[test]
+fn select_arms_returns_distinct_ranked_slate() {
+    let mut agent = Ucb1::new(4, 0.0);
+    for (arm, reward) in [(0, 0.1), (1, 0.9), (2, 0.5), (3, 0.7)] {
+        agent.update(arm, reward);
+    }
+    assert_eq!(Bandit::select_arms(&mut agent, 3), vec![1, 3, 2]);
+    // Asking for more arms than exist returns all of them.
+    assert_eq!(Bandit::select_arms(&mut agent, 10).len(), 4);
+
+    // With full exploration the slate is still made of distinct arms.
+    let mut explorer = EpsilonGreedy::new(5, 1.0);
+    let mut slate = explorer.select_arms(5);
+    slate.sort();
+    assert_eq!(slate, vec![0, 1, 2, 3, 4]);
+}
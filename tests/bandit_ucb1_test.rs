@@ -5,7 +5,7 @@ use approx::assert_relative_eq;
 fn test_initial_selection_cycles_through_arms() {
     let mut agent = Ucb1::new(3, 2.0);
     // Each time we select an arm, mark it as pulled.
-    let mut seen = vec![false; 3];
+    let mut seen = [false; 3];
     for _ in 0..3 {
         let arm = agent.select_arm();
         seen[arm] = true;
@@ -47,4 +47,306 @@ fn test_average_updates_correctly() {
     agent.update(0, 3.0);
     assert_relative_eq!(agent.values()[0], 2.0, epsilon = 1e-12);
     assert_eq!(agent.counts()[0], 2);
-}
\ No newline at end of file
+}
+
+
+#[test]
+fn test_with_range_keeps_exploring_large_rewards() {
+    let mut ranged = Ucb1::with_range(2, 1.0, 0.0, 100.0);
+    let mut plain = Ucb1::new(2, 1.0);
+    for agent in [&mut ranged, &mut plain] {
+        for _ in 0..200 {
+            let arm = agent.select_arm();
+            agent.update(arm, if arm == 0 { 60.0 } else { 50.0 });
+        }
+    }
+    assert_eq!(plain.counts()[1], 1);
+    assert!(ranged.counts()[1] > 10, "counts = {:?}", ranged.counts());
+}
+
+#[test]
+fn test_exploration_priority_orders_untried_arms() {
+    let mut agent = Ucb1::new(4, 2.0);
+    agent.set_exploration_priority(vec![0.1, 0.5, 0.9, 0.3]);
+
+    let mut order = Vec::new();
+    for _ in 0..4 {
+        let arm = agent.select_arm();
+        order.push(arm);
+        agent.update(arm, 0.5);
+    }
+    assert_eq!(order[0], 2);
+    assert_eq!(order, vec![2, 1, 3, 0]);
+    assert!(agent.counts().iter().all(|&c| c == 1));
+}
+
+#[test]
+fn test_try_select_arm_waits_for_warmup() {
+    let mut agent = Ucb1::new(3, 2.0);
+    agent.set_min_pulls(2);
+
+    for (i, arm) in [0, 1, 2, 0, 1, 2].into_iter().enumerate() {
+        assert_eq!(agent.try_select_arm(), None, "step {}", i);
+        agent.update(arm, 0.5);
+    }
+    let counts_before = agent.counts().to_vec();
+    assert!(agent.try_select_arm().is_some());
+    assert!(agent.try_select_arm().is_some());
+    assert_eq!(agent.counts(), &counts_before[..], "try_select_arm must not update");
+}
+
+/// Runs 1000 steps where arm 0 pays 1.0 and arm 1 pays 0.5, then 400 steps
+/// where arm 0 pays nothing, returning how often arm 1 is chosen afterwards.
+fn pulls_after_drift(agent: &mut Ucb1) -> usize {
+    for _ in 0..1000 {
+        let arm = agent.select_arm();
+        agent.update(arm, if arm == 0 { 1.0 } else { 0.5 });
+    }
+    let mut new_best = 0;
+    for _ in 0..400 {
+        let arm = agent.select_arm();
+        agent.update(arm, if arm == 0 { 0.0 } else { 0.5 });
+        new_best += (arm == 1) as usize;
+    }
+    new_best
+}
+
+#[test]
+fn test_discounted_agent_adapts_to_drift() {
+    let mut discounted = Ucb1::with_discount(2, 0.5, 0.99);
+    let mut plain = Ucb1::new(2, 0.5);
+
+    let adapted = pulls_after_drift(&mut discounted);
+    let lagged = pulls_after_drift(&mut plain);
+    assert!(adapted > 300, "discounted agent chose the new best arm {} times", adapted);
+    assert!(lagged < adapted / 2, "undiscounted {} vs discounted {}", lagged, adapted);
+}
+
+#[test]
+fn test_unit_discount_matches_plain_ucb1() {
+    let mut discounted = Ucb1::with_discount(3, 1.5, 1.0);
+    let mut plain = Ucb1::new(3, 1.5);
+    assert_eq!(discounted.gamma(), 1.0);
+    for step in 0..300 {
+        let arm = discounted.select_arm();
+        assert_eq!(arm, plain.select_arm());
+        let reward = ((step * 7 + arm * 3) % 10) as f64 / 10.0;
+        discounted.update(arm, reward);
+        plain.update(arm, reward);
+    }
+    assert_eq!(discounted.values(), plain.values());
+}
+
+#[test]
+fn test_confidence_bounds_favor_rarely_pulled_arm() {
+    let mut agent = Ucb1::new(3, 2.0);
+    for _ in 0..50 {
+        agent.update(0, 0.5);
+    }
+    agent.update(1, 0.5);
+
+    let bounds = agent.confidence_bounds();
+    assert_eq!(bounds.len(), 3);
+    assert_eq!(bounds[0].0, 0.5);
+    assert!(bounds[1].1 > bounds[0].1, "bounds = {:?}", bounds);
+    assert_eq!(bounds[2], (0.0, 0.0));
+    // The bonus matches the UCB1 formula with t = 51.
+    assert_relative_eq!(bounds[1].1, 2.0 * (2.0 * 51f64.ln()).sqrt(), epsilon = 1e-12);
+}
+
+#[test]
+fn test_with_priors_skips_exploration_of_seeded_arms() {
+    let mut agent = Ucb1::with_priors(1.0, vec![(0.1, 10), (0.9, 100)]);
+    // Both arms have history, so the better-supported arm wins immediately.
+    assert_eq!(agent.select_arm(), 1);
+
+    agent.update(0, 1.0);
+    assert_eq!(agent.counts()[0], 11);
+    assert_relative_eq!(agent.values()[0], (0.1 * 10.0 + 1.0) / 11.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_with_priors_variance_ignores_seeded_counts() {
+    let mut agent = Ucb1::with_priors(1.0, vec![(0.7, 1000)]);
+    assert_eq!(agent.arm_variance(0), None);
+    assert_eq!(agent.arm_confidence_interval(0, 1.96), (f64::NEG_INFINITY, f64::INFINITY));
+
+    agent.update(0, 0.0);
+    agent.update(0, 1.0);
+    assert_relative_eq!(agent.arm_variance(0).unwrap(), 0.5, epsilon = 1e-12);
+    let (lo, hi) = agent.arm_confidence_interval(0, 1.0);
+    assert_relative_eq!(lo, 0.0, epsilon = 1e-12);
+    assert_relative_eq!(hi, 1.0, epsilon = 1e-12);
+}
+
+#[test]
+#[should_panic(expected = "prior counts")]
+fn test_with_priors_rejects_huge_count() {
+    Ucb1::with_priors(1.0, vec![(0.5, u64::MAX)]);
+}
+
+#[test]
+fn test_try_with_prior_stats_validates_lengths() {
+    use rustybrain::bandit::BanditError;
+
+    let agent = Ucb1::try_with_prior_stats(1.0, &[10, 100], &[0.1, 0.9]).unwrap();
+    assert_eq!(agent.counts(), &[10, 100]);
+    assert!(matches!(
+        Ucb1::try_with_prior_stats(1.0, &[10, 100], &[0.1]),
+        Err(BanditError::InvalidPriors(_))
+    ));
+    assert!(matches!(
+        Ucb1::try_with_prior_stats(1.0, &[1], &[f64::INFINITY]),
+        Err(BanditError::InvalidPriors(_))
+    ));
+}
+
+/// Pulls for 300 steps where arm 0 always pays 0.7 and arm 1 alternates
+/// between 0.0 and 1.0, returning how often arm 0 was chosen.
+fn steady_arm_pulls(agent: &mut Ucb1) -> u64 {
+    let mut flip = false;
+    for _ in 0..300 {
+        let arm = agent.select_arm();
+        let reward = if arm == 0 {
+            0.7
+        } else {
+            flip = !flip;
+            if flip { 1.0 } else { 0.0 }
+        };
+        agent.update(arm, reward);
+    }
+    agent.counts()[0]
+}
+
+#[test]
+fn test_tuned_exploits_low_variance_arm_sooner() {
+    let vanilla = steady_arm_pulls(&mut Ucb1::new(2, 1.0));
+    let tuned = steady_arm_pulls(&mut Ucb1::new_tuned(2, 1.0));
+    assert!(tuned > vanilla, "tuned {} vs vanilla {}", tuned, vanilla);
+    assert!(tuned > 250, "tuned pulled the steady arm {} times", tuned);
+}
+
+#[test]
+fn test_try_update_rejects_bad_input() {
+    use rustybrain::bandit::BanditError;
+
+    let mut agent = Ucb1::new(3, 1.0);
+    assert_eq!(
+        agent.try_update(7, 1.0),
+        Err(BanditError::ArmOutOfRange { arm: 7, num_arms: 3 })
+    );
+    assert!(matches!(
+        agent.try_update(0, f64::INFINITY),
+        Err(BanditError::NonFiniteReward(_))
+    ));
+    assert_eq!(agent.counts(), &[0, 0, 0]);
+}
+
+#[test]
+fn test_labels() {
+    let agent = Ucb1::with_labels(1.0, vec!["control".into(), "variant_a".into()]);
+    assert_eq!(agent.counts().len(), 2);
+    let arm = agent.select_arm();
+    assert_eq!(agent.label(arm), Some(["control", "variant_a"][arm]));
+    assert_eq!(Ucb1::new(2, 1.0).label(0), None);
+}
+
+#[test]
+fn test_update_by_label() {
+    let mut agent = Ucb1::with_labels(0.0, vec!["control".into(), "variant".into()]);
+    agent.update_labeled("control", 0.2).unwrap();
+    agent.update_labeled("variant", 0.9).unwrap();
+    assert_eq!(agent.select_labeled(), (1, Some("variant")));
+    assert!(agent.update_labeled("other", 1.0).is_err());
+    assert_eq!(agent.counts(), &[1, 1]);
+}
+
+#[test]
+#[should_panic(expected = "one entry per arm")]
+fn test_labels_must_match_arms() {
+    Ucb1::new(3, 1.0).set_labels(vec!["a".into()]);
+}
+
+#[test]
+fn test_tie_break() {
+    use rustybrain::bandit::TieBreak;
+
+    // Untried arms tie, so the first pick is decided by the rule.
+    assert_eq!(Ucb1::new(4, 1.0).select_arm(), 0);
+
+    let mut agent = Ucb1::new(4, 1.0);
+    agent.set_tie_break(TieBreak::Highest);
+    assert_eq!(agent.select_arm(), 3);
+
+    // Equal pulls and means tie on score once every arm was tried.
+    let mut agent = Ucb1::new(4, 1.0);
+    for arm in 0..4 {
+        agent.update(arm, 0.5);
+    }
+    assert!((0..100).all(|_| agent.select_arm() == 0));
+
+    agent.set_tie_break(TieBreak::Random);
+    let mut seen = [0usize; 4];
+    for _ in 0..400 {
+        seen[agent.select_arm()] += 1;
+    }
+    assert!(seen.iter().all(|&n| n > 50), "{:?}", seen);
+}
+
+#[test]
+fn test_reset_replays_from_scratch() {
+    let run = |agent: &mut Ucb1| {
+        (0..30)
+            .map(|_| {
+                let arm = agent.select_arm();
+                agent.update(arm, arm as f64 / 2.0);
+                arm
+            })
+            .collect::<Vec<_>>()
+    };
+    let mut agent = Ucb1::with_labels(1.5, vec!["a".into(), "b".into(), "c".into()]);
+    let first = run(&mut agent);
+    agent.reset();
+    assert_eq!(agent.counts(), &[0, 0, 0]);
+    assert_eq!(agent.values(), &[0.0, 0.0, 0.0]);
+    assert_eq!(agent.label(2), Some("c"));
+
+    assert_eq!(run(&mut agent), first);
+    assert_eq!(run(&mut Ucb1::new(3, 1.5)), first);
+}
+
+#[test]
+fn test_reset_clears_discounted_state() {
+    let mut agent = Ucb1::with_discount(2, 1.0, 0.9);
+    for _ in 0..10 {
+        let arm = agent.select_arm();
+        agent.update(arm, 1.0);
+    }
+    agent.reset();
+    assert_eq!(agent.confidence_bounds(), vec![(0.0, 0.0); 2]);
+    assert_eq!(agent.gamma(), 0.9);
+}
+
+#[test]
+fn test_add_and_remove_arms_keep_statistics() {
+    let mut agent = Ucb1::with_discount(2, 1.0, 0.9).with_arm_history(5);
+    agent.set_cooldown(1);
+    agent.set_labels(vec!["a".into(), "b".into()]);
+    agent.update(0, 0.2);
+    agent.update(1, 0.8);
+
+    // The new arm is untried, so it is selected next.
+    assert_eq!(agent.add_arm(), 2);
+    assert_eq!(agent.label(2), Some("2"));
+    assert_eq!(agent.select_arm_mut(), 2);
+    agent.update(2, 0.5);
+    assert_eq!(agent.arm_stats(2), Some((0.5, 0.5, 0.5, 1)));
+
+    agent.remove_arm(1);
+    assert_eq!(agent.counts(), &[1, 1]);
+    assert_eq!(agent.values(), &[0.2, 0.5]);
+    assert_eq!(agent.label(1), Some("2"));
+    assert_eq!(agent.confidence_bounds().len(), 2);
+    // Arm 1 (formerly 2) is still cooling down.
+    assert_eq!(agent.select_arm(), 0);
+}
@@ -0,0 +1,34 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rustybrain::metrics::change_point;
+use rustybrain::metrics::reward_tracker::RewardTracker;
+
+/// Fills a window of `n` noisy rewards whose level jumps by `shift` at `at`.
+fn shifted_series(n: usize, at: usize, shift: f64, seed: u64) -> RewardTracker {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut tracker = RewardTracker::new(n);
+    for i in 0..n {
+        let level = if i < at { 0.5 } else { 0.5 + shift };
+        tracker.update(level + rng.gen_range(-0.2..0.2));
+    }
+    tracker
+}
+
+#[test]
+fn test_detects_mid_window_level_change() {
+    let tracker = shifted_series(100, 60, 0.4, 1);
+    let detected = change_point(&tracker).expect("shift should be detected");
+    assert!((55..=65).contains(&detected), "detected at {}", detected);
+}
+
+#[test]
+fn test_stable_series_has_no_change_point() {
+    let tracker = shifted_series(100, 100, 0.0, 2);
+    assert_eq!(change_point(&tracker), None);
+
+    let mut flat = RewardTracker::new(10);
+    for _ in 0..10 {
+        flat.update(1.0);
+    }
+    assert_eq!(change_point(&flat), None);
+    assert_eq!(change_point(&RewardTracker::new(10)), None);
+}
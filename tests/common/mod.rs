@@ -0,0 +1,87 @@
+//! Shared helpers for the REST integration tests.
+#![allow(dead_code)]
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+    Router,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tower::ServiceExt; // for `oneshot`
+
+/// Sends a JSON POST to `uri` and returns the status and parsed body
+/// (`Value::Null` when the body is empty or not JSON).
+pub async fn post_json(app: &Router, uri: &str, body: Value) -> (StatusCode, Value) {
+    let req = Request::post(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    send(app, req).await
+}
+
+/// Sends a GET to `uri` and returns the status and parsed body.
+pub async fn get_json(app: &Router, uri: &str) -> (StatusCode, Value) {
+    send(app, Request::get(uri).body(Body::empty()).unwrap()).await
+}
+
+async fn send(app: &Router, req: Request<Body>) -> (StatusCode, Value) {
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let status = resp.status();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+}
+
+/// Mirror of the `/bandit/:id/stats` response.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct StatsResp {
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+    pub count: usize,
+}
+
+/// Creates a bandit through `app`, runs `steps` select/update rounds against
+/// a seeded Bernoulli environment, and returns the final stats.
+///
+/// Arm `i` pays `1.0` with probability `(i + 1) / (arms + 1)`, so the last
+/// arm is the best. The same `seed` always yields the same stats.
+pub async fn run_experiment(
+    app: &Router,
+    strategy: &str,
+    arms: usize,
+    steps: usize,
+    seed: u64,
+) -> StatsResp {
+    let param = match strategy {
+        "epsilon_greedy" => 0.1,
+        _ => 1.0,
+    };
+    let (status, v) = post_json(
+        app,
+        "/",
+        json!({"strategy": strategy, "param": param, "num_arms": arms}),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK, "create failed: {}", v);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    for _ in 0..steps {
+        let (_, v) = get_json(app, &format!("/{}/select", id)).await;
+        let arm = v["arm"].as_u64().unwrap();
+        let p = (arm + 1) as f64 / (arms + 1) as f64;
+        let reward = if rng.gen::<f64>() < p { 1.0 } else { 0.0 };
+        let (status, _) = post_json(
+            app,
+            &format!("/{}/update", id),
+            json!({"arm": arm, "reward": reward}),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (_, v) = get_json(app, &format!("/{}/stats", id)).await;
+    serde_json::from_value(v).unwrap()
+}
@@ -0,0 +1,54 @@
+use axum::http::StatusCode;
+use rustybrain::service::dueling_api::{routes, MAX_ARMS};
+use serde_json::json;
+
+mod common;
+use common::{get_json, post_json};
+
+#[tokio::test]
+async fn dueling_api_finds_preferred_arm() {
+    let app = routes();
+    let (status, v) = post_json(&app, "/", json!({"num_arms": 3, "seed": 5})).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    // Arm 1 always wins; otherwise the lower index wins.
+    for _ in 0..200 {
+        let (status, v) = get_json(&app, &format!("/{}/select", id)).await;
+        assert_eq!(status, StatusCode::OK);
+        let (a, b) = (v["first"].as_u64().unwrap(), v["second"].as_u64().unwrap());
+        let (winner, loser) = if a == 1 || (b != 1 && a < b) { (a, b) } else { (b, a) };
+        let body = json!({"winner": winner, "loser": loser});
+        let (status, _) = post_json(&app, &format!("/{}/update", id), body).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, stats) = get_json(&app, &format!("/{}/stats", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stats["duels"], 200);
+    assert_eq!(stats["best_arm"], 1);
+    assert_eq!(stats["copeland_scores"][1], 2);
+    assert_eq!(stats["wins"][2][1], 0);
+}
+
+#[tokio::test]
+async fn dueling_api_rejects_invalid_requests() {
+    let app = routes();
+    for body in [
+        json!({"num_arms": 0}),
+        json!({"num_arms": MAX_ARMS + 1}),
+        json!({"num_arms": 2, "alpha": 0.5}),
+    ] {
+        let (status, _) = post_json(&app, "/", body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    let (_, v) = post_json(&app, "/", json!({"num_arms": 2})).await;
+    let id = v["id"].as_str().unwrap().to_string();
+    let body = json!({"winner": 0, "loser": 2});
+    let (status, _) = post_json(&app, &format!("/{}/update", id), body).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = get_json(&app, "/unknown/select").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,61 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::bandit::linucb::LinUcb;
+use rustybrain::eval::replay::{replay, replay_contextual, LoggedEvent};
+
+/// Log of a uniformly random policy over Bernoulli arms with `means`.
+fn uniform_log(means: &[f64], events: usize, seed: u64) -> Vec<LoggedEvent> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    (0..events)
+        .map(|_| {
+            let arm = rng.gen_range(0..means.len());
+            let reward = if rng.gen::<f64>() < means[arm] { 1.0 } else { 0.0 };
+            LoggedEvent::new(arm, reward)
+        })
+        .collect()
+}
+
+#[test]
+fn test_replay_estimates_reward_of_learning_and_random_policies() {
+    let means = [0.2, 0.5, 0.8];
+    let log = uniform_log(&means, 30_000, 1);
+
+    let greedy = replay(&mut EpsilonGreedy::new(3, 0.1), &log);
+    let random = replay(&mut EpsilonGreedy::new(3, 1.0), &log);
+    assert_eq!(greedy.events, 30_000);
+    assert!((random.match_rate() - 1.0 / 3.0).abs() < 0.02, "{}", random.match_rate());
+
+    let greedy_mean = greedy.mean_reward().unwrap();
+    let random_mean = random.mean_reward().unwrap();
+    assert!((random_mean - 0.5).abs() < 0.03, "random policy estimate {}", random_mean);
+    assert!(greedy_mean > 0.7, "greedy policy estimate {}", greedy_mean);
+}
+
+#[test]
+fn test_replay_contextual_learns_from_context() {
+    // Arm 0 pays for context [1, 0], arm 1 for [0, 1].
+    let mut rng = StdRng::seed_from_u64(2);
+    let log: Vec<LoggedEvent> = (0..4_000)
+        .map(|_| {
+            let user = rng.gen_range(0..2);
+            let context = if user == 0 { vec![1.0, 0.0] } else { vec![0.0, 1.0] };
+            let arm = rng.gen_range(0..2);
+            LoggedEvent::with_context(context, arm, if arm == user { 1.0 } else { 0.0 })
+        })
+        .collect();
+
+    let contextual = replay_contextual(&mut LinUcb::new(2, 2, 0.5), &log);
+    let blind = replay(&mut EpsilonGreedy::new(2, 0.1), &log);
+    assert!(contextual.mean_reward().unwrap() > 0.9, "{:?}", contextual);
+    assert!(blind.mean_reward().unwrap() < 0.6, "{:?}", blind);
+}
+
+#[test]
+fn test_empty_log_and_json_events() {
+    let report = replay(&mut EpsilonGreedy::new(2, 0.1), &[]);
+    assert_eq!(report.mean_reward(), None);
+    assert_eq!(report.match_rate(), 0.0);
+
+    let event: LoggedEvent = serde_json::from_str(r#"{"arm": 1, "reward": 0.5}"#).unwrap();
+    assert_eq!(event, LoggedEvent::new(1, 0.5));
+}
@@ -0,0 +1,44 @@
+use approx::assert_relative_eq;
+use rustybrain::metrics::ewma_tracker::EwmaTracker;
+
+#[test]
+fn test_empty_tracker_reports_zero() {
+    let t = EwmaTracker::new(0.3);
+    assert_eq!(t.value(), 0.0);
+    assert_eq!(t.count(), 0);
+}
+
+#[test]
+fn test_first_update_initializes_directly() {
+    let mut t = EwmaTracker::new(0.1);
+    t.update(8.0);
+    assert_eq!(t.value(), 8.0);
+}
+
+#[test]
+fn test_higher_alpha_tracks_step_change_faster() {
+    let mut fast = EwmaTracker::new(0.5);
+    let mut slow = EwmaTracker::new(0.05);
+    for _ in 0..20 {
+        fast.update(0.0);
+        slow.update(0.0);
+    }
+    for _ in 0..5 {
+        fast.update(1.0);
+        slow.update(1.0);
+    }
+    assert!(fast.value() > slow.value());
+    assert_relative_eq!(fast.value(), 1.0 - 0.5f64.powi(5), epsilon = 1e-12);
+    assert_relative_eq!(slow.value(), 1.0 - 0.95f64.powi(5), epsilon = 1e-12);
+}
+
+#[test]
+fn test_constant_stream_converges_to_constant() {
+    let mut t = EwmaTracker::new(0.2);
+    t.update(-10.0);
+    for _ in 0..200 {
+        t.update(3.5);
+    }
+    assert_relative_eq!(t.value(), 3.5, epsilon = 1e-12);
+    assert_eq!(t.count(), 201);
+}
@@ -0,0 +1,20 @@
+use rustybrain::service::bandit_api::routes;
+
+mod common;
+use common::run_experiment;
+
+#[tokio::test]
+async fn experiment_is_reproducible_with_same_seed() {
+    for strategy in ["epsilon_greedy", "ucb1"] {
+        let first = run_experiment(&routes(), strategy, 3, 200, 17).await;
+        let second = run_experiment(&routes(), strategy, 3, 200, 17).await;
+        assert_eq!(first, second, "{} stats differ across runs", strategy);
+    }
+}
+
+#[tokio::test]
+async fn experiment_reward_stream_depends_on_seed() {
+    let a = run_experiment(&routes(), "epsilon_greedy", 3, 200, 1).await;
+    let b = run_experiment(&routes(), "epsilon_greedy", 3, 200, 2).await;
+    assert_ne!(a, b);
+}
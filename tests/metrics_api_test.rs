@@ -0,0 +1,84 @@
+use axum::{
+    body::{to_bytes, Body},
+    http::{header, Request, StatusCode},
+    Router,
+};
+use rustybrain::service::{
+    bandit_api::{self, BanditApiConfig},
+    metrics_api, AppState,
+};
+use serde_json::json;
+use tower::ServiceExt; // for `oneshot`
+
+mod common;
+use common::post_json;
+
+fn app() -> Router {
+    let state = AppState::default();
+    Router::new()
+        .nest(
+            "/bandit",
+            bandit_api::routes_with_registry(BanditApiConfig::default(), state.bandits.clone()),
+        )
+        .nest("/metrics", metrics_api::routes(state))
+}
+
+async fn scrape(app: &Router) -> String {
+    let req = Request::get("/metrics/prometheus").body(Body::empty()).unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert!(resp.headers()[header::CONTENT_TYPE]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain"));
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn prometheus_reports_every_bandit_and_arm() {
+    let app = app();
+    let (_, v) = post_json(
+        &app,
+        "/bandit",
+        json!({"strategy":"epsilon_greedy","param":0.1,"num_arms":2}),
+    )
+    .await;
+    let eg = v["id"].as_str().unwrap().to_string();
+    let (_, v) = post_json(&app, "/bandit", json!({"strategy":"ucb1","param":1.0,"num_arms":3})).await;
+    let ucb = v["id"].as_str().unwrap().to_string();
+
+    for (arm, reward) in [(0, 1.0), (0, 0.0), (1, 0.5)] {
+        let body = json!({"arm": arm, "reward": reward});
+        post_json(&app, &format!("/bandit/{eg}/update"), body).await;
+    }
+    post_json(&app, &format!("/bandit/{ucb}/update"), json!({"arm": 2, "reward": 2.0})).await;
+
+    let body = scrape(&app).await;
+    for metric in ["bandit_arm_count", "bandit_arm_value", "bandit_pull_total"] {
+        assert!(body.contains(&format!("# HELP {metric} ")), "{body}");
+    }
+    assert!(body.contains("# TYPE bandit_arm_count gauge\n"));
+    assert!(body.contains("# TYPE bandit_pull_total counter\n"));
+
+    let expected = [
+        format!("bandit_arm_count{{id=\"{eg}\",arm=\"0\"}} 2"),
+        format!("bandit_arm_count{{id=\"{eg}\",arm=\"1\"}} 1"),
+        format!("bandit_arm_value{{id=\"{eg}\",arm=\"0\"}} 0.5"),
+        format!("bandit_arm_value{{id=\"{eg}\",arm=\"1\"}} 0.5"),
+        format!("bandit_pull_total{{id=\"{eg}\"}} 3"),
+        format!("bandit_arm_count{{id=\"{ucb}\",arm=\"0\"}} 0"),
+        format!("bandit_arm_count{{id=\"{ucb}\",arm=\"2\"}} 1"),
+        format!("bandit_arm_value{{id=\"{ucb}\",arm=\"2\"}} 2"),
+        format!("bandit_pull_total{{id=\"{ucb}\"}} 1"),
+    ];
+    for line in &expected {
+        assert!(body.lines().any(|l| l == line), "missing {line:?} in:\n{body}");
+    }
+}
+
+#[tokio::test]
+async fn prometheus_with_no_bandits_has_only_headers() {
+    let body = scrape(&app()).await;
+    assert!(body.lines().all(|l| l.starts_with('#')), "{body}");
+}
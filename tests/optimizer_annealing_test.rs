@@ -0,0 +1,44 @@
+use rustybrain::optimizer::{HillClimber1D, Optimizer, SimulatedAnnealing1D};
+
+/// Two peaks: a local one near x = 0 and a global one, twice as high, near
+/// x = 5. A gentle quadratic penalty keeps the search from drifting away.
+fn bimodal(x: f64) -> f64 {
+    (-(x * x) / 2.0).exp() + 2.0 * (-(x - 5.0).powi(2) / 2.0).exp() - 0.01 * (x - 2.5).powi(2)
+}
+
+fn run(opt: &mut dyn Optimizer, steps: usize) -> f64 {
+    for _ in 0..steps {
+        let x = opt.suggest();
+        opt.observe(bimodal(x));
+    }
+    opt.param()
+}
+
+#[test]
+fn annealing_escapes_local_peak_where_hill_climbing_fails() {
+    let stuck = run(&mut HillClimber1D::new(0.0), 3_000);
+    assert!(stuck.abs() < 0.5, "hill climber left the local peak: {}", stuck);
+
+    let mut annealer = SimulatedAnnealing1D::new(0.0, 2.0, 0.998, 7);
+    let found = run(&mut annealer, 3_000);
+    assert!((found - 5.0).abs() < 0.2, "expected ~5.0, got {}", found);
+    assert!(annealer.temperature() < 0.01);
+}
+
+#[test]
+fn annealing_is_deterministic_given_seed() {
+    let mut a = SimulatedAnnealing1D::new(1.0, 2.0, 0.99, 42);
+    let mut b = SimulatedAnnealing1D::new(1.0, 2.0, 0.99, 42);
+    for _ in 0..200 {
+        let (xa, xb) = (a.suggest(), b.suggest());
+        assert_eq!(xa, xb);
+        a.observe(bimodal(xa));
+        b.observe(bimodal(xb));
+    }
+    assert_eq!(a.param(), b.param());
+
+    // Reset replays the same run.
+    let first = a.param();
+    a.reset(1.0);
+    assert_eq!(run(&mut a, 200), first);
+}
@@ -0,0 +1,44 @@
+use axum::http::StatusCode;
+use rustybrain::service::optimizer_api::routes;
+use serde_json::json;
+
+mod common;
+use common::{get_json, post_json};
+
+// Convex reward surface: maximum at x = 3.0
+fn reward_fn(x: f64) -> f64 {
+    -(x - 3.0).powi(2) + 10.0
+}
+
+#[tokio::test]
+async fn optimizer_api_converges_toward_optimum() {
+    let app = routes();
+    let (status, v) = post_json(&app, "/", json!({"x0": 0.0, "step": 0.5, "min_step": 0.01})).await;
+    assert_eq!(status, StatusCode::OK);
+    let id = v["id"].as_str().unwrap().to_string();
+
+    for _ in 0..100 {
+        let (status, v) = get_json(&app, &format!("/{}/suggest", id)).await;
+        assert_eq!(status, StatusCode::OK);
+        let x = v["x"].as_f64().unwrap();
+        let (status, _) = post_json(&app, &format!("/{}/observe", id), json!({"reward": reward_fn(x)})).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, v) = get_json(&app, &format!("/{}/state", id)).await;
+    assert_eq!(status, StatusCode::OK);
+    let x = v["x"].as_f64().unwrap();
+    assert!((x - 3.0).abs() < 0.2, "expected ~3.0, got {}", x);
+}
+
+#[tokio::test]
+async fn optimizer_api_rejects_bad_input_and_unknown_ids() {
+    let app = routes();
+    let (status, _) = post_json(&app, "/", json!({"x0": 0.0, "step": 0.0, "min_step": 0.01})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = get_json(&app, "/missing/suggest").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = post_json(&app, "/missing/observe", json!({"reward": 1.0})).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,42 @@
+use rustybrain::optimizer::{HillClimberND, OptimizerND};
+
+// Concave reward surface: maximum at (x, y) = (3, -1)
+fn reward_fn(p: &[f64]) -> f64 {
+    -(p[0] - 3.0).powi(2) - (p[1] + 1.0).powi(2)
+}
+
+#[test]
+fn hill_climber_nd_converges_near_optimum() {
+    let mut opt = HillClimberND::with_params(vec![0.0, 0.0], 0.5, 0.01, 1.1, 0.5);
+
+    for _ in 0..200 {
+        let p = opt.suggest();
+        opt.observe(reward_fn(&p));
+    }
+
+    let best = opt.params();
+    assert!((best[0] - 3.0).abs() < 0.2, "expected x ~3.0, got {:?}", best);
+    assert!((best[1] + 1.0).abs() < 0.2, "expected y ~-1.0, got {:?}", best);
+}
+
+#[test]
+fn hill_climber_nd_is_deterministic_and_resettable() {
+    let run = |opt: &mut HillClimberND| {
+        (0..60)
+            .map(|_| {
+                let p = opt.suggest();
+                opt.observe(reward_fn(&p));
+                p
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut a = HillClimberND::new(vec![1.0, 1.0]);
+    let mut b = HillClimberND::new(vec![1.0, 1.0]);
+    let first = run(&mut a);
+    assert_eq!(first, run(&mut b));
+
+    a.reset(vec![1.0, 1.0]);
+    assert_eq!(a.params(), &[1.0, 1.0]);
+    assert_eq!(run(&mut a), first);
+}
@@ -47,4 +47,34 @@ fn hill_climber_is_deterministic() {
     }
 
     assert!((a.param() - b.param()).abs() < 1e-12);
-}
\ No newline at end of file
+}
+
+#[test]
+fn bounded_hill_climber_converges_to_nearest_bound() {
+    // Optimum at 3.0 lies above the upper bound of 1.0.
+    let mut opt = HillClimber1D::with_bounds(0.2, 0.5, 0.01, 1.1, 0.5, 0.0, 1.0);
+    for _ in 0..100 {
+        let x = opt.suggest();
+        opt.observe(reward_fn(x));
+    }
+    assert_eq!(opt.param(), 1.0);
+}
+
+#[test]
+fn bounded_hill_climber_never_leaves_range() {
+    // Optimum at 3.0 lies below the lower bound.
+    let mut opt = HillClimber1D::with_bounds(8.0, 2.0, 0.01, 1.5, 0.5, 5.0, 10.0);
+    for _ in 0..100 {
+        let x = opt.suggest();
+        assert!((5.0..=10.0).contains(&x), "suggestion {} out of range", x);
+        opt.observe(reward_fn(x));
+    }
+    assert_eq!(opt.param(), 5.0);
+}
+
+#[test]
+#[should_panic(expected = "x0 must lie within bounds")]
+fn bounded_hill_climber_rejects_reset_outside_range() {
+    let mut opt = HillClimber1D::with_bounds(0.5, 0.1, 0.01, 1.1, 0.5, 0.0, 1.0);
+    opt.reset(2.0);
+}
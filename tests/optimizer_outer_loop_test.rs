@@ -0,0 +1,34 @@
+use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+use rustybrain::optimizer::{HillClimber1D, OuterLoop};
+
+/// Arm means as a function of the parameter: arm 1 is best everywhere, and
+/// every arm's reward peaks at `x = 2.0`.
+fn arm_reward(x: f64, arm: usize) -> f64 {
+    let base = [0.3, 0.8][arm];
+    base - 0.1 * (x - 2.0).powi(2)
+}
+
+#[test]
+fn test_outer_loop_finds_reward_maximizing_parameter() {
+    let climber = HillClimber1D::with_params(-1.0, 0.5, 0.01, 1.1, 0.5);
+    let mut outer = OuterLoop::new(climber, 200);
+
+    let best = outer.run(60, || EpsilonGreedy::with_seed(2, 0.1, 3), arm_reward);
+
+    assert!((best - 2.0).abs() < 0.1, "expected ~2.0, got {}", best);
+    // The bandit mostly exploits arm 1, so the average approaches its peak of 0.8.
+    let reward = outer.best_reward().unwrap();
+    assert!(reward > 0.7 && reward <= 0.8, "best reward = {}", reward);
+}
+
+#[test]
+fn test_trial_reports_suggested_parameter_and_average() {
+    let mut outer = OuterLoop::new(HillClimber1D::new(0.5), 10);
+    assert_eq!(outer.best_param(), None);
+
+    let (param, average) = outer.trial(|| EpsilonGreedy::new(2, 0.0), |_, arm| arm as f64);
+    assert_eq!(param, 0.5);
+    // Greedy selection breaks the initial tie toward arm 0 and never leaves it.
+    assert_eq!(average, 0.0);
+    assert_eq!(outer.best_param(), Some(0.5));
+}
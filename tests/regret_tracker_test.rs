@@ -0,0 +1,121 @@
+use approx::assert_relative_eq;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rustybrain::bandit::ucb1::Ucb1;
+use rustybrain::metrics::regret::RegretTracker;
+
+/// Runs UCB1 on Bernoulli arms whose rewards are multiplied by `scale`.
+fn run_scaled(scale: f64) -> RegretTracker {
+    let means = [0.3, 0.5, 0.7];
+    let mut agent = Ucb1::with_range(3, 1.0, 0.0, scale);
+    let mut tracker = RegretTracker::new(0.7 * scale).with_reward_range(0.0, scale);
+    let mut rng = StdRng::seed_from_u64(5);
+    for _ in 0..2_000 {
+        let arm = agent.select_arm();
+        let reward = if rng.gen::<f64>() < means[arm] { scale } else { 0.0 };
+        agent.update(arm, reward);
+        tracker.record(means[arm] * scale);
+    }
+    tracker
+}
+
+#[test]
+fn test_normalized_regret_is_scale_free() {
+    let unit = run_scaled(1.0);
+    let hundred = run_scaled(100.0);
+
+    assert_relative_eq!(hundred.cumulative(), 100.0 * unit.cumulative(), max_relative = 1e-9);
+    assert_relative_eq!(unit.normalized_regret(), hundred.normalized_regret(), epsilon = 1e-9);
+    assert!(unit.normalized_regret() > 0.0 && unit.normalized_regret() < 1.0);
+}
+
+#[test]
+fn test_empty_tracker_has_zero_regret() {
+    let tracker = RegretTracker::new(1.0);
+    assert_eq!(tracker.cumulative(), 0.0);
+    assert_eq!(tracker.normalized_regret(), 0.0);
+}
+
+#[test]
+fn test_regret_is_zero_when_always_optimal() {
+    let mut tracker = RegretTracker::new(0.8);
+    for _ in 0..100 {
+        tracker.record(0.8);
+    }
+    assert_eq!(tracker.cumulative(), 0.0);
+    assert_eq!(tracker.average(), 0.0);
+    assert_eq!(tracker.per_step(), &[0.0; 100][..]);
+}
+
+#[test]
+fn test_regret_grows_linearly_with_fixed_gap() {
+    let mut tracker = RegretTracker::new(1.0);
+    for step in 1..=50 {
+        tracker.record(0.75);
+        assert_relative_eq!(tracker.cumulative(), 0.25 * step as f64, epsilon = 1e-12);
+    }
+    assert_eq!(tracker.steps(), 50);
+    assert_relative_eq!(tracker.average(), 0.25, epsilon = 1e-12);
+    assert!(tracker.per_step().iter().all(|&r| r == 0.25));
+}
+
+#[test]
+fn test_clamped_tracker_ignores_rewards_above_optimum() {
+    let mut raw = RegretTracker::new(0.5);
+    let mut clamped = RegretTracker::new_clamped(0.5);
+    for reward in [0.9, 0.3, 0.7, 0.5] {
+        raw.record(reward);
+        clamped.record(reward);
+    }
+    assert_relative_eq!(raw.cumulative(), -0.4, epsilon = 1e-12);
+    assert_relative_eq!(clamped.cumulative(), 0.2, epsilon = 1e-12);
+    assert_eq!(clamped.per_step()[0], 0.0);
+}
+
+#[test]
+fn test_arm_means_record_pseudo_regret_and_curve() {
+    let mut tracker = RegretTracker::with_arm_means(vec![0.2, 0.9, 0.5]);
+    assert_eq!(tracker.optimal_mean(), 0.9);
+    tracker.record_arm(0, 1.0);
+    tracker.record_arm(1, 0.0);
+    tracker.record_arm(2, 1.0);
+    // Realized rewards are ignored when the arm means are known.
+    assert_relative_eq!(tracker.cumulative(), 1.1, epsilon = 1e-12);
+    let curve = tracker.curve();
+    assert_eq!(curve.len(), 3);
+    assert_relative_eq!(curve[0], 0.7, epsilon = 1e-12);
+    assert_relative_eq!(curve[1], 0.7, epsilon = 1e-12);
+    assert_relative_eq!(curve[2], 1.1, epsilon = 1e-12);
+}
+
+#[test]
+fn test_regret_tracked_bandit_emits_sublinear_curve() {
+    use rustybrain::bandit::sim::{simulate, BernoulliEnv};
+    use rustybrain::metrics::regret::RegretTracked;
+
+    let env = BernoulliEnv::new(vec![0.3, 0.5, 0.7]);
+    let tracker = RegretTracker::with_arm_means(env.means().to_vec());
+    let mut agent = RegretTracked::new(Ucb1::new(3, 1.0), tracker);
+    let result = simulate(&mut agent, &env, 5_000, 3);
+
+    let (bandit, tracker) = agent.into_parts();
+    assert_eq!(tracker.steps(), 5_000);
+    assert_eq!(bandit.counts().iter().sum::<u64>(), 5_000);
+    // Same pseudo-regret as the simulator computes.
+    assert_relative_eq!(tracker.cumulative(), result.regret, epsilon = 1e-6);
+    let curve = tracker.curve();
+    assert!(curve[4_999] < 5.0 * curve[999], "{} vs {}", curve[999], curve[4_999]);
+}
+
+#[test]
+fn test_online_tracker_uses_best_estimate() {
+    use rustybrain::bandit::epsilon_greedy::EpsilonGreedy;
+    use rustybrain::bandit::Bandit;
+    use rustybrain::metrics::regret::RegretTracked;
+
+    let mut agent = RegretTracked::new(EpsilonGreedy::new(2, 0.0), RegretTracker::online());
+    agent.update(0, 1.0); // no estimate yet: optimum 0.0
+    agent.update(1, 0.25); // best estimate 1.0
+    agent.update(0, 1.0); // best estimate still 1.0
+    assert_eq!(agent.tracker().per_step(), &[-1.0, 0.75, 0.0]);
+    assert_eq!(agent.tracker().optimal_mean(), 1.0);
+}
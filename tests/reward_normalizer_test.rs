@@ -43,4 +43,274 @@ fn test_window_sliding_behavior() {
     let low = rn.normalized(2.0);
     let high = rn.normalized(4.0);
     assert!(high > low);
-}
\ No newline at end of file
+}
+
+
+#[test]
+fn test_confidence_low_when_empty_or_constant() {
+    let mut rn = RewardNormalizer::new(4);
+    assert_relative_eq!(rn.confidence(), 0.0, epsilon = 1e-12);
+
+    for _ in 0..4 {
+        rn.update(7.0);
+    }
+    assert_relative_eq!(rn.confidence(), 0.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_confidence_high_for_full_varied_window() {
+    let mut rn = RewardNormalizer::new(5);
+    rn.update(1.0);
+    rn.update(5.0);
+    let partial = rn.confidence();
+
+    for r in [2.0, 4.0, 3.0] {
+        rn.update(r);
+    }
+    let full = rn.confidence();
+
+    assert!(full > 0.95, "expected high confidence, got {}", full);
+    assert!(partial < full, "a partially filled window should be less confident");
+    assert!((0.0..=1.0).contains(&full));
+}
+
+#[test]
+fn test_calibrate_uniform_flattens_normal_input() {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    // Box-Muller: standard normal samples from a seeded RNG.
+    let mut rng = StdRng::seed_from_u64(5);
+    let samples: Vec<f64> = (0..1000)
+        .map(|_| {
+            let u1: f64 = rng.gen_range(f64::EPSILON..1.0);
+            let u2: f64 = rng.gen();
+            (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+        })
+        .collect();
+
+    let mut rn = RewardNormalizer::new(1000).calibrate_uniform();
+    for &x in &samples {
+        rn.update(x);
+    }
+
+    let mut bins = [0usize; 10];
+    for &x in &samples {
+        let norm = rn.normalized(x);
+        assert!((0.0..=1.0).contains(&norm));
+        bins[((norm * 10.0) as usize).min(9)] += 1;
+    }
+    for &count in &bins {
+        assert!((80..=120).contains(&count), "histogram not flat: {:?}", bins);
+    }
+}
+
+#[test]
+fn test_calibration_clamps_outside_window() {
+    let mut rn = RewardNormalizer::new(3).calibrate_uniform();
+    rn.update(1.0);
+    rn.update(2.0);
+    rn.update(3.0);
+    assert_relative_eq!(rn.normalized(0.0), 0.0, epsilon = 1e-12);
+    assert_relative_eq!(rn.normalized(2.0), 0.5, epsilon = 1e-12);
+    assert_relative_eq!(rn.normalized(9.0), 1.0, epsilon = 1e-12);
+}
+
+#[test]
+fn test_long_stream_matches_fresh_window() {
+    let mut streamed = RewardNormalizer::new(50);
+    for i in 0..10_000u64 {
+        streamed.update(((i * 7919) % 1000) as f64);
+    }
+    let mut fresh = RewardNormalizer::new(50);
+    for i in 9_950..10_000u64 {
+        fresh.update(((i * 7919) % 1000) as f64);
+    }
+    for probe in [0.0, 250.0, 500.0, 999.0] {
+        assert_eq!(streamed.normalized(probe), fresh.normalized(probe));
+    }
+    assert_eq!(streamed.confidence(), fresh.confidence());
+}
+
+#[test]
+fn test_capacity_is_allocated_up_front() {
+    let mut rn = RewardNormalizer::new(10);
+    let capacity = rn.capacity();
+    assert!(capacity >= 20);
+    for i in 0..10_000 {
+        rn.update(i as f64);
+    }
+    assert_eq!(rn.capacity(), capacity);
+}
+
+#[test]
+fn test_incremental_std_matches_full_recomputation() {
+    let mut rn = RewardNormalizer::new(100);
+    let mut x: u64 = 12345;
+    let mut window = std::collections::VecDeque::new();
+    for i in 0..100_000u64 {
+        // Cheap deterministic pseudo-random stream with a slowly drifting level.
+        x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        let r = 1_000.0 + (i / 1_000) as f64 + (x >> 40) as f64 / (1u64 << 24) as f64;
+        rn.update(r);
+        window.push_back(r);
+        if window.len() > 100 {
+            window.pop_front();
+        }
+    }
+
+    let n = window.len() as f64;
+    let mean = window.iter().sum::<f64>() / n;
+    let std = (window.iter().map(|&v| (v - mean).powi(2)).sum::<f64>() / n).sqrt();
+    assert_relative_eq!(rn.mean(), mean, max_relative = 1e-9);
+    assert_relative_eq!(rn.std(), std, max_relative = 1e-9);
+}
+
+#[test]
+fn test_constant_window_after_variation_is_neutral() {
+    let mut rn = RewardNormalizer::new(3);
+    for r in [0.1, 7.3, 2.9, 5.0, 5.0, 5.0] {
+        rn.update(r);
+    }
+    assert_eq!(rn.normalized(9.0), 0.5);
+    assert_eq!(RewardNormalizer::new(3).normalized(1.0), 0.5);
+}
+
+#[test]
+fn test_asymmetric_positive_outlier_saturates_gently() {
+    let mut rn = RewardNormalizer::with_asymmetric(4, 0.5, 2.0);
+    for r in [8.0, 12.0, 8.0, 12.0] {
+        rn.update(r);
+    }
+    // mean = 10, std = 2: probes are at z = +3 and z = -3.
+    let high = rn.normalized(16.0);
+    let low = rn.normalized(4.0);
+    assert!(1.0 - high > low, "high = {}, low = {}", high, low);
+    assert_relative_eq!(high, 1.0 / (1.0 + (-1.5f64).exp()), epsilon = 1e-12);
+    assert_relative_eq!(low, 1.0 / (1.0 + 6.0f64.exp()), epsilon = 1e-12);
+
+    assert_eq!(rn.normalized(10.0), 0.5);
+    assert_eq!(RewardNormalizer::with_asymmetric(4, 0.5, 2.0).normalized(3.0), 0.5);
+}
+
+#[test]
+fn test_streaming_matches_batch_on_random_sequences() {
+    use rand::{Rng, SeedableRng};
+
+    let mut rng = rand_chacha::ChaCha12Rng::seed_from_u64(2024);
+    for _ in 0..50 {
+        let window = rng.gen_range(1..40);
+        let offset = rng.gen_range(-1e3..1e3);
+        let scale = 10f64.powi(rng.gen_range(-3..4));
+        let mut rn = RewardNormalizer::new(window);
+        let mut reference = std::collections::VecDeque::new();
+
+        for _ in 0..rng.gen_range(1..500) {
+            // Occasionally repeat the previous reward to exercise zero spread.
+            let r = match reference.back() {
+                Some(&prev) if rng.gen_bool(0.1) => prev,
+                _ => offset + scale * rng.gen_range(-1.0..1.0),
+            };
+            rn.update(r);
+            reference.push_back(r);
+            if reference.len() > window {
+                reference.pop_front();
+            }
+
+            let n = reference.len() as f64;
+            let mean = reference.iter().sum::<f64>() / n;
+            let var = reference.iter().map(|&v| (v - mean).powi(2)).sum::<f64>() / n;
+            // Streaming updates lose about n ulps of the largest reward, and removing
+            // a sample cancels terms of size max², so the variance also gets an
+            // absolute slack on top of a tight tolerance relative to itself.
+            let max = reference.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+            let ulps = n * f64::EPSILON;
+            let mean_tol = 64.0 * ulps * max;
+            let var_tol = 1e-9 * var + 1024.0 * ulps * max * max;
+            assert!((rn.mean() - mean).abs() <= mean_tol, "{} vs {}", rn.mean(), mean);
+            assert!((rn.std().powi(2) - var).abs() <= var_tol, "{} vs {}", rn.std(), var.sqrt());
+            assert_eq!(rn.validate(), Ok(()));
+        }
+    }
+}
+
+#[test]
+fn test_min_max_mode_rescales_linearly() {
+    use rustybrain::reward_normalizer::NormalizeMode;
+
+    let mut rn = RewardNormalizer::with_mode(4, NormalizeMode::MinMax);
+    assert_eq!(rn.normalized(3.0), 0.5);
+    for r in [10.0, 20.0, 30.0, 50.0] {
+        rn.update(r);
+    }
+    assert_eq!(rn.normalized(20.0), 0.25);
+    assert_eq!(rn.normalized(50.0), 1.0);
+    assert_eq!(rn.normalized(-5.0), 0.0);
+    assert_eq!(rn.normalized(80.0), 1.0);
+
+    // Constant window is degenerate.
+    for _ in 0..4 {
+        rn.update(7.0);
+    }
+    assert_eq!(rn.normalized(7.0), 0.5);
+    assert_eq!(rn.normalized(100.0), 0.5);
+}
+
+#[test]
+fn test_default_mode_is_sigmoid() {
+    use rustybrain::reward_normalizer::NormalizeMode;
+
+    let mut a = RewardNormalizer::new(3);
+    let mut b = RewardNormalizer::with_mode(3, NormalizeMode::Sigmoid);
+    for r in [1.0, 4.0, 2.0] {
+        a.update(r);
+        b.update(r);
+    }
+    assert_eq!(NormalizeMode::default(), NormalizeMode::Sigmoid);
+    assert_eq!(a.normalized(3.0), b.normalized(3.0));
+}
+
+#[test]
+fn test_denormalize_round_trips() {
+    let mut rn = RewardNormalizer::new(50);
+    for i in 0..50 {
+        rn.update(100.0 + (i as f64 * 0.7).sin() * 15.0);
+    }
+    for r in [80.0, 95.5, 100.0, 104.2, 118.0] {
+        assert_relative_eq!(rn.denormalize(rn.normalized(r)), r, epsilon = 1e-6);
+    }
+
+    let mut asym = RewardNormalizer::with_asymmetric(4, 0.5, 2.0);
+    for r in [1.0, 2.0, 3.0, 4.0] {
+        asym.update(r);
+    }
+    for r in [0.5, 2.0, 3.7] {
+        assert_relative_eq!(asym.denormalize(asym.normalized(r)), r, epsilon = 1e-6);
+    }
+}
+
+#[test]
+fn test_denormalize_edge_cases() {
+    let mut rn = RewardNormalizer::new(4);
+    assert_eq!(rn.denormalize(0.7), 0.0);
+
+    rn.update(5.0);
+    rn.update(5.0);
+    assert_eq!(rn.denormalize(0.9), 5.0);
+
+    rn.update(7.0);
+    assert!(rn.denormalize(0.0).is_finite());
+    assert!(rn.denormalize(1.0).is_finite());
+    assert!(rn.denormalize(0.0) < rn.denormalize(1.0));
+}
+
+#[test]
+fn test_denormalize_min_max_mode() {
+    use rustybrain::reward_normalizer::NormalizeMode;
+
+    let mut rn = RewardNormalizer::with_mode(3, NormalizeMode::MinMax);
+    for r in [2.0, 6.0, 10.0] {
+        rn.update(r);
+    }
+    assert_relative_eq!(rn.denormalize(0.25), 4.0, epsilon = 1e-9);
+    assert_relative_eq!(rn.denormalize(rn.normalized(7.5)), 7.5, epsilon = 1e-9);
+}
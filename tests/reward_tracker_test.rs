@@ -33,4 +33,277 @@ fn test_window_rolls_over() {
     assert_relative_eq!(rt.min(), 3.0, epsilon = 1e-12);
     assert_relative_eq!(rt.max(), 5.0, epsilon = 1e-12);
     assert_eq!(rt.count(), 3);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_steps_to_reach_on_ramp() {
+    let mut rt = RewardTracker::new(3);
+    for i in 1..=10 {
+        rt.update(i as f64);
+    }
+    // Windowed means: 1, 1.5, 2, 3, 4, 5, ... so 5.0 is first reached at update 6.
+    assert_eq!(rt.steps_to_reach(5.0), Some(6));
+    assert_eq!(rt.steps_to_reach(1.0), Some(1));
+    assert_eq!(rt.steps_to_reach(4.5), Some(6));
+    assert_eq!(rt.steps_to_reach(100.0), None);
+    assert_eq!(rt.total_updates(), 10);
+}
+
+#[test]
+fn test_steps_to_reach_keeps_first_crossing() {
+    let mut rt = RewardTracker::new(1);
+    rt.update(1.0);
+    rt.update(0.0); // mean drops back below the target
+    rt.update(1.0);
+    assert_eq!(rt.steps_to_reach(1.0), Some(1));
+}
+
+#[test]
+fn test_downsample_ramp_preserves_length_and_monotonicity() {
+    let mut rt = RewardTracker::new(1000);
+    for i in 0..1000 {
+        rt.update(i as f64);
+    }
+    let points = rt.downsample(30);
+    assert_eq!(points.len(), 30);
+    assert!(points.windows(2).all(|w| w[0] < w[1]), "downsampled ramp must stay increasing");
+    assert!(points[0] >= 0.0 && points[29] <= 999.0);
+}
+
+#[test]
+fn test_downsample_returns_raw_values_when_small() {
+    let mut rt = RewardTracker::new(5);
+    rt.update(2.0);
+    rt.update(1.0);
+    assert_eq!(rt.downsample(10), vec![2.0, 1.0]);
+    assert!(rt.downsample(0).is_empty());
+}
+
+#[test]
+fn test_hybrid_reacts_faster_than_window() {
+    let mut window = RewardTracker::new(10);
+    let mut hybrid = RewardTracker::hybrid(10, 0.5);
+    for r in std::iter::repeat_n(0.0, 10).chain(std::iter::repeat_n(1.0, 3)) {
+        window.update(r);
+        hybrid.update(r);
+    }
+    assert_relative_eq!(window.mean(), 0.3, epsilon = 1e-12);
+    assert!(hybrid.hybrid_mean() > 0.8, "got {}", hybrid.hybrid_mean());
+}
+
+#[test]
+fn test_hybrid_ignores_evicted_samples() {
+    let alpha = 0.2;
+    let mut hybrid = RewardTracker::hybrid(10, alpha);
+    let mut ewma = 1000.0;
+    hybrid.update(1000.0);
+    for _ in 0..10 {
+        hybrid.update(1.0);
+        ewma = alpha * 1.0 + (1.0 - alpha) * ewma;
+    }
+    // The outlier is out of the window, so it has no influence at all...
+    assert_relative_eq!(hybrid.hybrid_mean(), 1.0, epsilon = 1e-12);
+    // ...whereas an unbounded EWMA still carries it.
+    assert!(ewma > 50.0);
+}
+
+#[test]
+fn test_hybrid_mean_falls_back_to_mean() {
+    let mut rt = RewardTracker::new(3);
+    rt.update(1.0);
+    rt.update(2.0);
+    assert_relative_eq!(rt.hybrid_mean(), rt.mean(), epsilon = 1e-12);
+    assert_eq!(RewardTracker::hybrid(3, 0.5).hybrid_mean(), 0.0);
+}
+
+#[test]
+fn test_latest_zscore_flags_spike() {
+    let mut rt = RewardTracker::new(10);
+    for r in [1.0, 1.1, 0.9, 1.0, 1.05, 0.95] {
+        rt.update(r);
+    }
+    assert!(rt.latest_zscore().abs() < 1.0);
+    rt.update(5.0);
+    assert!(rt.latest_zscore() > 10.0, "got {}", rt.latest_zscore());
+}
+
+#[test]
+fn test_latest_zscore_degenerate_cases() {
+    let mut rt = RewardTracker::new(5);
+    assert_eq!(rt.latest_zscore(), 0.0);
+    rt.update(1.0);
+    rt.update(2.0);
+    assert_eq!(rt.latest_zscore(), 0.0); // only one earlier value
+    let mut flat = RewardTracker::new(5);
+    for r in [3.0, 3.0, 3.0, 9.0] {
+        flat.update(r);
+    }
+    assert_eq!(flat.latest_zscore(), 0.0); // zero spread in history
+}
+
+#[test]
+fn test_autocorrelation_periodic_vs_noise() {
+    let mut periodic = RewardTracker::new(100);
+    for i in 0..100 {
+        periodic.update([1.0, 3.0, 2.0, 0.0][i % 4]);
+    }
+    assert!(periodic.autocorrelation(4) > 0.99);
+    assert!(periodic.autocorrelation(2) < -0.5);
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut noise = RewardTracker::new(1000);
+    for _ in 0..1000 {
+        noise.update(rng.gen());
+    }
+    assert!(noise.autocorrelation(4).abs() < 0.1);
+
+    assert_eq!(RewardTracker::new(10).autocorrelation(1), 0.0);
+}
+
+#[test]
+fn test_recommended_sample_rate_tracks_variance() {
+    let mut noisy = RewardTracker::new(1000);
+    let mut calm = RewardTracker::new(1000);
+    for i in 0..1000 {
+        let sign = if i % 2 == 0 { 1.0 } else { -1.0 };
+        noisy.update(10.0 + 5.0 * sign);
+        calm.update(10.0 + 0.5 * sign);
+    }
+
+    let target = 0.25;
+    let high = noisy.recommended_sample_rate(target);
+    let low = calm.recommended_sample_rate(target);
+    assert!(high > low, "high = {}, low = {}", high, low);
+    // σ² ≈ 25 needs ~400 of 1000 samples; σ² ≈ 0.25 needs ~4.
+    assert_relative_eq!(high, 0.4, epsilon = 0.01);
+    assert_relative_eq!(low, 0.004, epsilon = 0.001);
+
+    assert_eq!(RewardTracker::new(5).recommended_sample_rate(target), 1.0);
+}
+
+#[test]
+fn test_long_stream_matches_reference_window() {
+    use std::collections::VecDeque;
+
+    let mut tracker = RewardTracker::new(50);
+    let mut reference: VecDeque<f64> = VecDeque::new();
+    for i in 0..10_000u64 {
+        let reward = ((i * 7919) % 1000) as f64 / 10.0;
+        tracker.update(reward);
+        reference.push_back(reward);
+        if reference.len() > 50 {
+            reference.pop_front();
+        }
+
+        let expected: Vec<f64> = reference.iter().copied().collect();
+        assert_eq!(tracker.values(), &expected[..], "step {}", i);
+        assert_eq!(tracker.count(), expected.len());
+        assert_eq!(tracker.min(), expected.iter().copied().fold(f64::INFINITY, f64::min));
+        assert_eq!(tracker.max(), expected.iter().copied().fold(f64::NEG_INFINITY, f64::max));
+        assert_eq!(tracker.mean(), expected.iter().sum::<f64>() / expected.len() as f64);
+    }
+    assert_eq!(tracker.total_updates(), 10_000);
+}
+
+#[test]
+fn test_capacity_constant_across_million_updates() {
+    let mut tracker = RewardTracker::new(64);
+    tracker.reserve(192);
+    for i in 0..64 {
+        tracker.update(i as f64);
+    }
+    let capacity = tracker.capacity();
+    assert!(capacity >= 256);
+
+    for i in 0..1_000_000u64 {
+        tracker.update((i % 97) as f64);
+        assert_eq!(tracker.capacity(), capacity);
+    }
+    assert_eq!(tracker.count(), 64);
+}
+
+#[test]
+fn test_merge_ordered_interleaves_shards_by_sequence() {
+    let mut even = RewardTracker::new(10);
+    let mut odd = RewardTracker::new(10);
+    for seq in 0..12u64 {
+        let shard = if seq % 2 == 0 { &mut even } else { &mut odd };
+        shard.update_at(seq as f64 * 10.0, seq);
+    }
+
+    let merged = RewardTracker::merge_ordered(&[&odd, &even]);
+    let expected: Vec<f64> = (0..12).map(|seq| seq as f64 * 10.0).collect();
+    assert_eq!(merged, expected);
+
+    // Eviction drops the oldest sample together with its sequence number.
+    let mut small = RewardTracker::new(2);
+    for seq in [5, 1, 9] {
+        small.update_at(seq as f64, seq);
+    }
+    assert_eq!(RewardTracker::merge_ordered(&[&small]), vec![1.0, 9.0]);
+}
+
+#[test]
+fn test_single_element_window() {
+    let mut rt = RewardTracker::new(1);
+    rt.update(-4.0);
+    assert_eq!(rt.min(), -4.0);
+    assert_eq!(rt.max(), -4.0);
+    rt.update(7.5);
+    assert_eq!(rt.min(), 7.5);
+    assert_eq!(rt.max(), 7.5);
+}
+
+#[test]
+fn test_nan_rewards_are_skipped() {
+    let mut rt = RewardTracker::new(5);
+    for r in [f64::NAN, 2.0, f64::NAN, -1.0, 3.0, f64::NAN] {
+        rt.update(r);
+    }
+    assert_eq!(rt.count(), 3);
+    assert_eq!(rt.total_updates(), 3);
+    assert_eq!(rt.min(), -1.0);
+    assert_eq!(rt.max(), 3.0);
+    assert_relative_eq!(rt.mean(), 4.0 / 3.0, epsilon = 1e-12);
+
+    let mut only_nan = RewardTracker::new(3);
+    only_nan.update(f64::NAN);
+    assert_eq!(only_nan.count(), 0);
+    assert_eq!(only_nan.min(), 0.0);
+    assert_eq!(only_nan.max(), 0.0);
+}
+
+#[test]
+fn test_percentiles_of_known_dataset() {
+    let mut rt = RewardTracker::new(10);
+    // Insert out of order: the window is sorted internally.
+    for r in [7, 3, 10, 1, 5, 9, 2, 8, 4, 6] {
+        rt.update(r as f64);
+    }
+    assert_relative_eq!(rt.percentile(0.5), 5.5, epsilon = 1e-12);
+    assert_relative_eq!(rt.median(), 5.5, epsilon = 1e-12);
+    assert_relative_eq!(rt.percentile(0.9), 9.1, epsilon = 1e-12);
+    assert_relative_eq!(rt.percentile(0.25), 3.25, epsilon = 1e-12);
+    assert_eq!(rt.percentile(0.0), rt.min());
+    assert_eq!(rt.percentile(1.0), rt.max());
+}
+
+#[test]
+fn test_percentile_edge_cases() {
+    let mut rt = RewardTracker::new(3);
+    assert_eq!(rt.percentile(0.99), 0.0);
+    rt.update(4.0);
+    assert_eq!(rt.percentile(0.3), 4.0);
+    // Only the window counts.
+    for r in [100.0, 1.0, 2.0, 3.0] {
+        rt.update(r);
+    }
+    assert_eq!(rt.percentile(1.0), 3.0);
+}
+
+#[test]
+#[should_panic(expected = "q must be in")]
+fn test_percentile_rejects_out_of_range_q() {
+    RewardTracker::new(3).percentile(1.5);
+}
@@ -0,0 +1,20 @@
+//! Guards the public `service` module tree used by `main.rs`.
+
+use axum::{body::Body, http::Request, http::StatusCode, Router};
+use rustybrain::service::{bandit_api, dueling_api, optimizer_api, training_api};
+use tower::ServiceExt; // for `oneshot`
+
+#[tokio::test]
+async fn service_routers_are_exported_and_nest() {
+    let app = Router::new()
+        .nest("/bandit", bandit_api::routes())
+        .nest("/duel", dueling_api::routes())
+        .nest("/optimizer", optimizer_api::routes())
+        .nest("/train", training_api::routes());
+
+    let req = Request::get("/optimizer/unknown/state")
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
@@ -0,0 +1,81 @@
+use std::{
+    io::Write,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    body::{Body, to_bytes},
+    http::{Request, StatusCode},
+};
+use rustybrain::service::bandit_api::routes;
+use serde_json::{json, Value};
+use tower::ServiceExt; // for `oneshot`
+
+/// Writer that appends formatted log lines to a shared buffer.
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Capture {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn select_emits_event_with_bandit_id() {
+    let capture = Capture::default();
+    let writer = capture.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing::Level::DEBUG)
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let app = routes();
+    let req = Request::post("/")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({"strategy":"ucb1","param":1.0,"num_arms":2}).to_string(),
+        ))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let v: Value = serde_json::from_slice(&bytes).unwrap();
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let req = Request::get(format!("/{}/select", id)).body(Body::empty()).unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let logs = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+    let select_line = logs
+        .lines()
+        .find(|l| l.contains("op=\"select\""))
+        .expect("select event not emitted");
+    assert!(select_line.contains(&id), "select event missing bandit id: {}", select_line);
+    assert!(select_line.contains("DEBUG"));
+}
+
+#[tokio::test]
+async fn unknown_id_emits_warning() {
+    let capture = Capture::default();
+    let writer = capture.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_ansi(false)
+        .with_writer(move || writer.clone())
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let req = Request::get("/missing-id/select").body(Body::empty()).unwrap();
+    let resp = routes().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    let logs = String::from_utf8(capture.0.lock().unwrap().clone()).unwrap();
+    assert!(logs.lines().any(|l| l.contains("WARN") && l.contains("missing-id")));
+}
@@ -28,7 +28,7 @@ async fn training_api_happy_path() {
     // 2️⃣ Send mock metrics
     let req = Request::post("/metrics")
         .header("content-type", "application/json")
-        .body(Body::from(json!({"loss": 0.3, "reward": 0.7}).to_string()))
+        .body(Body::from(json!({"id": id, "loss": 0.3, "reward": 0.7}).to_string()))
         .unwrap();
     let resp = app.clone().oneshot(req).await.unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
@@ -73,7 +73,7 @@ async fn training_api_metrics_accumulate() {
     for r in [0.1, 0.5, 0.9] {
         let req = Request::post("/metrics")
             .header("content-type", "application/json")
-            .body(Body::from(json!({"loss": 1.0 - r, "reward": r}).to_string()))
+            .body(Body::from(json!({"id": id, "loss": 1.0 - r, "reward": r}).to_string()))
             .unwrap();
         let resp = app.clone().oneshot(req).await.unwrap();
         assert_eq!(resp.status(), StatusCode::OK);
@@ -86,4 +86,297 @@ async fn training_api_metrics_accumulate() {
         .unwrap();
     let resp = app.clone().oneshot(req).await.unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
-}
\ No newline at end of file
+}
+async fn poll_status(app: &axum::Router, id: &str, until: &str) -> Value {
+    for _ in 0..100 {
+        let req = Request::get(format!("/{}/status", id))
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let v: Value = serde_json::from_slice(&bytes).unwrap();
+        if v["status"] == until {
+            return v;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+    panic!("job {} never reached status {}", id, until);
+}
+
+#[tokio::test]
+async fn training_api_nonexistent_command_reports_failed() {
+    let app = routes();
+    let req = Request::post("/start")
+        .header("content-type", "application/json")
+        .body(Body::from(
+            json!({"cmd": "rustybrain-no-such-binary --epochs 1"}).to_string(),
+        ))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let v: Value = serde_json::from_slice(&bytes).unwrap();
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let status = poll_status(&app, &id, "failed").await;
+    let error = status["error"].as_str().unwrap();
+    assert!(error.contains("command not found"), "unexpected error: {}", error);
+    assert!(error.contains("rustybrain-no-such-binary"));
+}
+
+#[tokio::test]
+async fn training_api_successful_command_reports_completed() {
+    let app = routes();
+    let req = Request::post("/start")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"cmd": "true"}).to_string()))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let v: Value = serde_json::from_slice(&bytes).unwrap();
+    let id = v["id"].as_str().unwrap().to_string();
+
+    let status = poll_status(&app, &id, "completed").await;
+    assert!(status["error"].is_null());
+}
+
+#[tokio::test]
+async fn training_api_metrics_flag_spike_as_anomaly() {
+    let app = routes();
+    let req = Request::post("/start")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"cmd": "sleep 5"}).to_string()))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let id = serde_json::from_slice::<Value>(&bytes).unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string();
+
+    let mut last = Value::Null;
+    for r in [0.50, 0.52, 0.49, 0.51, 0.50, 0.48, 0.51, 5.0] {
+        let req = Request::post("/metrics")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({"id": id, "reward": r}).to_string()))
+            .unwrap();
+        let resp = app.clone().oneshot(req).await.unwrap();
+        let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        let v: Value = serde_json::from_slice(&bytes).unwrap();
+        if r < 1.0 {
+            assert_eq!(v["anomaly"], false, "steady reward {} flagged: {}", r, v);
+        }
+        last = v;
+    }
+    assert_eq!(last["anomaly"], true);
+    assert!(last["zscore"].as_f64().unwrap() > 10.0);
+
+    let req = Request::post("/stop")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"id": id}).to_string()))
+        .unwrap();
+    app.clone().oneshot(req).await.unwrap();
+}
+
+#[tokio::test]
+async fn training_api_shutdown_drains_running_jobs() {
+    use rustybrain::service::training_api::{routes_with_shutdown, TrainingApiConfig};
+
+    let (app, shutdown) = routes_with_shutdown(TrainingApiConfig::default());
+    let mut ids = Vec::new();
+    for _ in 0..3 {
+        let id = start(&app, "sleep 30").await;
+        poll_status(&app, &id, "running").await;
+        ids.push(id);
+    }
+    let id = &ids[0];
+
+    let started = std::time::Instant::now();
+    assert_eq!(shutdown.drain().await, 3);
+    assert!(started.elapsed() < std::time::Duration::from_secs(2));
+
+    let req = Request::get(format!("/{}/status", id))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(
+        resp.status(),
+        StatusCode::NOT_FOUND,
+        "registry should be empty"
+    );
+    assert_eq!(shutdown.drain().await, 0);
+}
+
+#[tokio::test]
+async fn training_api_parses_rewards_from_stdout() {
+    let app = routes();
+    let cmd = r#"echo 'reward=0.5'; echo 'epoch 2 loss=0.1 reward=1.5'; echo '{"reward": 1.0}'; echo 'mean_reward=9'"#;
+    let req = Request::post("/start")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "cmd": cmd }).to_string()))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let v: Value = serde_json::from_slice(&bytes).unwrap();
+    let id = v["id"].as_str().unwrap().to_string();
+
+    // Status only changes after stdout is fully consumed.
+    poll_status(&app, &id, "completed").await;
+
+    let req = Request::get(format!("/{}/stats", id))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let stats: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(stats["count"], 3);
+    assert_eq!(stats["mean"], 1.0);
+    assert_eq!(stats["min"], 0.5);
+    assert_eq!(stats["max"], 1.5);
+
+    let req = Request::get("/no-such-job/stats").body(Body::empty()).unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn training_api_skips_invalid_utf8_and_non_finite_rewards() {
+    let app = routes();
+    let cmd = r#"printf 'reward=1\n\377\376 garbage\nreward=nan\nreward=inf\n{"reward": 1e999}\nreward=3\n'"#;
+    let id = start(&app, cmd).await;
+    poll_status(&app, &id, "completed").await;
+
+    let req = Request::get(format!("/{}/stats", id))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let stats: Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(stats["count"], 2);
+    assert_eq!(stats["mean"], 2.0);
+}
+
+/// Starts `cmd` and returns the new job's id.
+async fn start(app: &axum::Router, cmd: &str) -> String {
+    let req = Request::post("/start")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({ "cmd": cmd }).to_string()))
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice::<Value>(&bytes).unwrap()["id"]
+        .as_str()
+        .unwrap()
+        .to_string()
+}
+
+async fn post_metrics(app: &axum::Router, body: Value) -> StatusCode {
+    let req = Request::post("/metrics")
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    app.clone().oneshot(req).await.unwrap().status()
+}
+
+async fn reward_count(app: &axum::Router, id: &str) -> u64 {
+    let req = Request::get(format!("/{}/stats", id))
+        .body(Body::empty())
+        .unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    serde_json::from_slice::<Value>(&bytes).unwrap()["count"]
+        .as_u64()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn training_api_metrics_route_to_single_job() {
+    let app = routes();
+    let a = start(&app, "sleep 5").await;
+    let b = start(&app, "sleep 5").await;
+
+    assert_eq!(post_metrics(&app, json!({"id": a, "reward": 0.4})).await, StatusCode::OK);
+    assert_eq!(reward_count(&app, &a).await, 1);
+    assert_eq!(reward_count(&app, &b).await, 0);
+
+    assert_eq!(
+        post_metrics(&app, json!({"reward": 0.6, "broadcast": true})).await,
+        StatusCode::OK
+    );
+    assert_eq!(reward_count(&app, &a).await, 2);
+    assert_eq!(reward_count(&app, &b).await, 1);
+
+    assert_eq!(
+        post_metrics(&app, json!({"id": "no-such-job", "reward": 0.1})).await,
+        StatusCode::NOT_FOUND
+    );
+    assert_eq!(post_metrics(&app, json!({"reward": 0.1})).await, StatusCode::BAD_REQUEST);
+
+    for id in [a, b] {
+        let req = Request::post("/stop")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "id": id }).to_string()))
+            .unwrap();
+        app.clone().oneshot(req).await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn server_stops_on_shutdown_signal() {
+    use rustybrain::service::training_api::start_server_with_shutdown;
+
+    let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+    let server = start_server_with_shutdown("127.0.0.1:0", async {
+        let _ = rx.await;
+    });
+    let signal = async {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        tx.send(()).unwrap();
+    };
+    let (result, ()) = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+        tokio::join!(server, signal)
+    })
+    .await
+    .expect("server did not shut down");
+    assert!(result.is_ok());
+}
+
+#[tokio::test]
+async fn training_api_status_and_listing() {
+    let app = routes();
+    let done = start(&app, "echo done; echo reward=0.5").await;
+    let running = start(&app, "sleep 30").await;
+
+    let status = poll_status(&app, &done, "completed").await;
+    assert_eq!(status["id"], done.as_str());
+    assert_eq!(status["reward_count"], 1);
+
+    let req = Request::get("/").body(Body::empty()).unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+    let jobs: Value = serde_json::from_slice(&bytes).unwrap();
+    let jobs = jobs.as_array().unwrap();
+    assert_eq!(jobs.len(), 2);
+    let find = |id: &str| jobs.iter().find(|j| j["id"] == id).unwrap().clone();
+    assert_eq!(find(&done)["status"], "completed");
+    assert_eq!(find(&running)["status"], "running");
+
+    // Stopped jobs stay listed as aborted.
+    let req = Request::post("/stop")
+        .header("content-type", "application/json")
+        .body(Body::from(json!({"id": running}).to_string()))
+        .unwrap();
+    app.clone().oneshot(req).await.unwrap();
+    poll_status(&app, &running, "aborted").await;
+}
+
+#[tokio::test]
+async fn training_api_status_unknown_id() {
+    let app = routes();
+    let req = Request::get("/no-such-job/status").body(Body::empty()).unwrap();
+    let resp = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}